@@ -0,0 +1,42 @@
+//! Lowering of surface syntax that later phases do not handle themselves.
+//!
+//! [`desugar_program`] runs after parsing and rewrites:
+//!
+//! - `x += y` (and `-=`, `*=`, `/=`) into `x = x + y`.
+
+use std::mem;
+
+use crate::nodes::{Expr, Node, Program, Statement};
+use crate::visit::{MutVisitor, walk_statement_mut};
+
+/// Lowers every desugarable construct in `program` in place.
+pub fn desugar_program(program: &mut Program) {
+    CompoundAssignments.visit_program_mut(program);
+}
+
+struct CompoundAssignments;
+
+impl MutVisitor for CompoundAssignments {
+    fn visit_statement_mut(&mut self, stmt: &mut Node<Statement>) {
+        walk_statement_mut(self, stmt);
+
+        if !matches!(stmt.as_ref(), Statement::CompoundAssignment { .. }) {
+            return;
+        }
+        let span = *stmt.span();
+        if let Statement::CompoundAssignment { name, op, expr } =
+            mem::replace(stmt.as_mut(), Statement::Pass)
+        {
+            let target = Node::new(Expr::Identifier(name.as_ref().clone()), *name.span());
+            let expr = Node::new(
+                Expr::Binary {
+                    op,
+                    left: Box::new(target),
+                    right: Box::new(expr),
+                },
+                span,
+            );
+            *stmt.as_mut() = Statement::Assignment { name, expr };
+        }
+    }
+}
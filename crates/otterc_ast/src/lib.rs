@@ -1,3 +1,6 @@
+pub mod desugar;
 pub mod nodes;
+pub mod visit;
 
 pub use nodes::{BinaryOp, Expr, Function, Literal, Program, Statement, UseImport};
+pub use visit::{MutVisitor, Visitor};
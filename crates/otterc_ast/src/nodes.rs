@@ -163,13 +163,30 @@ pub enum Type {
 #[derive(Debug, Clone)]
 pub struct Param {
     pub name: Node<String>,
+    /// For a variadic parameter this is the element type of the collected list
     pub ty: Option<Node<Type>>,
     pub default: Option<Node<Expr>>,
+    /// `*args`: collects the remaining positional arguments into a list
+    pub variadic: bool,
 }
 
 impl Param {
     pub fn new(name: Node<String>, ty: Option<Node<Type>>, default: Option<Node<Expr>>) -> Self {
-        Self { name, ty, default }
+        Self {
+            name,
+            ty,
+            default,
+            variadic: false,
+        }
+    }
+
+    pub fn new_variadic(name: Node<String>, ty: Option<Node<Type>>) -> Self {
+        Self {
+            name,
+            ty,
+            default: None,
+            variadic: true,
+        }
     }
 }
 
@@ -227,6 +244,13 @@ pub enum Statement {
         name: Node<String>,
         expr: Node<Expr>,
     },
+    /// `name op= expr`, as written. Lowered to an [`Statement::Assignment`]
+    /// by [`crate::desugar`] before type checking.
+    CompoundAssignment {
+        name: Node<String>,
+        op: BinaryOp,
+        expr: Node<Expr>,
+    },
 
     // Control flow
     If {
@@ -244,8 +268,11 @@ pub enum Statement {
         cond: Node<Expr>,
         body: Node<Block>,
     },
-    Break,
-    Continue,
+    /// `break`, optionally naming the loop variable of an enclosing `for`
+    /// loop to exit instead of the innermost loop.
+    Break(Option<Node<String>>),
+    /// `continue`, optionally labeled like [`Statement::Break`].
+    Continue(Option<Node<String>>),
     Pass,
     Return(Option<Node<Expr>>),
 
@@ -298,8 +325,9 @@ impl Statement {
         match self {
             Statement::Let { .. }
             | Statement::Assignment { .. }
-            | Statement::Break
-            | Statement::Continue
+            | Statement::CompoundAssignment { .. }
+            | Statement::Break(_)
+            | Statement::Continue(_)
             | Statement::Pass
             | Statement::Return(_)
             | Statement::Expr(_)
@@ -337,7 +365,7 @@ impl Statement {
     pub fn is_pure(&self) -> bool {
         matches!(
             self,
-            Statement::Let { .. } | Statement::Break | Statement::Continue | Statement::Pass
+            Statement::Let { .. } | Statement::Break(_) | Statement::Continue(_) | Statement::Pass
         )
     }
 }
@@ -368,11 +396,17 @@ pub enum Expr {
         object: Box<Node<Expr>>,
         field: String,
     },
+    /// `base[index]` on a list or map
+    Index {
+        base: Box<Node<Expr>>,
+        index: Box<Node<Expr>>,
+    },
 
     // Function calls
     Call {
         func: Box<Node<Expr>>,
         args: Vec<Node<Expr>>,
+        kwargs: Vec<(String, Node<Expr>)>, // keyword arguments, after positional ones
     },
 
     // Binary operations
@@ -401,10 +435,11 @@ pub enum Expr {
         arms: Vec<Node<MatchArm>>,
     },
 
-    // Range expressions
+    // Range expressions (`start..end`, or `start..=end` when `inclusive`)
     Range {
         start: Box<Node<Expr>>,
         end: Box<Node<Expr>>,
+        inclusive: bool,
     },
 
     // Collection literals
@@ -473,6 +508,14 @@ pub enum Pattern {
         patterns: Vec<Node<Pattern>>,
         rest: Option<String>, // Variable name for rest pattern
     },
+    /// Range pattern (1..10, 0..=9, ..10, 0..)
+    Range {
+        start: Option<Node<Literal>>, // None for `..end`
+        end: Option<Node<Literal>>,   // None for `start..`
+        inclusive: bool,
+    },
+    /// Or-pattern (1 | 2 | 3); matches if any alternative matches
+    Or(Vec<Node<Pattern>>),
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,428 @@
+//! Traversal of the AST.
+//!
+//! A pass implements [`Visitor`] and overrides the `visit_*` methods for the
+//! nodes it cares about. Every method defaults to the matching `walk_*`
+//! function, which visits the node's children, so an override that still
+//! wants to descend calls the `walk_*` function itself.
+//!
+//! [`MutVisitor`] is the same for passes that rewrite nodes in place, with
+//! `visit_*_mut` methods and `walk_*_mut` drivers.
+
+use crate::nodes::{
+    Block, Expr, FStringPart, Function, MatchArm, Node, Pattern, Program, Statement,
+};
+
+pub trait Visitor: Sized {
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    fn visit_statement(&mut self, stmt: &Node<Statement>) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_block(&mut self, block: &Node<Block>) {
+        walk_block(self, block);
+    }
+
+    /// Called for top-level and nested functions and for struct methods.
+    fn visit_function(&mut self, func: &Node<Function>) {
+        walk_function(self, func);
+    }
+
+    fn visit_expr(&mut self, expr: &Node<Expr>) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_match_arm(&mut self, arm: &Node<MatchArm>) {
+        walk_match_arm(self, arm);
+    }
+
+    /// Patterns hold no expressions, so there is nothing to walk by default.
+    fn visit_pattern(&mut self, _pattern: &Node<Pattern>) {}
+}
+
+pub fn walk_program<V: Visitor>(visitor: &mut V, program: &Program) {
+    for stmt in &program.statements {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_block<V: Visitor>(visitor: &mut V, block: &Node<Block>) {
+    for stmt in &block.as_ref().statements {
+        visitor.visit_statement(stmt);
+    }
+}
+
+/// Visits parameter defaults, then the body.
+pub fn walk_function<V: Visitor>(visitor: &mut V, func: &Node<Function>) {
+    let func = func.as_ref();
+    for param in &func.params {
+        if let Some(default) = &param.as_ref().default {
+            visitor.visit_expr(default);
+        }
+    }
+    visitor.visit_block(&func.body);
+}
+
+pub fn walk_statement<V: Visitor>(visitor: &mut V, stmt: &Node<Statement>) {
+    match stmt.as_ref() {
+        Statement::Let { expr, .. }
+        | Statement::Assignment { expr, .. }
+        | Statement::CompoundAssignment { expr, .. }
+        | Statement::Expr(expr)
+        | Statement::Return(Some(expr)) => visitor.visit_expr(expr),
+        Statement::If {
+            cond,
+            then_block,
+            elif_blocks,
+            else_block,
+        } => {
+            visitor.visit_expr(cond);
+            visitor.visit_block(then_block);
+            for (cond, block) in elif_blocks {
+                visitor.visit_expr(cond);
+                visitor.visit_block(block);
+            }
+            if let Some(block) = else_block {
+                visitor.visit_block(block);
+            }
+        }
+        Statement::For { iterable, body, .. } => {
+            visitor.visit_expr(iterable);
+            visitor.visit_block(body);
+        }
+        Statement::While { cond, body } => {
+            visitor.visit_expr(cond);
+            visitor.visit_block(body);
+        }
+        Statement::Function(func) => visitor.visit_function(func),
+        Statement::Struct { methods, .. } => {
+            for method in methods {
+                visitor.visit_function(method);
+            }
+        }
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::Return(None)
+        | Statement::Break(_)
+        | Statement::Continue(_)
+        | Statement::Pass
+        | Statement::Enum { .. }
+        | Statement::TypeAlias { .. }
+        | Statement::Use { .. }
+        | Statement::PubUse { .. } => {}
+    }
+}
+
+pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Node<Expr>) {
+    match expr.as_ref() {
+        Expr::Literal(_) | Expr::Identifier(_) => {}
+        Expr::Member { object, .. } => visitor.visit_expr(object),
+        Expr::Index { base, index } => {
+            visitor.visit_expr(base);
+            visitor.visit_expr(index);
+        }
+        Expr::Call { func, args, kwargs } => {
+            visitor.visit_expr(func);
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+            for (_, arg) in kwargs {
+                visitor.visit_expr(arg);
+            }
+        }
+        Expr::Binary { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Unary { expr, .. } | Expr::Await(expr) | Expr::Spawn(expr) => {
+            visitor.visit_expr(expr);
+        }
+        Expr::If {
+            cond,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_expr(cond);
+            visitor.visit_expr(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_expr(else_branch);
+            }
+        }
+        Expr::Match { value, arms } => {
+            visitor.visit_expr(value);
+            for arm in arms {
+                visitor.visit_match_arm(arm);
+            }
+        }
+        Expr::Range { start, end, .. } => {
+            visitor.visit_expr(start);
+            visitor.visit_expr(end);
+        }
+        Expr::Array(items) => {
+            for item in items {
+                visitor.visit_expr(item);
+            }
+        }
+        Expr::Dict(pairs) => {
+            for (key, value) in pairs {
+                visitor.visit_expr(key);
+                visitor.visit_expr(value);
+            }
+        }
+        Expr::ListComprehension {
+            element,
+            iterable,
+            condition,
+            ..
+        } => {
+            visitor.visit_expr(iterable);
+            if let Some(condition) = condition {
+                visitor.visit_expr(condition);
+            }
+            visitor.visit_expr(element);
+        }
+        Expr::DictComprehension {
+            key,
+            value,
+            iterable,
+            condition,
+            ..
+        } => {
+            visitor.visit_expr(iterable);
+            if let Some(condition) = condition {
+                visitor.visit_expr(condition);
+            }
+            visitor.visit_expr(key);
+            visitor.visit_expr(value);
+        }
+        Expr::FString { parts } => {
+            for part in parts {
+                if let FStringPart::Expr(expr) = part.as_ref() {
+                    visitor.visit_expr(expr);
+                }
+            }
+        }
+        Expr::Struct { fields, .. } => {
+            for (_, value) in fields {
+                visitor.visit_expr(value);
+            }
+        }
+    }
+}
+
+/// Visits the pattern, then the guard, then the body.
+pub fn walk_match_arm<V: Visitor>(visitor: &mut V, arm: &Node<MatchArm>) {
+    let arm = arm.as_ref();
+    visitor.visit_pattern(&arm.pattern);
+    if let Some(guard) = &arm.guard {
+        visitor.visit_expr(guard);
+    }
+    visitor.visit_block(&arm.body);
+}
+
+pub trait MutVisitor: Sized {
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    fn visit_statement_mut(&mut self, stmt: &mut Node<Statement>) {
+        walk_statement_mut(self, stmt);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Node<Block>) {
+        walk_block_mut(self, block);
+    }
+
+    /// Called for top-level and nested functions and for struct methods.
+    fn visit_function_mut(&mut self, func: &mut Node<Function>) {
+        walk_function_mut(self, func);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Node<Expr>) {
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_match_arm_mut(&mut self, arm: &mut Node<MatchArm>) {
+        walk_match_arm_mut(self, arm);
+    }
+
+    fn visit_pattern_mut(&mut self, _pattern: &mut Node<Pattern>) {}
+}
+
+pub fn walk_program_mut<V: MutVisitor>(visitor: &mut V, program: &mut Program) {
+    for stmt in &mut program.statements {
+        visitor.visit_statement_mut(stmt);
+    }
+}
+
+pub fn walk_block_mut<V: MutVisitor>(visitor: &mut V, block: &mut Node<Block>) {
+    for stmt in &mut block.as_mut().statements {
+        visitor.visit_statement_mut(stmt);
+    }
+}
+
+pub fn walk_function_mut<V: MutVisitor>(visitor: &mut V, func: &mut Node<Function>) {
+    let func = func.as_mut();
+    for param in &mut func.params {
+        if let Some(default) = &mut param.as_mut().default {
+            visitor.visit_expr_mut(default);
+        }
+    }
+    visitor.visit_block_mut(&mut func.body);
+}
+
+pub fn walk_statement_mut<V: MutVisitor>(visitor: &mut V, stmt: &mut Node<Statement>) {
+    match stmt.as_mut() {
+        Statement::Let { expr, .. }
+        | Statement::Assignment { expr, .. }
+        | Statement::CompoundAssignment { expr, .. }
+        | Statement::Expr(expr)
+        | Statement::Return(Some(expr)) => visitor.visit_expr_mut(expr),
+        Statement::If {
+            cond,
+            then_block,
+            elif_blocks,
+            else_block,
+        } => {
+            visitor.visit_expr_mut(cond);
+            visitor.visit_block_mut(then_block);
+            for (cond, block) in elif_blocks {
+                visitor.visit_expr_mut(cond);
+                visitor.visit_block_mut(block);
+            }
+            if let Some(block) = else_block {
+                visitor.visit_block_mut(block);
+            }
+        }
+        Statement::For { iterable, body, .. } => {
+            visitor.visit_expr_mut(iterable);
+            visitor.visit_block_mut(body);
+        }
+        Statement::While { cond, body } => {
+            visitor.visit_expr_mut(cond);
+            visitor.visit_block_mut(body);
+        }
+        Statement::Function(func) => visitor.visit_function_mut(func),
+        Statement::Struct { methods, .. } => {
+            for method in methods {
+                visitor.visit_function_mut(method);
+            }
+        }
+        Statement::Block(block) => visitor.visit_block_mut(block),
+        Statement::Return(None)
+        | Statement::Break(_)
+        | Statement::Continue(_)
+        | Statement::Pass
+        | Statement::Enum { .. }
+        | Statement::TypeAlias { .. }
+        | Statement::Use { .. }
+        | Statement::PubUse { .. } => {}
+    }
+}
+
+pub fn walk_expr_mut<V: MutVisitor>(visitor: &mut V, expr: &mut Node<Expr>) {
+    match expr.as_mut() {
+        Expr::Literal(_) | Expr::Identifier(_) => {}
+        Expr::Member { object, .. } => visitor.visit_expr_mut(object),
+        Expr::Index { base, index } => {
+            visitor.visit_expr_mut(base);
+            visitor.visit_expr_mut(index);
+        }
+        Expr::Call { func, args, kwargs } => {
+            visitor.visit_expr_mut(func);
+            for arg in args {
+                visitor.visit_expr_mut(arg);
+            }
+            for (_, arg) in kwargs {
+                visitor.visit_expr_mut(arg);
+            }
+        }
+        Expr::Binary { left, right, .. } => {
+            visitor.visit_expr_mut(left);
+            visitor.visit_expr_mut(right);
+        }
+        Expr::Unary { expr, .. } | Expr::Await(expr) | Expr::Spawn(expr) => {
+            visitor.visit_expr_mut(expr);
+        }
+        Expr::If {
+            cond,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_expr_mut(cond);
+            visitor.visit_expr_mut(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_expr_mut(else_branch);
+            }
+        }
+        Expr::Match { value, arms } => {
+            visitor.visit_expr_mut(value);
+            for arm in arms {
+                visitor.visit_match_arm_mut(arm);
+            }
+        }
+        Expr::Range { start, end, .. } => {
+            visitor.visit_expr_mut(start);
+            visitor.visit_expr_mut(end);
+        }
+        Expr::Array(items) => {
+            for item in items {
+                visitor.visit_expr_mut(item);
+            }
+        }
+        Expr::Dict(pairs) => {
+            for (key, value) in pairs {
+                visitor.visit_expr_mut(key);
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Expr::ListComprehension {
+            element,
+            iterable,
+            condition,
+            ..
+        } => {
+            visitor.visit_expr_mut(iterable);
+            if let Some(condition) = condition {
+                visitor.visit_expr_mut(condition);
+            }
+            visitor.visit_expr_mut(element);
+        }
+        Expr::DictComprehension {
+            key,
+            value,
+            iterable,
+            condition,
+            ..
+        } => {
+            visitor.visit_expr_mut(iterable);
+            if let Some(condition) = condition {
+                visitor.visit_expr_mut(condition);
+            }
+            visitor.visit_expr_mut(key);
+            visitor.visit_expr_mut(value);
+        }
+        Expr::FString { parts } => {
+            for part in parts {
+                if let FStringPart::Expr(expr) = part.as_mut() {
+                    visitor.visit_expr_mut(expr);
+                }
+            }
+        }
+        Expr::Struct { fields, .. } => {
+            for (_, value) in fields {
+                visitor.visit_expr_mut(value);
+            }
+        }
+    }
+}
+
+pub fn walk_match_arm_mut<V: MutVisitor>(visitor: &mut V, arm: &mut Node<MatchArm>) {
+    let arm = arm.as_mut();
+    visitor.visit_pattern_mut(&mut arm.pattern);
+    if let Some(guard) = &mut arm.guard {
+        visitor.visit_expr_mut(guard);
+    }
+    visitor.visit_block_mut(&mut arm.body);
+}
@@ -8,8 +8,12 @@ use anyhow::{Context, Result, anyhow, bail};
 use glob::glob;
 use inkwell::OptimizationLevel;
 use inkwell::context::Context as LlvmContext;
-use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target};
+use inkwell::module::Module;
+use inkwell::targets::{
+    CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine,
+};
 use otterc_ast::nodes::Program;
+use otterc_cache::path::cache_root;
 use otterc_span::Span;
 
 use otterc_config::{CodegenOptLevel, CodegenOptions, TargetTriple};
@@ -17,13 +21,18 @@ use otterc_typecheck::{EnumLayout, TypeInfo};
 
 use super::bridges::prepare_rust_bridges;
 use super::compiler::Compiler;
-use super::config::{BuildArtifact, llvm_triple_to_string, preferred_target_flag};
+use super::config::{
+    BuildArtifact, compiler_reports_clang, llvm_triple_to_string, preferred_target_flag,
+};
 
 const RUNTIME_CODE_STANDARD: &str = include_str!("runtimes/standard.c");
 const RUNTIME_CODE_EMBEDDED: &str = include_str!("runtimes/embedded.c");
 const RUNTIME_CODE_WASM: &str = include_str!("runtimes/wasm.c");
 const RUNTIME_CODE_SHIM: &str = include_str!("runtimes/shim.c");
 
+/// Leading bytes of a raw LLVM bitcode file.
+const BITCODE_MAGIC: [u8; 4] = *b"BC\xC0\xDE";
+
 /// Check if a library is available on the system
 fn check_library_available(lib_name: &str) -> bool {
     // Try pkg-config first
@@ -71,6 +80,138 @@ fn check_library_available(lib_name: &str) -> bool {
     false
 }
 
+/// Write the lowered module next to `output` for the link step. With LTO the
+/// module is emitted as LLVM bitcode so the linker can optimize across it;
+/// otherwise it is compiled to a native object.
+fn emit_module_for_link(
+    module: &Module<'_>,
+    target_machine: &TargetMachine,
+    output: &Path,
+    lto: bool,
+) -> Result<PathBuf> {
+    if lto {
+        let bitcode_path = output.with_extension("bc");
+        if !module.write_bitcode_to_path(&bitcode_path) {
+            bail!("failed to emit bitcode at {}", bitcode_path.display());
+        }
+        if !is_llvm_bitcode(&bitcode_path)? {
+            bail!("{} is not an LLVM bitcode file", bitcode_path.display());
+        }
+        return Ok(bitcode_path);
+    }
+
+    let object_path = output.with_extension("o");
+    target_machine
+        .write_to_file(module, FileType::Object, &object_path)
+        .map_err(|e| {
+            anyhow!(
+                "failed to emit object file at {}: {e}",
+                object_path.display()
+            )
+        })?;
+    Ok(object_path)
+}
+
+/// Directory holding compiled runtime shim objects.
+fn runtime_cache_dir() -> PathBuf {
+    cache_root()
+        .map(|root| root.join("runtime"))
+        .unwrap_or_else(|_| env::temp_dir().join("otterlang-runtime"))
+}
+
+/// Compile the C runtime shim `source` for `runtime_triple`, reusing the
+/// object in `cache_dir` when the same source was already compiled with the
+/// same compiler and flags. `cross_triple` is passed as the compiler target
+/// when building for a non-native triple.
+fn cached_runtime_object(
+    source: &str,
+    runtime_triple: &TargetTriple,
+    cross_triple: Option<&str>,
+    cache_dir: &Path,
+) -> Result<PathBuf> {
+    use sha2::{Digest, Sha256};
+
+    let c_compiler = runtime_triple.c_compiler();
+    let mut flags = vec!["-c".to_string()];
+    if runtime_triple.needs_pic() && !runtime_triple.is_windows() {
+        flags.push("-fPIC".to_string());
+    }
+    if runtime_triple.os == "darwin" {
+        flags.push("-mmacosx-version-min=11.0".to_string());
+    }
+    if let Some(triple) = cross_triple {
+        flags.push(preferred_target_flag(&c_compiler).to_string());
+        flags.push(triple.to_string());
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(c_compiler.as_bytes());
+    for flag in &flags {
+        hasher.update(b"flag:");
+        hasher.update(flag.as_bytes());
+    }
+    hasher.update(b"source:");
+    hasher.update(source.as_bytes());
+    let key = format!("{:x}", hasher.finalize());
+
+    let object = cache_dir.join(format!("{key}.o"));
+    if object.exists() {
+        return Ok(object);
+    }
+
+    fs::create_dir_all(cache_dir).with_context(|| {
+        format!(
+            "failed to create runtime cache directory {}",
+            cache_dir.display()
+        )
+    })?;
+    // Build under per-process names and rename into place, so concurrent
+    // builds never link a half-written object
+    let pid = std::process::id();
+    let source_path = cache_dir.join(format!("{key}-{pid}.c"));
+    let partial = cache_dir.join(format!("{key}-{pid}.o"));
+    fs::write(&source_path, source).context("failed to write runtime C file")?;
+
+    let status = Command::new(&c_compiler)
+        .args(&flags)
+        .arg(&source_path)
+        .arg("-o")
+        .arg(&partial)
+        .status()
+        .context("failed to compile runtime C file");
+    fs::remove_file(&source_path)?;
+    if !status?.success() {
+        let _ = fs::remove_file(&partial);
+        bail!("failed to compile runtime C file");
+    }
+
+    fs::rename(&partial, &object)
+        .with_context(|| format!("failed to cache runtime object {}", object.display()))?;
+    Ok(object)
+}
+
+/// Whether the file at `path` starts with the LLVM bitcode magic.
+fn is_llvm_bitcode(path: &Path) -> Result<bool> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(bytes.starts_with(&BITCODE_MAGIC))
+}
+
+/// Linker driver for the final link. Bitcode inputs need a clang driver,
+/// which hands them to the linker's LTO plugin: the target's own linker
+/// when it is clang, otherwise `clang` from `PATH`.
+fn link_driver(runtime_triple: &TargetTriple, lto: bool) -> Result<String> {
+    let linker = runtime_triple.linker();
+    if !lto || compiler_reports_clang(&linker) {
+        return Ok(linker);
+    }
+    if compiler_reports_clang("clang") {
+        return Ok("clang".to_string());
+    }
+    bail!(
+        "linking with LTO needs a clang driver, but `{linker}` is not clang and `clang` was not found"
+    );
+}
+
 pub fn current_llvm_version() -> String {
     "15.0".to_string()
 }
@@ -153,6 +294,8 @@ pub fn build_executable(
         enum_layouts.clone(),
         Some(runtime_triple.clone()),
     );
+    compiler.bounds_checks = options.bounds_checks;
+    compiler.infer_param_types = options.infer_param_types;
 
     compiler.lower_program(program, true)?; // Require main for executables
     compiler
@@ -215,83 +358,44 @@ pub fn build_executable(
         options.pgo_profile_file.as_deref(),
         options.inline_threshold,
         &target_machine,
-    );
+    )?;
 
     if let Some(parent) = output.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed to create output directory {}", parent.display()))?;
     }
 
-    let object_path = output.with_extension("o");
-    target_machine
-        .write_to_file(&compiler.module, FileType::Object, &object_path)
-        .map_err(|e| {
-            anyhow!(
-                "failed to emit object file at {}: {e}",
-                object_path.display()
-            )
-        })?;
+    let lto = options.enable_lto && !runtime_triple.is_wasm();
+    let object_path = emit_module_for_link(&compiler.module, &target_machine, output, lto)?;
 
     // Build and link the runtime static library (check once)
     let runtime_lib = find_runtime_library(&runtime_triple)?;
     let use_rust_runtime = runtime_lib.exists();
 
-    // Create a C runtime shim for the FFI functions (target-specific)
-    let runtime_c = if runtime_triple.is_wasm() {
+    // Compile the C runtime shim for the FFI functions (target-specific)
+    let runtime_o = if runtime_triple.is_wasm() {
         None
     } else {
-        let runtime_c = output.with_extension("runtime.c");
         let runtime_c_content = if use_rust_runtime {
-            RUNTIME_CODE_SHIM.to_string()
+            RUNTIME_CODE_SHIM
         } else if runtime_triple.is_wasm() {
-            RUNTIME_CODE_WASM.to_string()
+            RUNTIME_CODE_WASM
         } else if runtime_triple.is_embedded() {
-            RUNTIME_CODE_EMBEDDED.to_string()
+            RUNTIME_CODE_EMBEDDED
         } else {
-            RUNTIME_CODE_STANDARD.to_string()
+            RUNTIME_CODE_STANDARD
         };
-        fs::write(&runtime_c, runtime_c_content).context("failed to write runtime C file")?;
-        Some(runtime_c)
-    };
-
-    // Compile runtime C file (target-specific)
-    let runtime_o = if let Some(ref rt_c) = runtime_c {
-        let runtime_o = output.with_extension("runtime.o");
-        let c_compiler = runtime_triple.c_compiler();
-        let mut cc = Command::new(&c_compiler);
-
-        // Add target-specific compiler flags
-        cc.arg("-c");
-        if runtime_triple.needs_pic() && !runtime_triple.is_windows() {
-            cc.arg("-fPIC");
-        }
-
-        // Add macOS version minimum
-        if runtime_triple.os == "darwin" {
-            cc.arg("-mmacosx-version-min=11.0");
-        }
-
-        // Add target triple for cross-compilation (skip for native target)
-        if !is_native_target {
-            let compiler_target_flag = preferred_target_flag(&c_compiler);
-            cc.arg(compiler_target_flag).arg(&triple_str);
-        }
-
-        cc.arg(rt_c).arg("-o").arg(&runtime_o);
-
-        let cc_status = cc.status().context("failed to compile runtime C file")?;
-
-        if !cc_status.success() {
-            bail!("failed to compile runtime C file");
-        }
-
-        Some(runtime_o)
-    } else {
-        None
+        let cross_triple = (!is_native_target).then_some(triple_str.as_str());
+        Some(cached_runtime_object(
+            runtime_c_content,
+            &runtime_triple,
+            cross_triple,
+            &runtime_cache_dir(),
+        )?)
     };
 
     // Link the object files together (target-specific)
-    let linker = runtime_triple.linker();
+    let linker = link_driver(&runtime_triple, lto)?;
     let mut cc = Command::new(&linker);
 
     // Add target-specific linker flags
@@ -351,7 +455,7 @@ pub fn build_executable(
         cc.arg(&flag);
     }
 
-    if options.enable_lto && !runtime_triple.is_wasm() {
+    if lto {
         cc.arg("-flto");
         // Note: clang doesn't support -flto=O2/O3, use -O flags instead
         match options.opt_level {
@@ -462,19 +566,15 @@ pub fn build_executable(
         cc.arg("-v");
     }
 
-    let status = cc.status().context("failed to invoke system linker (cc)")?;
+    let status = cc
+        .status()
+        .with_context(|| format!("failed to invoke system linker ({linker})"))?;
 
     if !status.success() {
         bail!("linker invocation failed with status {status}");
     }
 
     // Clean up temporary files
-    if let Some(ref rt_c) = runtime_c {
-        fs::remove_file(rt_c)?;
-    }
-    if let Some(ref rt_o) = runtime_o {
-        fs::remove_file(rt_o)?;
-    }
 
     fs::remove_file(&object_path)?;
 
@@ -525,6 +625,8 @@ pub fn build_shared_library(
         enum_layouts.clone(),
         Some(runtime_triple.clone()),
     );
+    compiler.bounds_checks = options.bounds_checks;
+    compiler.infer_param_types = options.infer_param_types;
 
     compiler.lower_program(program, false)?; // Don't require main for shared libraries
     compiler
@@ -575,71 +677,41 @@ pub fn build_shared_library(
         options.pgo_profile_file.as_deref(),
         options.inline_threshold,
         &target_machine,
-    );
+    )?;
 
     if let Some(parent) = output.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed to create output directory {}", parent.display()))?;
     }
 
-    // Compile to object file with position-independent code
-    let object_path = output.with_extension("o");
-    target_machine
-        .write_to_file(&compiler.module, FileType::Object, &object_path)
-        .map_err(|e| {
-            anyhow!(
-                "failed to emit object file at {}: {e}",
-                object_path.display()
-            )
-        })?;
+    // Compile to object file (or bitcode for LTO) with position-independent code
+    let lto = options.enable_lto && !runtime_triple.is_wasm();
+    let object_path = emit_module_for_link(&compiler.module, &target_machine, output, lto)?;
 
-    // Create runtime C file (target-specific)
-    let runtime_c = if runtime_triple.is_wasm() {
+    // Build and check runtime static library (check once)
+    let runtime_lib = find_runtime_library(&runtime_triple)?;
+    let use_rust_runtime = runtime_lib.exists();
+
+    // Compile the C runtime shim (target-specific); the full C runtime would
+    // clash with the Rust runtime's definitions of the same functions
+    let runtime_o = if runtime_triple.is_wasm() {
         None
     } else {
-        let runtime_c = output.with_extension("runtime.c");
-        let runtime_c_content = if runtime_triple.is_wasm() {
-            RUNTIME_CODE_WASM.to_string()
+        let runtime_c_content = if use_rust_runtime {
+            RUNTIME_CODE_SHIM
+        } else if runtime_triple.is_wasm() {
+            RUNTIME_CODE_WASM
         } else if runtime_triple.is_embedded() {
-            RUNTIME_CODE_EMBEDDED.to_string()
+            RUNTIME_CODE_EMBEDDED
         } else {
-            RUNTIME_CODE_STANDARD.to_string()
+            RUNTIME_CODE_STANDARD
         };
-        fs::write(&runtime_c, runtime_c_content).context("failed to write runtime C file")?;
-        Some(runtime_c)
-    };
-
-    // Compile runtime C file (target-specific)
-    let runtime_o = if let Some(ref rt_c) = runtime_c {
-        let runtime_o = output.with_extension("runtime.o");
-        let c_compiler = runtime_triple.c_compiler();
-        let mut cc = Command::new(&c_compiler);
-
-        // Add target-specific compiler flags
-        cc.arg("-c");
-        if runtime_triple.needs_pic() && !runtime_triple.is_windows() {
-            cc.arg("-fPIC");
-        }
-
-        // Add macOS version minimum
-        if runtime_triple.os == "darwin" {
-            cc.arg("-mmacosx-version-min=11.0");
-        }
-
-        let compiler_target_flag = preferred_target_flag(&c_compiler);
-        cc.arg(compiler_target_flag).arg(&triple_str);
-
-        cc.arg(rt_c).arg("-o").arg(&runtime_o);
-
-        let cc_status = cc.status().context("failed to compile runtime C file")?;
-
-        if !cc_status.success() {
-            bail!("failed to compile runtime C file");
-        }
-
-        Some(runtime_o)
-    } else {
-        None
+        Some(cached_runtime_object(
+            runtime_c_content,
+            &runtime_triple,
+            Some(triple_str.as_str()),
+            &runtime_cache_dir(),
+        )?)
     };
 
     // Determine shared library extension (target-specific)
@@ -659,12 +731,8 @@ pub fn build_shared_library(
         output.with_extension(lib_ext)
     };
 
-    // Build and check runtime static library (check once)
-    let runtime_lib = find_runtime_library(&runtime_triple)?;
-    let use_rust_runtime = runtime_lib.exists();
-
     // Link as shared library (target-specific)
-    let linker = runtime_triple.linker();
+    let linker = link_driver(&runtime_triple, lto)?;
     let mut cc = Command::new(&linker);
 
     let linker_target_flag = preferred_target_flag(&linker);
@@ -702,7 +770,7 @@ pub fn build_shared_library(
         cc.arg(&flag);
     }
 
-    if options.enable_lto && !runtime_triple.is_wasm() {
+    if lto {
         cc.arg("-flto");
         // Note: clang doesn't support -flto=O2/O3, use -O flags instead
         match options.opt_level {
@@ -787,19 +855,15 @@ pub fn build_shared_library(
         }
     }
 
-    let status = cc.status().context("failed to invoke system linker (cc)")?;
+    let status = cc
+        .status()
+        .with_context(|| format!("failed to invoke system linker ({linker})"))?;
 
     if !status.success() {
         bail!("linker invocation failed with status {status}");
     }
 
     // Clean up temporary files
-    if let Some(ref rt_c) = runtime_c {
-        fs::remove_file(rt_c)?;
-    }
-    if let Some(ref rt_o) = runtime_o {
-        fs::remove_file(rt_o)?;
-    }
     fs::remove_file(&object_path)?;
 
     Ok(BuildArtifact {
@@ -807,3 +871,127 @@ pub fn build_shared_library(
         ir: compiler.cached_ir.take(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llvm::compiler::test_support::with_compiled;
+
+    #[test]
+    fn recognizes_bitcode_by_magic() {
+        let dir = env::temp_dir().join(format!("otter-bitcode-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let bitcode = dir.join("module.bc");
+        let object = dir.join("module.o");
+        fs::write(&bitcode, [b'B', b'C', 0xC0, 0xDE, 0x35, 0x14]).unwrap();
+        fs::write(&object, b"\x7fELF\x02\x01").unwrap();
+
+        assert!(is_llvm_bitcode(&bitcode).unwrap());
+        assert!(!is_llvm_bitcode(&object).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[ignore] // Needs a C compiler: cargo test -- --ignored
+    fn c_runtimes_format_floats_like_the_rust_runtime() {
+        let dir = env::temp_dir().join(format!("otter-c-floats-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let values = [0.1 + 0.2, 1e-12, 3.0, -0.5, 1e21, 5e-324, 1.0 / 3.0];
+        let literals: Vec<String> = values.iter().map(|value| format!("{value:?}")).collect();
+        let probe = dir.join("probe.c");
+        fs::write(
+            &probe,
+            format!(
+                "#include <stdio.h>\nchar* otter_format_float(double value);\n\
+                 void otter_entry(void) {{}}\n\
+                 int main(void) {{\n    double values[] = {{{}}};\n\
+                 for (int i = 0; i < {}; i++) puts(otter_format_float(values[i]));\n    return 0;\n}}\n",
+                literals.join(", "),
+                values.len()
+            ),
+        )
+        .unwrap();
+        let expected: String = values.iter().map(|value| format!("{value}\n")).collect();
+
+        for (name, source) in [
+            ("standard", RUNTIME_CODE_STANDARD),
+            ("embedded", RUNTIME_CODE_EMBEDDED),
+        ] {
+            let runtime = dir.join(format!("{name}.c"));
+            let object = dir.join(format!("{name}.o"));
+            let binary = dir.join(name);
+            fs::write(&runtime, source).unwrap();
+            // The standard runtime brings its own `main`, which would call `otter_entry`
+            let status = Command::new("cc")
+                .arg("-c")
+                .arg(&runtime)
+                .arg("-Dmain=otter_runtime_main")
+                .arg("-o")
+                .arg(&object)
+                .status()
+                .unwrap();
+            assert!(status.success(), "{name} runtime did not compile");
+            let status = Command::new("cc")
+                .arg(&probe)
+                .arg(&object)
+                .arg("-lm")
+                .arg("-o")
+                .arg(&binary)
+                .status()
+                .unwrap();
+            assert!(status.success(), "{name} probe did not link");
+
+            let output = Command::new(&binary).output().unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), expected, "{name}");
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn lto_links_emit_bitcode() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("program");
+        let source = "fn main():\n    println(\"hi\")\n";
+
+        let bitcode = with_compiled(
+            source,
+            CodegenOptLevel::Default,
+            RelocMode::Default,
+            |module, machine| emit_module_for_link(module, machine, &output, true),
+        )
+        .unwrap();
+        assert_eq!(bitcode, output.with_extension("bc"));
+        assert_eq!(fs::read(&bitcode).unwrap()[..4], BITCODE_MAGIC);
+
+        let object = with_compiled(
+            source,
+            CodegenOptLevel::Default,
+            RelocMode::Default,
+            |module, machine| emit_module_for_link(module, machine, &output, false),
+        )
+        .unwrap();
+        assert!(!is_llvm_bitcode(&object).unwrap());
+    }
+
+    #[test]
+    fn reuses_cached_runtime_object() {
+        let dir = env::temp_dir().join(format!("otter-runtime-cache-{}", std::process::id()));
+        let triple = TargetTriple::new("x86_64", "unknown", "linux", Some("gnu"));
+        let source = "int otter_cache_probe(void) { return 1; }\n";
+
+        let first = cached_runtime_object(source, &triple, None, &dir).unwrap();
+        let built_at = fs::metadata(&first).unwrap().modified().unwrap();
+        let second = cached_runtime_object(source, &triple, None, &dir).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(fs::metadata(&second).unwrap().modified().unwrap(), built_at);
+
+        let changed =
+            cached_runtime_object("int other(void) { return 2; }\n", &triple, None, &dir).unwrap();
+        assert_ne!(first, changed);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
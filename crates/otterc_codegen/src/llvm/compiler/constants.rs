@@ -0,0 +1,79 @@
+//! Propagation of `let`-bound constants.
+//!
+//! A name that a function binds with a single `let` of a number or bool
+//! literal, and never assigns to, always holds that literal. Such a binding
+//! gets no stack slot: the constant is substituted at every use. Any
+//! assignment to the name anywhere in the function, or a second `let` of it,
+//! keeps the ordinary alloca and load.
+
+use std::collections::{HashMap, HashSet};
+
+use otterc_ast::nodes::{Block, Expr, Literal, Node, Statement};
+use otterc_ast::visit::{Visitor, walk_statement};
+
+/// Names in `body` whose `let` can be replaced by its literal.
+pub(crate) fn constant_bindings(body: &Node<Block>) -> HashSet<String> {
+    let mut collector = BindingCollector::default();
+    collector.visit_block(body);
+    collector
+        .lets
+        .into_iter()
+        .filter(|(name, (count, literal))| {
+            *count == 1 && *literal && !collector.assigned.contains(name)
+        })
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Whether `expr` is a literal that can stand in for a binding.
+pub(crate) fn is_constant_literal(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal(lit) if matches!(lit.as_ref(), Literal::Number(_) | Literal::Bool(_)))
+}
+
+#[derive(Default)]
+struct BindingCollector {
+    /// `let` count per name, and whether every one binds a literal
+    lets: HashMap<String, (usize, bool)>,
+    assigned: HashSet<String>,
+}
+
+impl Visitor for BindingCollector {
+    fn visit_statement(&mut self, stmt: &Node<Statement>) {
+        match stmt.as_ref() {
+            Statement::Let { name, expr, .. } => {
+                let entry = self.lets.entry(name.as_ref().clone()).or_insert((0, true));
+                entry.0 += 1;
+                entry.1 &= is_constant_literal(expr.as_ref());
+            }
+            Statement::Assignment { name, .. } | Statement::CompoundAssignment { name, .. } => {
+                self.assigned.insert(name.as_ref().clone());
+            }
+            _ => {}
+        }
+        walk_statement(self, stmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::llvm::compiler::test_support::{function_ir, lower_to_ir};
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn literal_binding_gets_no_stack_slot() {
+        let ir = lower_to_ir("fn answer() -> int:\n    let k = 41\n    return k + 1\n").unwrap();
+        let body = function_ir(&ir, "answer");
+        assert!(!body.contains("alloca"), "{body}");
+        assert!(!body.contains("store"), "{body}");
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn reassigned_binding_is_not_folded() {
+        let ir = lower_to_ir("fn count() -> int:\n    let n = 1\n    n = n + 2\n    return n\n")
+            .unwrap();
+        let body = function_ir(&ir, "count");
+        assert!(body.contains("%n = alloca"), "{body}");
+        assert!(body.contains("store"), "{body}");
+    }
+}
@@ -2,11 +2,16 @@ use anyhow::{Result, anyhow, bail};
 use inkwell::AddressSpace;
 use inkwell::IntPredicate;
 use inkwell::types::{BasicTypeEnum, PointerType, StructType};
-use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, IntValue};
-use std::collections::BTreeSet;
+use inkwell::values::{
+    BasicMetadataValueEnum, BasicValueEnum, FunctionValue, InstructionOpcode, IntValue,
+    LLVMTailCallKind,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::mem;
 
 use crate::llvm::compiler::Compiler;
 use crate::llvm::compiler::types::{EvaluatedValue, FunctionContext, OtterType, Variable};
+use crate::llvm::error::CodegenError;
 use otterc_ast::nodes::{BinaryOp, Block, Expr, FStringPart, Literal, Node, Statement, UnaryOp};
 use otterc_typecheck::TypeInfo;
 
@@ -68,7 +73,13 @@ impl<'ctx> Compiler<'ctx> {
         let spawn_id = self.next_spawn_id;
         self.next_spawn_id += 1;
 
-        let wrapper = self.build_spawn_wrapper(spawn_id, expr, context_struct, &capture_fields)?;
+        let wrapper = self.build_spawn_wrapper(
+            spawn_id,
+            expr,
+            context_struct,
+            &capture_fields,
+            ctx.constants.clone(),
+        )?;
 
         let context_arg = if let Some(struct_type) = context_struct {
             let context_ptr = self.builder.build_malloc(struct_type, "spawn_ctx")?;
@@ -115,6 +126,7 @@ impl<'ctx> Compiler<'ctx> {
         expr: &Expr,
         context_type: Option<StructType<'ctx>>,
         captures: &[CapturedVariable<'ctx>],
+        constants: HashMap<String, EvaluatedValue<'ctx>>,
     ) -> Result<FunctionValue<'ctx>> {
         let fn_name = format!("spawn_wrapper_{}", spawn_id);
         let fn_type = self
@@ -127,6 +139,7 @@ impl<'ctx> Compiler<'ctx> {
         self.builder.position_at_end(entry);
 
         let mut wrapper_ctx = FunctionContext::new();
+        wrapper_ctx.constants = constants;
         let mut raw_ptr: Option<inkwell::values::PointerValue<'ctx>> = None;
 
         if let Some(struct_type) = context_type {
@@ -195,13 +208,17 @@ impl<'ctx> Compiler<'ctx> {
             Expr::Member { object, .. } => {
                 self.collect_captured_names(object.as_ref().as_ref(), ctx, captures);
             }
-            Expr::Call { func, args } => {
+            Expr::Call { func, args, kwargs } => {
                 self.collect_captured_names(func.as_ref().as_ref(), ctx, captures);
-                for arg in args {
+                for arg in args.iter().chain(kwargs.iter().map(|(_, arg)| arg)) {
                     self.collect_captured_names(arg.as_ref(), ctx, captures);
                 }
             }
-            Expr::Binary { left, right, .. } => {
+            Expr::Binary { left, right, .. }
+            | Expr::Index {
+                base: left,
+                index: right,
+            } => {
                 self.collect_captured_names(left.as_ref().as_ref(), ctx, captures);
                 self.collect_captured_names(right.as_ref().as_ref(), ctx, captures);
             }
@@ -228,7 +245,7 @@ impl<'ctx> Compiler<'ctx> {
                     self.collect_captured_names_in_block(arm.as_ref().body.as_ref(), ctx, captures);
                 }
             }
-            Expr::Range { start, end } => {
+            Expr::Range { start, end, .. } => {
                 self.collect_captured_names(start.as_ref().as_ref(), ctx, captures);
                 self.collect_captured_names(end.as_ref().as_ref(), ctx, captures);
             }
@@ -308,6 +325,7 @@ impl<'ctx> Compiler<'ctx> {
             Statement::Expr(expr)
             | Statement::Let { expr, .. }
             | Statement::Assignment { expr, .. }
+            | Statement::CompoundAssignment { expr, .. }
             | Statement::Return(Some(expr)) => {
                 self.collect_captured_names(expr.as_ref(), ctx, captures);
             }
@@ -339,8 +357,8 @@ impl<'ctx> Compiler<'ctx> {
                 self.collect_captured_names_in_block(block.as_ref(), ctx, captures);
             }
             Statement::Return(None)
-            | Statement::Break
-            | Statement::Continue
+            | Statement::Break(_)
+            | Statement::Continue(_)
             | Statement::Pass
             | Statement::Use { .. }
             | Statement::PubUse { .. }
@@ -390,6 +408,19 @@ impl<'ctx> Compiler<'ctx> {
         &mut self,
         expr: &Expr,
         ctx: &mut FunctionContext<'ctx>,
+    ) -> Result<EvaluatedValue<'ctx>> {
+        let id = expr as *const Expr as usize;
+        self.eval_expr_kind(expr, ctx)
+            .map_err(|err| match self.expr_spans.get(&id) {
+                Some(span) => CodegenError::locate(err, *span),
+                None => err,
+            })
+    }
+
+    fn eval_expr_kind(
+        &mut self,
+        expr: &Expr,
+        ctx: &mut FunctionContext<'ctx>,
     ) -> Result<EvaluatedValue<'ctx>> {
         match expr {
             Expr::Literal(lit) => {
@@ -412,23 +443,26 @@ impl<'ctx> Compiler<'ctx> {
                             value: None,
                         })
                     }
+                } else if let Some(constant) = ctx.constant(name) {
+                    Ok(constant.clone())
                 } else {
-                    bail!("Variable {} not found", name);
+                    bail!(CodegenError::unknown_identifier(name.as_str()));
                 }
             }
             Expr::Binary { left, op, right } => {
                 self.eval_binary_expr(left.as_ref().as_ref(), op, right.as_ref().as_ref(), ctx)
             }
             Expr::Unary { op, expr } => self.eval_unary_expr(op, expr.as_ref().as_ref(), ctx),
-            Expr::Call { func: _, args: _ } => self.eval_call_expr(expr, ctx),
+            Expr::Call { .. } => self.eval_call_expr(expr, ctx),
             Expr::Member { object, field } => {
                 if let Some(value) =
                     self.try_build_enum_member(expr, object.as_ref().as_ref(), field, ctx)?
                 {
                     Ok(value)
-                } else if self
-                    .module_path_from_expr(object.as_ref().as_ref())
-                    .is_some()
+                } else if self.module_path_from_expr(expr).is_some()
+                    || self
+                        .module_path_from_expr(object.as_ref().as_ref())
+                        .is_some()
                 {
                     // Module references don't need runtime materialization.
                     Ok(EvaluatedValue {
@@ -454,14 +488,21 @@ impl<'ctx> Compiler<'ctx> {
                         let field_ty = info.field_types[idx].clone();
                         Ok(EvaluatedValue::with_value(extracted, field_ty))
                     } else {
-                        bail!(
-                            "Complex member expressions not yet supported (expr, ty={:?})",
-                            object_ty
-                        );
+                        bail!(CodegenError::unsupported_expr(format!(
+                            "field access on {} values not yet supported (field '{}')",
+                            object_ty, field
+                        )));
                     }
                 }
             }
             Expr::Struct { name, fields } => {
+                // `f(x=1, y=2)` parses like a struct literal; lower it as a keyword-only call.
+                if self.struct_info_by_name(name).is_none()
+                    && self.function_param_names.contains_key(name)
+                {
+                    let func = Expr::Identifier(name.clone());
+                    return self.eval_call(expr, &func, &[], fields, ctx);
+                }
                 let (struct_id, _) = self
                     .struct_info_by_name(name)
                     .ok_or_else(|| anyhow!("unknown struct type '{}'", name))?;
@@ -505,6 +546,16 @@ impl<'ctx> Compiler<'ctx> {
                 else_branch: _,
             } => self.eval_if_expr(expr, ctx),
             Expr::Match { value: _, arms: _ } => self.eval_match_expr(expr, ctx),
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+            } => self.eval_range_expr(
+                start.as_ref().as_ref(),
+                end.as_ref().as_ref(),
+                *inclusive,
+                ctx,
+            ),
             Expr::FString { parts: _ } => self.eval_fstring_expr(expr, ctx),
             Expr::Array(elements) => {
                 let expr_id = expr as *const Expr as usize;
@@ -541,10 +592,52 @@ impl<'ctx> Compiler<'ctx> {
             ),
             Expr::Await(expr) => self.eval_await_expr(expr.as_ref().as_ref(), ctx),
             Expr::Spawn(expr) => self.eval_spawn_expr(expr.as_ref().as_ref(), ctx),
-            _ => bail!("Expression type not implemented: {:?}", expr),
+            Expr::Index { base, index } => {
+                self.eval_index_expr(expr, base.as_ref().as_ref(), index.as_ref().as_ref(), ctx)
+            }
+            _ => bail!(CodegenError::unsupported_expr(format!(
+                "Expression type not implemented: {:?}",
+                expr
+            ))),
         }
     }
 
+    /// Build a range value from integer bounds; see [`OtterType::Range`].
+    fn eval_range_expr(
+        &mut self,
+        start: &Expr,
+        end: &Expr,
+        inclusive: bool,
+        ctx: &mut FunctionContext<'ctx>,
+    ) -> Result<EvaluatedValue<'ctx>> {
+        let mut range = self.range_struct_type().get_undef();
+        for (index, (bound, label)) in [(start, "start"), (end, "end")].into_iter().enumerate() {
+            let bound_val = self.eval_expr(bound, ctx)?;
+            let value = bound_val
+                .value
+                .ok_or_else(|| anyhow!("range {label} produced no value"))?;
+            let value = match bound_val.ty {
+                OtterType::I32 | OtterType::I64 => {
+                    self.coerce_type(value, bound_val.ty, OtterType::I64)?
+                }
+                other => bail!("range {label} must be an integer, got {other}"),
+            };
+            range = self
+                .builder
+                .build_insert_value(range, value, index as u32, label)?
+                .into_struct_value();
+        }
+        let inclusive = self
+            .context
+            .bool_type()
+            .const_int(u64::from(inclusive), false);
+        let range = self
+            .builder
+            .build_insert_value(range, inclusive, 2, "inclusive")?
+            .into_struct_value();
+        Ok(EvaluatedValue::with_value(range.into(), OtterType::Range))
+    }
+
     fn eval_match_expr(
         &mut self,
         expr: &Expr,
@@ -587,15 +680,35 @@ impl<'ctx> Compiler<'ctx> {
                 .context
                 .append_basic_block(function, &format!("match_arm_body_{}", i));
 
+            // With a guard, a successful pattern match still has to pass the
+            // guard before entering the body; a false guard tries the next arm.
+            let pattern_ok_bb = if arm.as_ref().guard.is_some() {
+                self.context
+                    .append_basic_block(function, &format!("match_arm_guard_{}", i))
+            } else {
+                body_bb
+            };
+
             self.compile_pattern_match(
                 &arm.as_ref().pattern,
                 &matched_val,
                 matched_type_info.clone(),
-                body_bb,
+                pattern_ok_bb,
                 next_check_bb,
                 ctx,
             )?;
 
+            if let Some(guard) = &arm.as_ref().guard {
+                self.builder.position_at_end(pattern_ok_bb);
+                let guard_val = self.eval_expr(guard.as_ref(), ctx)?;
+                let cond = guard_val
+                    .value
+                    .ok_or_else(|| anyhow!("Match guard produced no value"))?
+                    .into_int_value();
+                self.builder
+                    .build_conditional_branch(cond, body_bb, next_check_bb)?;
+            }
+
             self.builder.position_at_end(body_bb);
             let body_val = self.lower_block_expression(&arm.as_ref().body, function, ctx)?;
 
@@ -990,9 +1103,129 @@ impl<'ctx> Compiler<'ctx> {
 
                 Ok(())
             }
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let in_range = self.build_range_pattern_check(
+                    matched_val,
+                    start.as_ref(),
+                    end.as_ref(),
+                    *inclusive,
+                )?;
+                self.builder
+                    .build_conditional_branch(in_range, success_bb, fail_bb)?;
+                Ok(())
+            }
+            Pattern::Or(alternatives) => {
+                let function = self
+                    .builder
+                    .get_insert_block()
+                    .unwrap()
+                    .get_parent()
+                    .unwrap();
+
+                // Try each alternative in turn; only the last one falls through to fail_bb.
+                for (idx, alternative) in alternatives.iter().enumerate() {
+                    let next_bb = if idx + 1 < alternatives.len() {
+                        self.context
+                            .append_basic_block(function, &format!("or_alt_{}", idx + 1))
+                    } else {
+                        fail_bb
+                    };
+
+                    self.compile_pattern_match(
+                        alternative,
+                        matched_val,
+                        matched_type.clone(),
+                        success_bb,
+                        next_bb,
+                        ctx,
+                    )?;
+
+                    if idx + 1 < alternatives.len() {
+                        self.builder.position_at_end(next_bb);
+                    }
+                }
+
+                if alternatives.is_empty() {
+                    self.builder.build_unconditional_branch(fail_bb)?;
+                }
+                Ok(())
+            }
         }
     }
 
+    /// Builds `start <= value && value < end` (or `<= end` when inclusive),
+    /// skipping whichever side of the range is open.
+    fn build_range_pattern_check(
+        &mut self,
+        matched_val: &EvaluatedValue<'ctx>,
+        start: Option<&Node<Literal>>,
+        end: Option<&Node<Literal>>,
+        inclusive: bool,
+    ) -> Result<IntValue<'ctx>> {
+        let bound_type = match matched_val.ty {
+            OtterType::I64 | OtterType::I32 => TypeInfo::I64,
+            OtterType::F64 => TypeInfo::F64,
+            ref other => bail!("Range pattern not supported for type {}", other),
+        };
+        let mut value = matched_val
+            .value
+            .ok_or_else(|| anyhow!("Range pattern matched against void value"))?;
+        // Bounds are i64 literals, so compare an i32 at that width
+        if matched_val.ty == OtterType::I32 {
+            value = self
+                .builder
+                .build_int_s_extend(
+                    value.into_int_value(),
+                    self.context.i64_type(),
+                    "i32_to_i64",
+                )?
+                .into();
+        }
+
+        let mut in_range = self.context.bool_type().const_int(1, false);
+        let upper = if inclusive {
+            (IntPredicate::SLE, inkwell::FloatPredicate::OLE)
+        } else {
+            (IntPredicate::SLT, inkwell::FloatPredicate::OLT)
+        };
+        let checks = [
+            (start, (IntPredicate::SGE, inkwell::FloatPredicate::OGE)),
+            (end, upper),
+        ];
+
+        for (bound, (int_pred, float_pred)) in checks {
+            let Some(bound) = bound else {
+                continue;
+            };
+            let bound_val = self
+                .eval_literal(bound.as_ref(), Some(&bound_type))?
+                .value
+                .ok_or_else(|| anyhow!("Range pattern bound is void"))?;
+            let check = if bound_type == TypeInfo::I64 {
+                self.builder.build_int_compare(
+                    int_pred,
+                    value.into_int_value(),
+                    bound_val.into_int_value(),
+                    "range_bound",
+                )?
+            } else {
+                self.builder.build_float_compare(
+                    float_pred,
+                    value.into_float_value(),
+                    bound_val.into_float_value(),
+                    "range_bound",
+                )?
+            };
+            in_range = self.builder.build_and(in_range, check, "in_range")?;
+        }
+
+        Ok(in_range)
+    }
+
     fn resolve_enum_variant_fields(
         &self,
         enum_name: &str,
@@ -1057,7 +1290,7 @@ impl<'ctx> Compiler<'ctx> {
                 )?;
                 Ok(res_bool)
             }
-            _ => bail!("Equality check not implemented for type {:?}", lhs_ty),
+            _ => bail!("Equality check not implemented for type {}", lhs_ty),
         }
     }
 
@@ -1169,7 +1402,7 @@ impl<'ctx> Compiler<'ctx> {
                 }
             }
             Literal::String(s) => {
-                let val = self.builder.build_global_string_ptr(s, "str_lit")?;
+                let val = self.intern_string_literal(s)?;
                 Ok(EvaluatedValue::with_value(
                     val.as_pointer_value().into(),
                     OtterType::Str,
@@ -1187,6 +1420,17 @@ impl<'ctx> Compiler<'ctx> {
         }
     }
 
+    /// Returns the global holding `s`, emitting it on first use.
+    fn intern_string_literal(&mut self, s: &str) -> Result<inkwell::values::GlobalValue<'ctx>> {
+        if let Some(global) = self.string_literals.get(s) {
+            return Ok(*global);
+        }
+
+        let global = self.builder.build_global_string_ptr(s, "str_lit")?;
+        self.string_literals.insert(s.to_string(), global);
+        Ok(global)
+    }
+
     fn eval_binary_expr(
         &mut self,
         left: &Expr,
@@ -1194,6 +1438,10 @@ impl<'ctx> Compiler<'ctx> {
         right: &Expr,
         ctx: &mut FunctionContext<'ctx>,
     ) -> Result<EvaluatedValue<'ctx>> {
+        if matches!(op, BinaryOp::And | BinaryOp::Or) {
+            return self.eval_logical_expr(left, op, right, ctx);
+        }
+
         let lhs = self.eval_expr(left, ctx)?;
         let rhs = self.eval_expr(right, ctx)?;
         let lhs_ty = lhs.ty.clone();
@@ -1271,70 +1519,62 @@ impl<'ctx> Compiler<'ctx> {
             };
         }
 
-        // Coerce types if needed - promote to F64 if either operand is F64
+        // Mixed operands are coerced: to F64 if either side is a float,
+        // otherwise a narrower integer is widened to I64
+        let is_int = |ty: &OtterType| matches!(ty, OtterType::I32 | OtterType::I64);
         let (lhs_val, rhs_val, result_ty) = if lhs_ty == OtterType::F64 || rhs_ty == OtterType::F64
         {
-            // Promote both to F64
-            let l_f64 = if lhs_ty == OtterType::F64 {
-                lhs.value.unwrap().into_float_value()
-            } else if lhs_ty == OtterType::I64 {
-                let int_val = lhs.value.unwrap().into_int_value();
-                self.builder
-                    .build_signed_int_to_float(int_val, self.context.f64_type(), "itof")?
-            } else if lhs_ty == OtterType::Opaque {
-                let coerced = self.coerce_type(lhs.value.unwrap(), lhs_ty, OtterType::F64)?;
-                coerced.into_float_value()
-            } else {
-                bail!("Cannot coerce {:?} to F64", lhs_ty);
-            };
-
-            let r_f64 = if rhs_ty == OtterType::F64 {
-                rhs.value.unwrap().into_float_value()
-            } else if rhs_ty == OtterType::I64 {
-                let int_val = rhs.value.unwrap().into_int_value();
-                self.builder
-                    .build_signed_int_to_float(int_val, self.context.f64_type(), "itof")?
-            } else if rhs_ty == OtterType::Opaque {
-                let coerced = self.coerce_type(rhs.value.unwrap(), rhs_ty, OtterType::F64)?;
-                coerced.into_float_value()
-            } else {
-                bail!("Cannot coerce {:?} to F64", rhs_ty);
+            let promote = |value: EvaluatedValue<'ctx>| -> Result<BasicValueEnum<'ctx>> {
+                let ty = value.ty.clone();
+                if ty != OtterType::F64 && !is_int(&ty) && ty != OtterType::Opaque {
+                    bail!(CodegenError::type_mismatch(format!(
+                        "Cannot coerce {} to float",
+                        ty
+                    )));
+                }
+                self.coerce_type(value.value.unwrap(), ty, OtterType::F64)
             };
-
-            (l_f64.into(), r_f64.into(), OtterType::F64)
-        } else if lhs_ty == OtterType::I64 && rhs_ty == OtterType::I64 {
-            (lhs.value.unwrap(), rhs.value.unwrap(), OtterType::I64)
+            (promote(lhs)?, promote(rhs)?, OtterType::F64)
+        } else if lhs_ty == rhs_ty && is_int(&lhs_ty) {
+            (lhs.value.unwrap(), rhs.value.unwrap(), lhs_ty)
+        } else if is_int(&lhs_ty) && is_int(&rhs_ty) {
+            let l = self.coerce_type(lhs.value.unwrap(), lhs_ty, OtterType::I64)?;
+            let r = self.coerce_type(rhs.value.unwrap(), rhs_ty, OtterType::I64)?;
+            (l, r, OtterType::I64)
         } else if lhs_ty == OtterType::Bool && rhs_ty == OtterType::Bool {
             (lhs.value.unwrap(), rhs.value.unwrap(), OtterType::Bool)
         } else {
-            bail!(
-                "Type mismatch or unsupported types for binary op: {:?} and {:?}",
-                lhs_ty,
-                rhs_ty
-            );
+            bail!(CodegenError::type_mismatch(format!(
+                "Type mismatch or unsupported types for binary op: {} and {}",
+                lhs_ty, rhs_ty
+            )));
         };
 
         // Perform the operation based on the result type
         match result_ty {
-            OtterType::I64 => {
+            OtterType::I32 | OtterType::I64 => {
                 let l = lhs_val.into_int_value();
                 let r = rhs_val.into_int_value();
                 match op {
                     BinaryOp::Add => Ok(EvaluatedValue::with_value(
                         self.builder.build_int_add(l, r, "add")?.into(),
-                        OtterType::I64,
+                        result_ty,
                     )),
                     BinaryOp::Sub => Ok(EvaluatedValue::with_value(
                         self.builder.build_int_sub(l, r, "sub")?.into(),
-                        OtterType::I64,
+                        result_ty,
                     )),
                     BinaryOp::Mul => Ok(EvaluatedValue::with_value(
                         self.builder.build_int_mul(l, r, "mul")?.into(),
-                        OtterType::I64,
+                        result_ty,
                     )),
                     BinaryOp::Div => Ok(EvaluatedValue::with_value(
                         self.builder.build_int_signed_div(l, r, "div")?.into(),
-                        OtterType::I64,
+                        result_ty,
+                    )),
+                    BinaryOp::Mod => Ok(EvaluatedValue::with_value(
+                        self.builder.build_int_signed_rem(l, r, "rem")?.into(),
+                        result_ty,
                     )),
                     BinaryOp::Eq => Ok(EvaluatedValue::with_value(
                         self.builder
@@ -1372,7 +1612,7 @@ impl<'ctx> Compiler<'ctx> {
                             .into(),
                         OtterType::Bool,
                     )),
-                    _ => bail!("Unsupported binary op for I64"),
+                    _ => bail!("Unsupported binary op for int"),
                 }
             }
             OtterType::F64 => {
@@ -1395,6 +1635,12 @@ impl<'ctx> Compiler<'ctx> {
                         self.builder.build_float_div(l, r, "div")?.into(),
                         OtterType::F64,
                     )),
+                    BinaryOp::Mod => Ok(EvaluatedValue::with_value(
+                        self.builder.build_float_rem(l, r, "rem")?.into(),
+                        OtterType::F64,
+                    )),
+                    // IEEE 754 semantics: every comparison involving NaN is
+                    // false except `!=`, which stays the negation of `==`
                     BinaryOp::Eq => Ok(EvaluatedValue::with_value(
                         self.builder
                             .build_float_compare(inkwell::FloatPredicate::OEQ, l, r, "eq")?
@@ -1403,7 +1649,7 @@ impl<'ctx> Compiler<'ctx> {
                     )),
                     BinaryOp::Ne => Ok(EvaluatedValue::with_value(
                         self.builder
-                            .build_float_compare(inkwell::FloatPredicate::ONE, l, r, "ne")?
+                            .build_float_compare(inkwell::FloatPredicate::UNE, l, r, "ne")?
                             .into(),
                         OtterType::Bool,
                     )),
@@ -1431,13 +1677,85 @@ impl<'ctx> Compiler<'ctx> {
                             .into(),
                         OtterType::Bool,
                     )),
-                    _ => bail!("Unsupported binary op for F64"),
+                    _ => bail!("Unsupported binary op for float"),
                 }
             }
             _ => bail!("Unsupported type for binary operation"),
         }
     }
 
+    /// Lowers `and`/`or`, evaluating the right operand only when the left
+    /// one does not already decide the result.
+    fn eval_logical_expr(
+        &mut self,
+        left: &Expr,
+        op: &BinaryOp,
+        right: &Expr,
+        ctx: &mut FunctionContext<'ctx>,
+    ) -> Result<EvaluatedValue<'ctx>> {
+        let is_and = matches!(op, BinaryOp::And);
+        let op_name = if is_and { "and" } else { "or" };
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|bb| bb.get_parent())
+            .ok_or_else(|| anyhow!("`{}` outside of a function", op_name))?;
+
+        let lhs = self.eval_expr(left, ctx)?;
+        if lhs.ty != OtterType::Bool {
+            bail!(CodegenError::type_mismatch(format!(
+                "`{}` expects bool operands, found {}",
+                op_name, lhs.ty
+            )));
+        }
+        let lhs_val = self.to_bool_value(lhs)?;
+        let lhs_bb = self
+            .builder
+            .get_insert_block()
+            .ok_or_else(|| anyhow!("no insert block for `{}`", op_name))?;
+
+        let rhs_bb = self
+            .context
+            .append_basic_block(function, &format!("{}_rhs", op_name));
+        let merge_bb = self
+            .context
+            .append_basic_block(function, &format!("{}_end", op_name));
+        if is_and {
+            self.builder
+                .build_conditional_branch(lhs_val, rhs_bb, merge_bb)?;
+        } else {
+            self.builder
+                .build_conditional_branch(lhs_val, merge_bb, rhs_bb)?;
+        }
+
+        self.builder.position_at_end(rhs_bb);
+        let rhs = self.eval_expr(right, ctx)?;
+        if rhs.ty != OtterType::Bool {
+            bail!(CodegenError::type_mismatch(format!(
+                "`{}` expects bool operands, found {}",
+                op_name, rhs.ty
+            )));
+        }
+        let rhs_val = self.to_bool_value(rhs)?;
+        // The right operand may have branched; the phi needs the block it ended in
+        let rhs_end_bb = self
+            .builder
+            .get_insert_block()
+            .ok_or_else(|| anyhow!("no insert block for `{}`", op_name))?;
+        self.builder.build_unconditional_branch(merge_bb)?;
+
+        self.builder.position_at_end(merge_bb);
+        let bool_type = self.context.bool_type();
+        // Skipping the right operand means `and` saw false and `or` saw true
+        let short_circuit = bool_type.const_int(u64::from(!is_and), false);
+        let phi = self.builder.build_phi(bool_type, op_name)?;
+        phi.add_incoming(&[(&short_circuit, lhs_bb), (&rhs_val, rhs_end_bb)]);
+        Ok(EvaluatedValue::with_value(
+            phi.as_basic_value(),
+            OtterType::Bool,
+        ))
+    }
+
     fn eval_unary_expr(
         &mut self,
         op: &UnaryOp,
@@ -1461,7 +1779,7 @@ impl<'ctx> Compiler<'ctx> {
                         OtterType::F64,
                     ))
                 } else {
-                    bail!("Unsupported type for negation");
+                    bail!(CodegenError::type_mismatch("Unsupported type for negation"));
                 }
             }
             UnaryOp::Not => {
@@ -1473,7 +1791,7 @@ impl<'ctx> Compiler<'ctx> {
                         OtterType::Bool,
                     ))
                 } else {
-                    bail!("Unsupported type for not");
+                    bail!(CodegenError::type_mismatch("Unsupported type for not"));
                 }
             }
         }
@@ -1501,9 +1819,22 @@ impl<'ctx> Compiler<'ctx> {
                 }
                 Ok(Some(self.context.struct_type(&llvm_fields, false).into()))
             }
+            OtterType::Range => Ok(Some(self.range_struct_type().into())),
         }
     }
 
+    pub(crate) fn range_struct_type(&self) -> StructType<'ctx> {
+        let i64_type = self.context.i64_type();
+        self.context.struct_type(
+            &[
+                i64_type.into(),
+                i64_type.into(),
+                self.context.bool_type().into(),
+            ],
+            false,
+        )
+    }
+
     pub(crate) fn to_bool_value(&self, val: EvaluatedValue<'ctx>) -> Result<IntValue<'ctx>> {
         let EvaluatedValue { ty, value } = val;
         if ty == OtterType::Bool {
@@ -1659,11 +1990,48 @@ impl<'ctx> Compiler<'ctx> {
 
             // Incompatible types
             _ => {
-                bail!("Cannot coerce type {:?} to {:?}", from_ty, to_ty)
+                bail!(CodegenError::type_mismatch(format!(
+                    "Cannot coerce type {} to {}",
+                    from_ty, to_ty
+                )))
             }
         }
     }
 
+    /// Reject an argument that cannot be passed as parameter `index` of the
+    /// user function `name`. Calls to runtime functions are not checked.
+    fn check_argument_type(&self, name: &str, index: usize, actual: &OtterType) -> Result<()> {
+        let Some(expected) = self
+            .function_signatures
+            .get(name)
+            .and_then(|signature| signature.params.get(index))
+        else {
+            return Ok(());
+        };
+        // Numbers are converted by `cast_argument_for_call`
+        let accepted = expected == actual
+            || matches!(
+                (expected, actual),
+                (OtterType::Opaque, _)
+                    | (_, OtterType::Opaque)
+                    | (
+                        OtterType::I32 | OtterType::I64 | OtterType::F64,
+                        OtterType::I32 | OtterType::I64 | OtterType::F64,
+                    )
+                    | (OtterType::List(_), OtterType::List(_))
+            );
+        if !accepted {
+            bail!(CodegenError::type_mismatch(format!(
+                "argument {} of `{}` expects {}, found {}",
+                index + 1,
+                name,
+                expected,
+                actual
+            )));
+        }
+        Ok(())
+    }
+
     fn cast_argument_for_call(
         &self,
         value: BasicValueEnum<'ctx>,
@@ -1678,155 +2046,100 @@ impl<'ctx> Compiler<'ctx> {
                 .into())
         } else if value.get_type() != *param_type
             && value.get_type().is_struct_type()
-            && param_type.is_struct_type()
+            && !param_type.is_pointer_type()
         {
             // Repack aggregates whose LLVM struct names don't match (e.g., named Otter structs
-            // passed to anonymous FFI structs).
+            // passed to anonymous FFI structs), or that the C ABI passes as register pieces.
+            // The pieces cover at least the aggregate, so the slot takes their type.
             let current_function = self
                 .builder
                 .get_insert_block()
                 .and_then(|bb| bb.get_parent())
                 .ok_or_else(|| anyhow!("Cannot determine current function for argument cast"))?;
-            let tmp = self.create_entry_block_alloca(current_function, "struct_cast", from_ty)?;
+            let tmp = self.create_entry_block_alloca_of_type(
+                current_function,
+                "struct_cast",
+                *param_type,
+            )?;
             self.builder.build_store(tmp, value)?;
-            let generic_ptr = self.raw_ptr_type();
-            let cast_ptr = self
-                .builder
-                .build_bit_cast(tmp, generic_ptr, "struct_cast_ptr")?
-                .into_pointer_value();
             let loaded = self
                 .builder
-                .build_load(*param_type, cast_ptr, "struct_cast_load")?;
+                .build_load(*param_type, tmp, "struct_cast_load")?;
             Ok(loaded)
+        } else if value.get_type().is_struct_type() && param_type.is_pointer_type() {
+            // Aggregates the C ABI passes in memory go by pointer; spill the
+            // value to a stack slot and pass its address.
+            let current_function = self
+                .builder
+                .get_insert_block()
+                .and_then(|bb| bb.get_parent())
+                .ok_or_else(|| anyhow!("Cannot determine current function for argument cast"))?;
+            let slot = self.create_entry_block_alloca_of_type(
+                current_function,
+                "struct_arg",
+                value.get_type(),
+            )?;
+            self.builder.build_store(slot, value)?;
+            Ok(slot.into())
         } else {
             Ok(value)
         }
     }
 
+    /// Whether `arg` points into the current function's stack frame.
+    fn is_stack_slot(arg: BasicMetadataValueEnum<'ctx>) -> bool {
+        matches!(arg, BasicMetadataValueEnum::PointerValue(ptr)
+            if ptr
+                .as_instruction()
+                .is_some_and(|inst| inst.get_opcode() == InstructionOpcode::Alloca))
+    }
+
     fn eval_call_expr(
         &mut self,
         expr: &Expr,
         ctx: &mut FunctionContext<'ctx>,
     ) -> Result<EvaluatedValue<'ctx>> {
-        if let Expr::Call { func, args } = expr {
-            let mut implicit_self: Option<EvaluatedValue<'ctx>> = None;
-            if let Some(enum_value) =
-                self.try_build_enum_constructor(expr, func.as_ref().as_ref(), args, ctx)?
-            {
-                return Ok(enum_value);
-            }
-
-            // Evaluate function expression
-            let func_name = match func.as_ref().as_ref() {
-                Expr::Identifier(name) => name.clone(),
-                Expr::Member { object, field } => {
-                    // First, try to evaluate the object to check its runtime type
-                    // This handles cases like list.append() where the object is a variable
-                    if let Ok(evaluated) = self.eval_expr(object.as_ref().as_ref(), ctx) {
-                        if evaluated.value.is_some() {
-                            // Check if it's a list type and handle list methods
-                            if matches!(evaluated.ty, OtterType::List(_)) {
-                                if field == "append" && !args.is_empty() {
-                                    // Determine the append function based on argument type
-                                    let arg_val = self.eval_expr(args[0].as_ref(), ctx)?;
-                                    let method_name: String = match arg_val.ty {
-                                        OtterType::Str => "append<list,string>".to_string(),
-                                        OtterType::I64 | OtterType::I32 => {
-                                            "append<list,int>".to_string()
-                                        }
-                                        OtterType::F64 => "append<list,float>".to_string(),
-                                        OtterType::Bool => "append<list,bool>".to_string(),
-                                        OtterType::List(_) | OtterType::Opaque => {
-                                            "append<list,list>".to_string()
-                                        }
-                                        _ => bail!(
-                                            "unsupported append argument type: {:?}",
-                                            arg_val.ty
-                                        ),
-                                    };
-                                    implicit_self = Some(evaluated);
-                                    method_name
-                                } else {
-                                    bail!(
-                                        "list method '{}' not supported or missing arguments",
-                                        field
-                                    );
-                                }
-                            } else if let OtterType::Struct(struct_id) = evaluated.ty.clone() {
-                                if let Some(method_name) =
-                                    self.resolve_struct_method_name(struct_id, field)
-                                {
-                                    implicit_self = Some(evaluated);
-                                    method_name
-                                } else {
-                                    bail!(
-                                        "struct method '{}.{}' not found",
-                                        self.struct_info(struct_id).name,
-                                        field
-                                    );
-                                }
-                            } else {
-                                // Not a list or struct, continue with other checks
-                                if let Expr::Identifier(enum_name) = object.as_ref().as_ref() {
-                                    if let Some(enum_value) = self
-                                        .try_build_enum_from_member(enum_name, field, args, ctx)?
-                                    {
-                                        return Ok(enum_value);
-                                    }
-                                    format!("{}.{}", enum_name, field)
-                                } else {
-                                    bail!("Complex member expressions not yet supported");
-                                }
-                            }
-                        } else {
-                            // Value is None, try enum constructor path
-                            if let Expr::Identifier(enum_name) = object.as_ref().as_ref() {
-                                if let Some(enum_value) =
-                                    self.try_build_enum_from_member(enum_name, field, args, ctx)?
-                                {
-                                    return Ok(enum_value);
-                                }
-                                format!("{}.{}", enum_name, field)
-                            } else {
-                                bail!("cannot call member '{}' without value", field);
-                            }
-                        }
-                    } else if let Expr::Identifier(enum_name) = object.as_ref().as_ref() {
-                        // Fallback: try enum constructor
-                        if let Some(enum_value) =
-                            self.try_build_enum_from_member(enum_name, field, args, ctx)?
-                        {
-                            return Ok(enum_value);
-                        }
-                        format!("{}.{}", enum_name, field)
-                    } else if let Some(OtterType::Struct(struct_id)) =
-                        self.struct_type_from_expr(object.as_ref().as_ref())
-                    {
-                        let self_value = self.eval_expr(object.as_ref().as_ref(), ctx)?;
-                        if self_value.value.is_none() {
-                            bail!("cannot call method '{}' without value", field);
-                        }
-                        if let Some(method_name) = self.resolve_struct_method_name(struct_id, field)
-                        {
-                            implicit_self = Some(self_value);
-                            method_name
-                        } else {
-                            bail!(
-                                "struct method '{}.{}' not found",
-                                self.struct_info(struct_id).name,
-                                field
-                            );
-                        }
-                    } else {
-                        // Evaluate the object first to check its runtime type
-                        let evaluated = self.eval_expr(object.as_ref().as_ref(), ctx)?;
-                        if evaluated.value.is_none() {
-                            bail!("cannot call member '{}' without value", field);
-                        }
+        if let Expr::Call { func, args, kwargs } = expr {
+            self.eval_call(expr, func.as_ref().as_ref(), args, kwargs, ctx)
+        } else {
+            bail!("Expected Call expression");
+        }
+    }
 
+    fn eval_call(
+        &mut self,
+        call_expr: &Expr,
+        func: &Expr,
+        args: &[Node<Expr>],
+        kwargs: &[(String, Node<Expr>)],
+        ctx: &mut FunctionContext<'ctx>,
+    ) -> Result<EvaluatedValue<'ctx>> {
+        // Only the outermost call of `return f(...)` is in tail position
+        let tail_call = mem::take(&mut ctx.tail_call);
+        let mut implicit_self: Option<EvaluatedValue<'ctx>> = None;
+        if let Some(enum_value) = self.try_build_enum_constructor(call_expr, func, args, ctx)? {
+            return Ok(enum_value);
+        }
+
+        // Evaluate function expression
+        let func_name = match func {
+            Expr::Identifier(name) => name.clone(),
+            Expr::Member { object, field } => {
+                // A function of a module path like `std.io` resolves straight
+                // to the registry; there is no object to evaluate
+                let module_function = self
+                    .module_path_from_expr(object.as_ref().as_ref())
+                    .and_then(|_| {
+                        self.resolve_member_function_name(object.as_ref().as_ref(), field)
+                    });
+                if let Some(name) = module_function {
+                    name
+                } else if let Ok(evaluated) = self.eval_expr(object.as_ref().as_ref(), ctx) {
+                    // Evaluating the object tells its runtime type; this handles
+                    // cases like list.append() where the object is a variable
+                    if evaluated.value.is_some() {
                         // Check if it's a list type and handle list methods
                         if matches!(evaluated.ty, OtterType::List(_)) {
-                            // Handle list method calls like list.append()
                             if field == "append" && !args.is_empty() {
                                 // Determine the append function based on argument type
                                 let arg_val = self.eval_expr(args[0].as_ref(), ctx)?;
@@ -1840,9 +2153,7 @@ impl<'ctx> Compiler<'ctx> {
                                     OtterType::List(_) | OtterType::Opaque => {
                                         "append<list,list>".to_string()
                                     }
-                                    _ => {
-                                        bail!("unsupported append argument type: {:?}", arg_val.ty)
-                                    }
+                                    _ => bail!("unsupported append argument type: {}", arg_val.ty),
                                 };
                                 implicit_self = Some(evaluated);
                                 method_name
@@ -1862,151 +2173,468 @@ impl<'ctx> Compiler<'ctx> {
                                     field
                                 );
                             }
-                        } else if let Some(func_name) =
-                            self.resolve_member_function_name(object.as_ref().as_ref(), field)
+                        } else {
+                            // Not a list or struct, continue with other checks
+                            if let Expr::Identifier(enum_name) = object.as_ref().as_ref() {
+                                if let Some(enum_value) =
+                                    self.try_build_enum_from_member(enum_name, field, args, ctx)?
+                                {
+                                    return Ok(enum_value);
+                                }
+                                format!("{}.{}", enum_name, field)
+                            } else {
+                                bail!(CodegenError::unsupported_expr(
+                                    "Complex member expressions not yet supported"
+                                ));
+                            }
+                        }
+                    } else {
+                        // Value is None, try enum constructor path
+                        if let Expr::Identifier(enum_name) = object.as_ref().as_ref() {
+                            if let Some(enum_value) =
+                                self.try_build_enum_from_member(enum_name, field, args, ctx)?
+                            {
+                                return Ok(enum_value);
+                            }
+                            format!("{}.{}", enum_name, field)
+                        } else {
+                            bail!("cannot call member '{}' without value", field);
+                        }
+                    }
+                } else if let Expr::Identifier(enum_name) = object.as_ref().as_ref() {
+                    // Fallback: try enum constructor
+                    if let Some(enum_value) =
+                        self.try_build_enum_from_member(enum_name, field, args, ctx)?
+                    {
+                        return Ok(enum_value);
+                    }
+                    format!("{}.{}", enum_name, field)
+                } else if let Some(OtterType::Struct(struct_id)) =
+                    self.struct_type_from_expr(object.as_ref().as_ref())
+                {
+                    let self_value = self.eval_expr(object.as_ref().as_ref(), ctx)?;
+                    if self_value.value.is_none() {
+                        bail!("cannot call method '{}' without value", field);
+                    }
+                    if let Some(method_name) = self.resolve_struct_method_name(struct_id, field) {
+                        implicit_self = Some(self_value);
+                        method_name
+                    } else {
+                        bail!(
+                            "struct method '{}.{}' not found",
+                            self.struct_info(struct_id).name,
+                            field
+                        );
+                    }
+                } else {
+                    // Evaluate the object first to check its runtime type
+                    let evaluated = self.eval_expr(object.as_ref().as_ref(), ctx)?;
+                    if evaluated.value.is_none() {
+                        bail!("cannot call member '{}' without value", field);
+                    }
+
+                    // Check if it's a list type and handle list methods
+                    if matches!(evaluated.ty, OtterType::List(_)) {
+                        // Handle list method calls like list.append()
+                        if field == "append" && !args.is_empty() {
+                            // Determine the append function based on argument type
+                            let arg_val = self.eval_expr(args[0].as_ref(), ctx)?;
+                            let method_name: String = match arg_val.ty {
+                                OtterType::Str => "append<list,string>".to_string(),
+                                OtterType::I64 | OtterType::I32 => "append<list,int>".to_string(),
+                                OtterType::F64 => "append<list,float>".to_string(),
+                                OtterType::Bool => "append<list,bool>".to_string(),
+                                OtterType::List(_) | OtterType::Opaque => {
+                                    "append<list,list>".to_string()
+                                }
+                                _ => {
+                                    bail!("unsupported append argument type: {}", arg_val.ty)
+                                }
+                            };
+                            implicit_self = Some(evaluated);
+                            method_name
+                        } else {
+                            bail!("list method '{}' not supported or missing arguments", field);
+                        }
+                    } else if let OtterType::Struct(struct_id) = evaluated.ty.clone() {
+                        if let Some(method_name) = self.resolve_struct_method_name(struct_id, field)
                         {
-                            func_name
+                            implicit_self = Some(evaluated);
+                            method_name
                         } else {
-                            bail!("Complex member expressions not yet supported (call)");
+                            bail!(
+                                "struct method '{}.{}' not found",
+                                self.struct_info(struct_id).name,
+                                field
+                            );
                         }
+                    } else if let Some(func_name) =
+                        self.resolve_member_function_name(object.as_ref().as_ref(), field)
+                    {
+                        func_name
+                    } else {
+                        bail!(CodegenError::unsupported_expr(
+                            "Complex member expressions not yet supported (call)"
+                        ));
                     }
                 }
-                _ => bail!("Complex function expressions not yet supported"),
-            };
+            }
+            _ => bail!(CodegenError::unsupported_expr(
+                "Complex function expressions not yet supported"
+            )),
+        };
 
-            // Handle overloaded builtins like len() - evaluate first arg to determine type
-            let (function, resolved_func_name, first_arg_evaluated) =
-                if func_name == "len" && !args.is_empty() {
-                    // Evaluate the first argument to determine its type
-                    let arg_val = self.eval_expr(args[0].as_ref(), ctx)?;
-                    let overloaded_name = match arg_val.ty {
+        // Handle overloaded builtins like len() and str() - evaluate first arg to determine type
+        let (function, resolved_func_name, first_arg_evaluated) =
+            if (func_name == "len" || func_name == "str") && !args.is_empty() {
+                // Evaluate the first argument to determine its type
+                let arg_val = self.eval_expr(args[0].as_ref(), ctx)?;
+                let overloaded_name = if func_name == "str" {
+                    Self::str_overload(&arg_val.ty)
+                        .ok_or_else(|| anyhow!("str() not supported for type {}", arg_val.ty))?
+                        .to_string()
+                } else {
+                    match arg_val.ty {
                         OtterType::Str => "len".to_string(),
                         OtterType::List(_) => "len<list>".to_string(),
                         OtterType::Map => "len<map>".to_string(),
-                        _ => bail!("len() not supported for type {:?}", arg_val.ty),
-                    };
-                    if self.symbol_registry.contains(&overloaded_name) {
-                        (
-                            self.get_or_declare_ffi_function(&overloaded_name)?,
-                            overloaded_name,
-                            Some(arg_val),
-                        )
-                    } else {
-                        bail!("Function {} not found", overloaded_name);
+                        _ => bail!("len() not supported for type {}", arg_val.ty),
                     }
-                } else if let Some(func) = self.declared_functions.get(&func_name) {
-                    (*func, func_name.clone(), None)
-                } else if self.symbol_registry.contains(&func_name) {
+                };
+                if self.symbol_registry.contains(&overloaded_name) {
                     (
-                        self.get_or_declare_ffi_function(&func_name)?,
-                        func_name.clone(),
-                        None,
+                        self.get_or_declare_ffi_function(&overloaded_name)?,
+                        overloaded_name,
+                        Some(arg_val),
                     )
                 } else {
-                    bail!("Function {} not found", func_name);
+                    bail!(CodegenError::unknown_identifier(overloaded_name));
+                }
+            } else if self.symbol_registry.is_overloaded(&func_name) {
+                // Each overload is declared under its own key so its sret and
+                // ownership details stay separate
+                let arg_types: Vec<_> = args
+                    .iter()
+                    .map(|arg| self.expr_type(arg.as_ref()).and_then(TypeInfo::to_ffi_type))
+                    .collect();
+                let ffi_func = self
+                    .symbol_registry
+                    .resolve_call(&func_name, &arg_types)
+                    .map_err(|err| anyhow!("{err}"))?;
+                let key = format!("{func_name}/{}", ffi_func.symbol);
+                let function = match self.declared_functions.get(&key) {
+                    Some(function) => *function,
+                    None => self.declare_external_function(&key, &ffi_func)?,
                 };
+                (function, key, None)
+            } else if let Some(func) = self.declared_functions.get(&func_name) {
+                (*func, func_name.clone(), None)
+            } else if self.symbol_registry.contains(&func_name) {
+                (
+                    self.get_or_declare_ffi_function(&func_name)?,
+                    func_name.clone(),
+                    None,
+                )
+            } else {
+                bail!(CodegenError::unknown_identifier(func_name));
+            };
 
-            // Get parameter types upfront to avoid borrow issues
-            let param_types: Vec<BasicTypeEnum> = function
-                .get_param_iter()
-                .map(|arg| arg.get_type())
-                .collect();
+        // Get parameter types upfront to avoid borrow issues
+        let param_types: Vec<BasicTypeEnum> = function
+            .get_param_iter()
+            .map(|arg| arg.get_type())
+            .collect();
 
-            // Evaluate arguments and convert types as needed
-            let mut arg_values: Vec<BasicMetadataValueEnum> = Vec::new();
-            let mut param_offset = 0;
+        // Evaluate arguments and convert types as needed
+        let mut arg_values: Vec<BasicMetadataValueEnum> = Vec::new();
+        let mut param_offset = 0;
 
-            if let Some(self_arg) = implicit_self {
-                let v = self_arg
-                    .value
-                    .ok_or_else(|| anyhow!("Cannot pass unit value as self"))?;
-                let param_type = param_types.first().ok_or_else(|| {
-                    anyhow!("Method '{}' missing self parameter", resolved_func_name)
-                })?;
-                let converted = self.cast_argument_for_call(v, self_arg.ty.clone(), param_type)?;
-                arg_values.push(converted.into());
+        // Aggregates returned via `sret` come back through a caller-provided slot.
+        let sret = match self.sret_return_types.get(&resolved_func_name).copied() {
+            Some(ret_type) => {
+                let current_function = self
+                    .builder
+                    .get_insert_block()
+                    .and_then(|bb| bb.get_parent())
+                    .ok_or_else(|| anyhow!("Cannot determine current function for call"))?;
+                let slot = self.create_entry_block_alloca_of_type(
+                    current_function,
+                    "sret_slot",
+                    ret_type,
+                )?;
+                arg_values.push(slot.into());
                 param_offset = 1;
+                Some((slot, ret_type))
             }
+            None => None,
+        };
 
-            for (i, arg) in args.iter().enumerate() {
-                // Reuse first arg if it was already evaluated for len() dispatch
-                let arg_val = if i == 0 {
-                    if let Some(val) = first_arg_evaluated.as_ref() {
-                        val.clone()
-                    } else {
-                        self.eval_expr(arg.as_ref(), ctx)?
-                    }
+        if let Some(self_arg) = implicit_self {
+            let v = self_arg
+                .value
+                .ok_or_else(|| anyhow!("Cannot pass unit value as self"))?;
+            let param_type = param_types
+                .get(param_offset)
+                .ok_or_else(|| anyhow!("Method '{}' missing self parameter", resolved_func_name))?;
+            let converted = self.cast_argument_for_call(v, self_arg.ty.clone(), param_type)?;
+            arg_values.push(converted.into());
+            param_offset += 1;
+        }
+
+        // `*args` occupies the last slot; positional arguments past the fixed
+        // parameters are packed into a list for it.
+        let fixed_params = if self.variadic_functions.contains(&resolved_func_name) {
+            param_types.len().saturating_sub(1)
+        } else {
+            param_types.len()
+        };
+        let fixed_args = args.len().min(fixed_params.saturating_sub(param_offset));
+        let (args, rest_args) = args.split_at(fixed_args);
+
+        // Owned strings passed to a borrowing runtime function are freed once it returns.
+        let borrowing = self.is_borrowing_callee(&resolved_func_name);
+        let mut temporaries = Vec::new();
+        for (i, arg) in args.iter().enumerate() {
+            // Reuse first arg if it was already evaluated for len() dispatch
+            let arg_val = if i == 0 {
+                if let Some(val) = first_arg_evaluated.as_ref() {
+                    val.clone()
                 } else {
                     self.eval_expr(arg.as_ref(), ctx)?
-                };
-                if let Some(v) = arg_val.value {
-                    let param_type = param_types.get(i + param_offset).ok_or_else(|| {
-                        anyhow!("Too many arguments for function {}", resolved_func_name)
+                }
+            } else {
+                self.eval_expr(arg.as_ref(), ctx)?
+            };
+            if let Some(v) = arg_val.value {
+                let param_type = param_types.get(i + param_offset).ok_or_else(|| {
+                    anyhow!("Too many arguments for function {}", resolved_func_name)
+                })?;
+                self.check_argument_type(&resolved_func_name, i + param_offset, &arg_val.ty)?;
+                if borrowing
+                    && matches!(arg_val.ty, OtterType::List(_))
+                    && param_type.is_pointer_type()
+                {
+                    // A list passed where a string is expected, as in `print(xs)`,
+                    // is formatted first.
+                    let formatted =
+                        self.call_ffi_returning_value("fmt.list", vec![v], "fmt_list")?;
+                    arg_values.push(formatted.into());
+                    if let Some(release) = self.release_function("fmt.list") {
+                        temporaries.push((formatted, release));
+                    }
+                    continue;
+                }
+                if matches!(resolved_func_name.as_str(), "print" | "println")
+                    && arg_val.ty != OtterType::Str
+                    && let Some(stringify) = Self::str_overload(&arg_val.ty)
+                {
+                    // `print(42)` prints the value as `str(42)` would
+                    let value = match arg_val.ty {
+                        OtterType::I32 => self.coerce_type(v, OtterType::I32, OtterType::I64)?,
+                        _ => v,
+                    };
+                    let formatted =
+                        self.call_ffi_returning_value(stringify, vec![value], "fmt_arg")?;
+                    arg_values.push(formatted.into());
+                    if let Some(release) = self.release_function(stringify) {
+                        temporaries.push((formatted, release));
+                    }
+                    continue;
+                }
+                let converted = self.cast_argument_for_call(v, arg_val.ty.clone(), param_type)?;
+                arg_values.push(converted.into());
+                if borrowing && let Some(release) = self.owned_result_release(arg.as_ref()) {
+                    temporaries.push((v, release));
+                }
+            } else {
+                bail!("Cannot pass unit value as argument");
+            }
+        }
+
+        let variadic_list = if fixed_params < param_types.len() {
+            let create_fn = self.get_or_declare_ffi_function("list.new")?;
+            let handle = self
+                .builder
+                .build_call(create_fn, &[], "variadic_args")?
+                .try_as_basic_value()
+                .left()
+                .ok_or_else(|| anyhow!("list creation returned void"))?
+                .into_int_value();
+            for (idx, arg) in rest_args.iter().enumerate() {
+                let arg_val = self.eval_expr(arg.as_ref(), ctx)?;
+                let value = arg_val
+                    .value
+                    .ok_or_else(|| anyhow!("Cannot pass unit value as argument"))?;
+                self.append_value_to_list(handle, value, arg_val.ty, &format!("vararg_{}", idx))?;
+            }
+            Some(handle)
+        } else if !rest_args.is_empty() {
+            bail!("Too many arguments for function {}", resolved_func_name);
+        } else {
+            None
+        };
+
+        // Bind keyword arguments to their parameter slots, evaluating them in source order
+        if !kwargs.is_empty() {
+            let param_names = self
+                .function_param_names
+                .get(&resolved_func_name)
+                .cloned()
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Function {} does not accept keyword arguments",
+                        resolved_func_name
+                    )
+                })?;
+            let first_slot = arg_values.len();
+            let mut slots: Vec<Option<BasicMetadataValueEnum>> =
+                vec![None; fixed_params.saturating_sub(first_slot)];
+            for (name, arg) in kwargs {
+                let index = param_names
+                    .iter()
+                    .position(|param| param == name)
+                    .filter(|index| (first_slot..fixed_params).contains(index))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Unknown keyword argument `{}` for function {}",
+                            name,
+                            resolved_func_name
+                        )
                     })?;
-                    let converted =
-                        self.cast_argument_for_call(v, arg_val.ty.clone(), param_type)?;
+                let arg_val = self.eval_expr(arg.as_ref(), ctx)?;
+                let v = arg_val
+                    .value
+                    .ok_or_else(|| anyhow!("Cannot pass unit value as argument"))?;
+                self.check_argument_type(&resolved_func_name, index, &arg_val.ty)?;
+                let converted =
+                    self.cast_argument_for_call(v, arg_val.ty.clone(), &param_types[index])?;
+                slots[index - first_slot] = Some(converted.into());
+            }
+            let defaults = self
+                .function_defaults
+                .get(&resolved_func_name)
+                .cloned()
+                .unwrap_or_default();
+            for (i, slot) in (first_slot..).zip(slots) {
+                if let Some(value) = slot {
+                    arg_values.push(value);
+                    continue;
+                }
+                let default_expr = defaults.get(i).cloned().flatten().ok_or_else(|| {
+                    anyhow!(
+                        "Missing argument `{}` for function {}",
+                        param_names[i],
+                        resolved_func_name
+                    )
+                })?;
+                let val = self.eval_expr(&default_expr, ctx)?;
+                if let Some(v) = val.value {
+                    let converted = self.cast_argument_for_call(v, val.ty, &param_types[i])?;
                     arg_values.push(converted.into());
                 } else {
-                    bail!("Cannot pass unit value as argument");
+                    bail!("Default value for argument {} evaluated to void", i);
                 }
             }
+        }
 
-            // Fill in default values for missing arguments
-            if arg_values.len() < param_types.len() {
-                let defaults_to_eval =
-                    if let Some(defaults) = self.function_defaults.get(&resolved_func_name) {
-                        let mut to_eval = Vec::new();
-                        for i in arg_values.len()..param_types.len() {
-                            if let Some(default_expr) = defaults.get(i).and_then(|d| d.as_ref()) {
-                                to_eval.push((i, default_expr.clone()));
-                            } else {
-                                bail!("Missing argument {} for function {}", i, resolved_func_name);
-                            }
+        // Fill in default values for missing arguments
+        if arg_values.len() < fixed_params {
+            let defaults_to_eval =
+                if let Some(defaults) = self.function_defaults.get(&resolved_func_name) {
+                    let mut to_eval = Vec::new();
+                    for i in arg_values.len()..fixed_params {
+                        if let Some(default_expr) = defaults.get(i).and_then(|d| d.as_ref()) {
+                            to_eval.push((i, default_expr.clone()));
+                        } else {
+                            bail!("Missing argument {} for function {}", i, resolved_func_name);
                         }
-                        to_eval
-                    } else {
-                        bail!("Missing arguments for function {}", resolved_func_name);
-                    };
-
-                for (i, default_expr) in defaults_to_eval {
-                    let val = self.eval_expr(&default_expr, ctx)?;
-                    if let Some(v) = val.value {
-                        let param_type = param_types[i];
-                        let converted = self.cast_argument_for_call(v, val.ty, &param_type)?;
-                        arg_values.push(converted.into());
-                    } else {
-                        bail!("Default value for argument {} evaluated to void", i);
                     }
+                    to_eval
+                } else {
+                    bail!("Missing arguments for function {}", resolved_func_name);
+                };
+
+            for (i, default_expr) in defaults_to_eval {
+                let val = self.eval_expr(&default_expr, ctx)?;
+                if let Some(v) = val.value {
+                    let param_type = param_types[i];
+                    let converted = self.cast_argument_for_call(v, val.ty, &param_type)?;
+                    arg_values.push(converted.into());
+                } else {
+                    bail!("Default value for argument {} evaluated to void", i);
                 }
             }
+        }
 
-            // Call the function
-            let call_site = self.builder.build_call(function, &arg_values, &func_name)?;
-
-            // Get return value
-            if let Some(ret_val) = call_site.try_as_basic_value().left() {
-                // Use declared return type if available, otherwise infer from LLVM type
-                let return_ty = self
-                    .function_return_types
-                    .get(&resolved_func_name)
-                    .cloned()
-                    .unwrap_or_else(|| {
-                        function
-                            .get_type()
-                            .get_return_type()
-                            .map(|ty| self.otter_type_from_basic_type(ty))
-                            .unwrap_or(OtterType::Opaque)
-                    });
-                Ok(EvaluatedValue::with_value(ret_val, return_ty))
-            } else {
-                // Function returns void
-                Ok(EvaluatedValue {
-                    ty: OtterType::Unit,
-                    value: None,
-                })
-            }
+        if let Some(handle) = variadic_list {
+            arg_values.push(handle.into());
+        }
+
+        // Call the function
+        let call_site = self.builder.build_call(function, &arg_values, &func_name)?;
+        // A self-recursive call returned as is reuses the caller's frame, so
+        // deep recursion does not grow the stack. The callee must not see
+        // the caller's stack slots, which the frame no longer holds.
+        let current_function = self
+            .builder
+            .get_insert_block()
+            .and_then(|bb| bb.get_parent());
+        if tail_call
+            && current_function == Some(function)
+            && sret.is_none()
+            && temporaries.is_empty()
+            && !arg_values.iter().any(|arg| Self::is_stack_slot(*arg))
+        {
+            call_site.set_tail_call_kind(LLVMTailCallKind::LLVMTailCallKindMustTail);
+        }
+        for (value, release) in temporaries {
+            self.release_string(value, release)?;
+        }
+
+        if let Some((slot, ret_type)) = sret {
+            let ret_val = self.builder.build_load(ret_type, slot, "sret_load")?;
+            let return_ty = self.otter_type_from_basic_type(ret_type);
+            return Ok(EvaluatedValue::with_value(ret_val, return_ty));
+        }
+
+        // Aggregates returned as register pieces are reassembled in memory
+        if let Some(ret_type) = self.coerced_return_types.get(&resolved_func_name).copied()
+            && let Some(pieces) = call_site.try_as_basic_value().left()
+        {
+            let current_function = current_function
+                .ok_or_else(|| anyhow!("Cannot determine current function for call"))?;
+            let slot = self.create_entry_block_alloca_of_type(
+                current_function,
+                "coerced_ret",
+                pieces.get_type(),
+            )?;
+            self.builder.build_store(slot, pieces)?;
+            let ret_val = self.builder.build_load(ret_type, slot, "coerced_load")?;
+            let return_ty = self.otter_type_from_basic_type(ret_type);
+            return Ok(EvaluatedValue::with_value(ret_val, return_ty));
+        }
+
+        // Get return value
+        if let Some(ret_val) = call_site.try_as_basic_value().left() {
+            // Use declared return type if available, otherwise infer from LLVM type
+            let return_ty = self
+                .function_signatures
+                .get(&resolved_func_name)
+                .map(|signature| signature.ret.clone())
+                .unwrap_or_else(|| {
+                    function
+                        .get_type()
+                        .get_return_type()
+                        .map(|ty| self.otter_type_from_basic_type(ty))
+                        .unwrap_or(OtterType::Opaque)
+                });
+            Ok(EvaluatedValue::with_value(ret_val, return_ty))
         } else {
-            bail!("Expected Call expression");
+            // Function returns void
+            Ok(EvaluatedValue {
+                ty: OtterType::Unit,
+                value: None,
+            })
         }
     }
 
@@ -2023,6 +2651,12 @@ impl<'ctx> Compiler<'ctx> {
         {
             // Evaluate condition
             let cond_val = self.eval_expr(cond.as_ref().as_ref(), ctx)?;
+            if cond_val.ty != OtterType::Bool {
+                bail!(CodegenError::type_mismatch(format!(
+                    "if-expression condition must be bool, found {}",
+                    cond_val.ty
+                )));
+            }
             let cond_bool = self.to_bool_value(cond_val)?;
 
             // Get current function
@@ -2076,13 +2710,20 @@ impl<'ctx> Compiler<'ctx> {
             let then_ty = then_val.ty.clone();
             let else_ty = else_val.ty.clone();
 
+            if then_ty != else_ty && then_val.value.is_some() && else_val.value.is_some() {
+                bail!(CodegenError::type_mismatch(format!(
+                    "if-expression branches have different types: {} and {}",
+                    then_ty, else_ty
+                )));
+            }
+
             if then_ty == else_ty
                 && let Some(then_val) = then_val.value
-                && else_val.value.is_some()
+                && let Some(else_val) = else_val.value
             {
                 if let Some(basic_ty) = self.basic_type(then_ty.clone())? {
                     let phi = self.builder.build_phi(basic_ty, "if_result")?;
-                    phi.add_incoming(&[(&then_val, then_bb_end), (&then_val, else_bb_end)]);
+                    phi.add_incoming(&[(&then_val, then_bb_end), (&else_val, else_bb_end)]);
                     Ok(EvaluatedValue::with_value(phi.as_basic_value(), then_ty))
                 } else {
                     // Unit type
@@ -2175,10 +2816,152 @@ impl<'ctx> Compiler<'ctx> {
             OtterType::List(_) => Ok(("append<list,list>", ty.clone())),
             OtterType::Map => Ok(("append<list,map>", OtterType::Map)),
             OtterType::Opaque => Ok(("append<list,list>", OtterType::opaque_list())),
-            _ => bail!("unsupported array element type: {:?}", ty),
+            _ => bail!("unsupported array element type: {}", ty),
         }
     }
 
+    /// Lowers `base[index]`: a bounds-checked load from a list, or a lookup
+    /// by string key in a map. The getter is picked by the element type,
+    /// taken from the type checker when the value itself doesn't carry it.
+    fn eval_index_expr(
+        &mut self,
+        expr: &Expr,
+        base: &Expr,
+        index: &Expr,
+        ctx: &mut FunctionContext<'ctx>,
+    ) -> Result<EvaluatedValue<'ctx>> {
+        let checked_ty = self
+            .expr_type(expr)
+            .cloned()
+            .and_then(|ty| self.typeinfo_to_otter_type(&ty));
+        let base_value = self.eval_expr(base, ctx)?;
+        let handle = base_value
+            .value
+            .ok_or_else(|| anyhow!("cannot index into a value of type {}", base_value.ty))?;
+        let index_value = self.eval_expr(index, ctx)?;
+
+        match base_value.ty {
+            OtterType::List(element) => {
+                if !matches!(index_value.ty, OtterType::I32 | OtterType::I64) {
+                    bail!(CodegenError::type_mismatch(format!(
+                        "list index must be an int, found {}",
+                        index_value.ty
+                    )));
+                }
+                let index_ty = index_value.ty.clone();
+                let raw_index = index_value
+                    .value
+                    .ok_or_else(|| anyhow!("list index has no value"))?;
+                let index_int = self
+                    .coerce_type(raw_index, index_ty, OtterType::I64)?
+                    .into_int_value();
+                let handle = handle.into_int_value();
+                self.build_list_bounds_check(handle, index_int)?;
+
+                let element_ty = match *element {
+                    OtterType::Opaque => checked_ty.unwrap_or(OtterType::Opaque),
+                    element => element,
+                };
+                let (getter, result_ty) = match &element_ty {
+                    OtterType::Str => ("list.get", OtterType::Str),
+                    OtterType::I32 | OtterType::I64 => ("list.get_int", OtterType::I64),
+                    OtterType::F64 => ("list.get_float", OtterType::F64),
+                    OtterType::Bool => ("list.get_bool", OtterType::Bool),
+                    OtterType::List(_) => ("list.get_list", element_ty.clone()),
+                    OtterType::Map => ("list.get_map", OtterType::Map),
+                    _ => ("runtime.list.get", OtterType::Opaque),
+                };
+                let get_fn = self.get_or_declare_ffi_function(getter)?;
+                let value = self
+                    .builder
+                    .build_call(get_fn, &[handle.into(), index_int.into()], "list_item")?
+                    .try_as_basic_value()
+                    .left()
+                    .ok_or_else(|| anyhow!("{} returned void", getter))?;
+                Ok(EvaluatedValue::with_value(value, result_ty))
+            }
+            OtterType::Map => {
+                if index_value.ty != OtterType::Str {
+                    bail!(CodegenError::type_mismatch(format!(
+                        "map key must be a str, found {}",
+                        index_value.ty
+                    )));
+                }
+                let key = self.ensure_string_value(index_value)?;
+                let (getter, result_ty) = match checked_ty.unwrap_or(OtterType::Str) {
+                    OtterType::I32 | OtterType::I64 => ("map.get_int", OtterType::I64),
+                    OtterType::F64 => ("map.get_float", OtterType::F64),
+                    OtterType::Bool => ("map.get_bool", OtterType::Bool),
+                    list @ OtterType::List(_) => ("map.get_list", list),
+                    OtterType::Map => ("map.get_map", OtterType::Map),
+                    _ => ("map.get", OtterType::Str),
+                };
+                let get_fn = self.get_or_declare_ffi_function(getter)?;
+                let value = self
+                    .builder
+                    .build_call(get_fn, &[handle.into(), key.into()], "map_item")?
+                    .try_as_basic_value()
+                    .left()
+                    .ok_or_else(|| anyhow!("{} returned void", getter))?;
+                Ok(EvaluatedValue::with_value(value, result_ty))
+            }
+            other => bail!(CodegenError::type_mismatch(format!(
+                "cannot index into a value of type {}",
+                other
+            ))),
+        }
+    }
+
+    /// Guard an index into `list_handle`: out-of-range indices (negative ones
+    /// included, via the unsigned compare) branch to the runtime's
+    /// out-of-bounds panic. A no-op when bounds checks are disabled.
+    pub(crate) fn build_list_bounds_check(
+        &mut self,
+        list_handle: IntValue<'ctx>,
+        index: IntValue<'ctx>,
+    ) -> Result<()> {
+        if !self.bounds_checks {
+            return Ok(());
+        }
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|bb| bb.get_parent())
+            .ok_or_else(|| anyhow!("bounds check emitted outside of a function"))?;
+
+        let len_fn = self.get_or_declare_ffi_function("runtime.list.length")?;
+        let len = self
+            .builder
+            .build_call(len_fn, &[list_handle.into()], "list_len")?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| anyhow!("list length returned void"))?
+            .into_int_value();
+        let index = self.builder.build_int_s_extend_or_bit_cast(
+            index,
+            self.context.i64_type(),
+            "index_i64",
+        )?;
+        let in_bounds =
+            self.builder
+                .build_int_compare(IntPredicate::ULT, index, len, "index_in_bounds")?;
+
+        let ok_bb = self.context.append_basic_block(function, "index_ok");
+        let oob_bb = self.context.append_basic_block(function, "index_oob");
+        self.builder
+            .build_conditional_branch(in_bounds, ok_bb, oob_bb)?;
+
+        self.builder.position_at_end(oob_bb);
+        let panic_fn = self.get_or_declare_ffi_function("runtime.panic_index_out_of_bounds")?;
+        self.builder
+            .build_call(panic_fn, &[index.into(), len.into()], "")?;
+        self.builder.build_unreachable()?;
+
+        self.builder.position_at_end(ok_bb);
+        Ok(())
+    }
+
     fn eval_list_comprehension(
         &mut self,
         full_expr: &Expr,
@@ -2216,7 +2999,7 @@ impl<'ctx> Compiler<'ctx> {
             let iterable_val = self.eval_expr(iterable, ctx)?;
             if !matches!(iterable_val.ty, OtterType::List(_)) {
                 bail!(
-                    "list comprehension expects list iterable, got {:?}",
+                    "list comprehension expects list iterable, got {}",
                     iterable_val.ty
                 );
             }
@@ -2386,7 +3169,7 @@ impl<'ctx> Compiler<'ctx> {
             let iterable_val = self.eval_expr(iterable, ctx)?;
             if !matches!(iterable_val.ty, OtterType::List(_)) {
                 bail!(
-                    "dict comprehension expects list iterable, got {:?}",
+                    "dict comprehension expects list iterable, got {}",
                     iterable_val.ty
                 );
             }
@@ -2552,7 +3335,7 @@ impl<'ctx> Compiler<'ctx> {
             OtterType::List(_) => Ok(("set<map,list>", ty.clone())),
             OtterType::Map => Ok(("set<map,map>", OtterType::Map)),
             OtterType::Opaque => Ok(("set<map,list>", OtterType::opaque_list())),
-            _ => bail!("unsupported dict comprehension value type: {:?}", ty),
+            _ => bail!("unsupported dict comprehension value type: {}", ty),
         }
     }
 
@@ -2582,16 +3365,21 @@ impl<'ctx> Compiler<'ctx> {
                 .expr_type(expr)
                 .and_then(|ty| self.typeinfo_to_otter_type(ty)),
             Expr::Identifier(_) | Expr::Literal(_) => None,
-            Expr::Binary { left, right, .. } => self
+            Expr::Binary { left, right, .. }
+            | Expr::Index {
+                base: left,
+                index: right,
+            } => self
                 .find_identifier_type_in_expr(left.as_ref().as_ref(), var)
                 .or_else(|| self.find_identifier_type_in_expr(right.as_ref().as_ref(), var)),
             Expr::Unary { expr, .. } | Expr::Await(expr) | Expr::Spawn(expr) => {
                 self.find_identifier_type_in_expr(expr.as_ref().as_ref(), var)
             }
-            Expr::Call { func, args } => self
+            Expr::Call { func, args, kwargs } => self
                 .find_identifier_type_in_expr(func.as_ref().as_ref(), var)
                 .or_else(|| {
                     args.iter()
+                        .chain(kwargs.iter().map(|(_, arg)| arg))
                         .find_map(|arg| self.find_identifier_type_in_expr(arg.as_ref(), var))
                 }),
             Expr::Member { object, .. } => {
@@ -2623,7 +3411,7 @@ impl<'ctx> Compiler<'ctx> {
                         self.find_identifier_type_in_block(arm_ref.body.as_ref(), var)
                     })
                 }),
-            Expr::Range { start, end } => self
+            Expr::Range { start, end, .. } => self
                 .find_identifier_type_in_expr(start.as_ref().as_ref(), var)
                 .or_else(|| self.find_identifier_type_in_expr(end.as_ref().as_ref(), var)),
             Expr::Array(elements) => elements
@@ -2695,12 +3483,13 @@ impl<'ctx> Compiler<'ctx> {
             Statement::Expr(expr)
             | Statement::Return(Some(expr))
             | Statement::Let { expr, .. }
-            | Statement::Assignment { expr, .. } => {
+            | Statement::Assignment { expr, .. }
+            | Statement::CompoundAssignment { expr, .. } => {
                 self.find_identifier_type_in_expr(expr.as_ref(), var)
             }
             Statement::Return(None)
-            | Statement::Break
-            | Statement::Continue
+            | Statement::Break(_)
+            | Statement::Continue(_)
             | Statement::Pass
             | Statement::Struct { .. }
             | Statement::Enum { .. }
@@ -2770,9 +3559,7 @@ impl<'ctx> Compiler<'ctx> {
                 "fmt_bool",
             ),
             OtterType::List(_) => {
-                // Try to convert list handle to string
-                // Opaque types might be list handles, so try stringify
-                self.call_ffi_returning_value("stringify<list>", vec![base_value], "stringify_list")
+                self.call_ffi_returning_value("fmt.list", vec![base_value], "fmt_list")
             }
             OtterType::Map => {
                 self.call_ffi_returning_value("stringify<map>", vec![base_value], "stringify_map")
@@ -2780,7 +3567,7 @@ impl<'ctx> Compiler<'ctx> {
             OtterType::Opaque => {
                 self.call_ffi_returning_value("stringify<list>", vec![base_value], "stringify_list")
             }
-            _ => bail!("cannot convert {:?} to string", ty),
+            _ => bail!("cannot convert {} to string", ty),
         }
     }
 
@@ -2911,23 +3698,29 @@ impl<'ctx> Compiler<'ctx> {
         })
     }
 
-    fn resolve_member_function_name(&self, object: &Expr, field: &str) -> Option<String> {
+    pub(crate) fn resolve_member_function_name(
+        &self,
+        object: &Expr,
+        field: &str,
+    ) -> Option<String> {
         if let Some(module) = self.module_path_from_expr(object) {
             let candidate = format!("{}.{}", module, field);
-            if self.symbol_registry.contains(&candidate)
-                || self.declared_functions.contains_key(&candidate)
-            {
+            if self.declared_functions.contains_key(&candidate) {
                 return Some(candidate);
             }
+            if let Some(function) = self
+                .symbol_registry
+                .resolve_with_aliases(&candidate, &self.module_aliases)
+            {
+                return Some(function.name);
+            }
         }
 
         let prefix = self.flatten_member_chain(object)?;
         let candidate = format!("{}.{}", prefix, field);
-        if self.symbol_registry.contains(&candidate) {
-            Some(candidate)
-        } else {
-            None
-        }
+        self.symbol_registry
+            .resolve_with_aliases(&candidate, &self.module_aliases)
+            .map(|function| function.name)
     }
 
     fn flatten_member_chain(&self, expr: &Expr) -> Option<String> {
@@ -3078,7 +3871,7 @@ impl<'ctx> Compiler<'ctx> {
                 "str_ptr_to_int",
             )?,
             _ => {
-                bail!("cannot convert {:?} to i64 for enum field", ty);
+                bail!("cannot convert {} to int for enum field", ty);
             }
         };
         Ok(int_value)
@@ -3100,7 +3893,7 @@ impl<'ctx> Compiler<'ctx> {
                 "i32_to_f64",
             )?,
             _ => {
-                bail!("cannot convert {:?} to f64 for enum field", ty);
+                bail!("cannot convert {} to float for enum field", ty);
             }
         };
         Ok(float_value.into())
@@ -3113,7 +3906,7 @@ impl<'ctx> Compiler<'ctx> {
                 .ok_or_else(|| anyhow!("missing bool value for enum field"))?
                 .into_int_value())
         } else {
-            bail!("expected bool value for enum field, got {:?}", ty);
+            bail!("expected bool value for enum field, got {}", ty);
         }
     }
 }
@@ -0,0 +1,312 @@
+//! How FFI aggregates cross the C calling convention.
+//!
+//! LLVM passes a first-class struct value field by field, which only matches
+//! C for some small structs. Callers of bridged functions instead follow the
+//! target's C ABI: an aggregate travels in registers as a sequence of
+//! integer/float pieces, or in memory behind a pointer.
+
+use otterc_config::TargetTriple;
+use otterc_symbol::registry::FfiType;
+
+/// The C calling convention that decides how aggregates are passed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AggregateAbi {
+    /// x86-64 System V (Linux, macOS, BSDs)
+    SysV64,
+    /// x86-64 Windows
+    Win64,
+    /// Aggregates are passed as LLVM struct values
+    Direct,
+}
+
+impl AggregateAbi {
+    /// The convention for `target`, or for the host when there is none
+    pub(crate) fn for_target(target: Option<&TargetTriple>) -> Self {
+        let (is_x86_64, is_windows) = match target {
+            Some(target) => (target.arch == "x86_64", target.is_windows()),
+            None => (cfg!(target_arch = "x86_64"), cfg!(windows)),
+        };
+        match (is_x86_64, is_windows) {
+            (true, true) => AggregateAbi::Win64,
+            (true, false) => AggregateAbi::SysV64,
+            (false, _) => AggregateAbi::Direct,
+        }
+    }
+}
+
+/// How one parameter or result is passed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Passing {
+    /// As its own LLVM type
+    Direct,
+    /// Reinterpreted as these register-sized pieces
+    Coerced(Vec<Piece>),
+    /// In memory: by pointer for parameters, through `sret` for results
+    Indirect,
+}
+
+/// A register-sized piece of a coerced aggregate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Piece {
+    /// An integer of this many bytes
+    Int(usize),
+    /// A `double`
+    Float,
+}
+
+/// Size and alignment in bytes of `ty` as a C type
+pub(crate) fn c_layout(ty: &FfiType) -> (usize, usize) {
+    match ty {
+        FfiType::Unit | FfiType::Bool => (1, 1),
+        FfiType::I32 => (4, 4),
+        FfiType::I64
+        | FfiType::F64
+        | FfiType::Str
+        | FfiType::Opaque
+        | FfiType::List
+        | FfiType::Map => (8, 8),
+        FfiType::Struct { fields } | FfiType::Tuple(fields) => {
+            let mut size: usize = 0;
+            let mut align = 1;
+            for field in fields.iter() {
+                let (field_size, field_align) = c_layout(field);
+                size = size.next_multiple_of(field_align) + field_size;
+                align = align.max(field_align);
+            }
+            (size.next_multiple_of(align), align)
+        }
+    }
+}
+
+/// How `ty` is passed under `abi`
+pub(crate) fn classify(abi: AggregateAbi, ty: &FfiType) -> Passing {
+    if !matches!(ty, FfiType::Struct { .. } | FfiType::Tuple(_)) {
+        return Passing::Direct;
+    }
+    let (size, _) = c_layout(ty);
+    match abi {
+        AggregateAbi::Direct => Passing::Direct,
+        AggregateAbi::Win64 => match size {
+            1 | 2 | 4 | 8 => Passing::Coerced(vec![Piece::Int(size)]),
+            _ => Passing::Indirect,
+        },
+        AggregateAbi::SysV64 if size > 16 => Passing::Indirect,
+        AggregateAbi::SysV64 => {
+            let mut scalars = Vec::new();
+            flatten(ty, 0, &mut scalars);
+            // Each eightbyte goes in an SSE register when it holds only
+            // doubles, otherwise in a general-purpose one
+            let pieces = (0..size.div_ceil(8))
+                .map(|index| {
+                    let all_floats = scalars
+                        .iter()
+                        .filter(|(offset, _)| offset / 8 == index)
+                        .all(|(_, scalar)| matches!(scalar, FfiType::F64));
+                    if all_floats {
+                        Piece::Float
+                    } else {
+                        Piece::Int((size - index * 8).min(8))
+                    }
+                })
+                .collect();
+            Passing::Coerced(pieces)
+        }
+    }
+}
+
+/// The scalar fields of `ty` with their byte offsets, starting at `base`
+fn flatten<'a>(ty: &'a FfiType, base: usize, scalars: &mut Vec<(usize, &'a FfiType)>) {
+    match ty {
+        FfiType::Struct { fields } | FfiType::Tuple(fields) => {
+            let mut offset: usize = 0;
+            for field in fields.iter() {
+                let (field_size, field_align) = c_layout(field);
+                offset = offset.next_multiple_of(field_align);
+                flatten(field, base + offset, scalars);
+                offset += field_size;
+            }
+        }
+        scalar => scalars.push((base, scalar)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use otterc_symbol::registry::{FfiFunction, FfiSignature};
+
+    use super::super::test_support::link_with_c;
+    use super::*;
+
+    fn record(fields: Vec<FfiType>) -> FfiType {
+        FfiType::Struct {
+            fields: fields.into(),
+        }
+    }
+
+    /// Registers `module.name`, implemented by the C function `module_name`
+    fn register(module: &str, name: &str, param: FfiType, result: FfiType) {
+        let registry = otterc_ffi::bootstrap_stdlib();
+        registry.register(FfiFunction {
+            name: format!("{module}.{name}"),
+            symbol: format!("{module}_{name}"),
+            signature: FfiSignature::new(vec![param], result),
+        });
+        registry.mark_module_active(module);
+    }
+
+    #[test]
+    fn layout_pads_fields_to_their_alignment() {
+        assert_eq!(
+            c_layout(&record(vec![FfiType::Bool, FfiType::I64])),
+            (16, 8)
+        );
+        assert_eq!(
+            c_layout(&record(vec![FfiType::I64, FfiType::Bool])),
+            (16, 8)
+        );
+        assert_eq!(
+            c_layout(&record(vec![FfiType::Bool, FfiType::Bool, FfiType::I32])),
+            (8, 4)
+        );
+        let nested = record(vec![
+            FfiType::I64,
+            FfiType::Tuple(vec![FfiType::Bool, FfiType::F64].into()),
+        ]);
+        assert_eq!(c_layout(&nested), (24, 8));
+    }
+
+    #[test]
+    fn sysv_splits_small_aggregates_into_eightbytes() {
+        let abi = AggregateAbi::SysV64;
+        assert_eq!(classify(abi, &FfiType::I64), Passing::Direct);
+        assert_eq!(
+            classify(abi, &record(vec![FfiType::I64, FfiType::F64])),
+            Passing::Coerced(vec![Piece::Int(8), Piece::Float])
+        );
+        assert_eq!(
+            classify(abi, &record(vec![FfiType::I32, FfiType::I32])),
+            Passing::Coerced(vec![Piece::Int(8)])
+        );
+        assert_eq!(
+            classify(abi, &record(vec![FfiType::I64, FfiType::I32])),
+            Passing::Coerced(vec![Piece::Int(8), Piece::Int(8)])
+        );
+        assert_eq!(
+            classify(
+                abi,
+                &FfiType::Tuple(vec![FfiType::I32, FfiType::I32, FfiType::I32].into())
+            ),
+            Passing::Coerced(vec![Piece::Int(8), Piece::Int(4)])
+        );
+        let nested = record(vec![
+            FfiType::I64,
+            FfiType::Tuple(vec![FfiType::Bool, FfiType::F64].into()),
+        ]);
+        assert_eq!(classify(abi, &nested), Passing::Indirect);
+    }
+
+    #[test]
+    fn win64_passes_register_sized_aggregates_as_integers() {
+        let abi = AggregateAbi::Win64;
+        assert_eq!(
+            classify(abi, &record(vec![FfiType::I32, FfiType::I32])),
+            Passing::Coerced(vec![Piece::Int(8)])
+        );
+        assert_eq!(
+            classify(
+                abi,
+                &record(vec![FfiType::Bool, FfiType::Bool, FfiType::Bool])
+            ),
+            Passing::Indirect
+        );
+        assert_eq!(
+            classify(abi, &record(vec![FfiType::I64, FfiType::F64])),
+            Passing::Indirect
+        );
+    }
+
+    const C_PRELUDE: &str = "#include <stdbool.h>\n#include <stdint.h>\n";
+
+    #[test]
+    #[ignore] // Needs LLVM and a C compiler: cargo test -- --ignored
+    fn struct_fields_round_trip_in_order() {
+        let flag = record(vec![FfiType::Bool, FfiType::I64]);
+        register("abiorder", "flip", flag.clone(), flag);
+        register(
+            "abiorder",
+            "swap",
+            record(vec![FfiType::I64, FfiType::F64]),
+            record(vec![FfiType::F64, FfiType::I64]),
+        );
+        let native = format!(
+            "{C_PRELUDE}
+typedef struct {{ bool on; int64_t count; }} Flag;
+typedef struct {{ int64_t count; double weight; }} Mixed;
+typedef struct {{ double weight; int64_t count; }} Swapped;
+Flag abiorder_flip(Flag f) {{ Flag r = {{ !f.on, f.count + 1 }}; return r; }}
+Swapped abiorder_swap(Mixed m) {{ Swapped r = {{ m.weight * 2, m.count * 3 }}; return r; }}
+"
+        );
+        let source = "use abiorder\n\nstruct Flag:\n    on: bool\n    count: int\n\nstruct Mixed:\n    count: int\n    weight: float\n\nstruct Swapped:\n    weight: float\n    count: int\n\nfn flipped() -> int:\n    let f: Flag = abiorder.flip(Flag(on=true, count=41))\n    if f.on:\n        return 0\n    return f.count\n\nfn swapped_weight() -> float:\n    let s: Swapped = abiorder.swap(Mixed(count=5, weight=1.25))\n    return s.weight\n\nfn swapped_count() -> int:\n    let s: Swapped = abiorder.swap(Mixed(count=5, weight=1.25))\n    return s.count\n";
+
+        let (_dir, library) = link_with_c(source, &native).unwrap();
+        unsafe {
+            let flipped = library.get::<extern "C" fn() -> i64>(b"flipped").unwrap();
+            let swapped_weight = library
+                .get::<extern "C" fn() -> f64>(b"swapped_weight")
+                .unwrap();
+            let swapped_count = library
+                .get::<extern "C" fn() -> i64>(b"swapped_count")
+                .unwrap();
+            assert_eq!(flipped(), 42);
+            assert_eq!(swapped_weight(), 2.5);
+            assert_eq!(swapped_count(), 15);
+        }
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and a C compiler: cargo test -- --ignored
+    fn nested_tuples_round_trip() {
+        // Two bools share the first eightbyte of a register-passed aggregate
+        let tagged = FfiType::Tuple(
+            vec![
+                FfiType::Tuple(vec![FfiType::Bool, FfiType::Bool].into()),
+                FfiType::F64,
+            ]
+            .into(),
+        );
+        register("abinested", "mirror", tagged.clone(), tagged);
+        // 24 bytes, so passed and returned in memory
+        let entry = FfiType::Tuple(
+            vec![
+                FfiType::I64,
+                FfiType::Tuple(vec![FfiType::Bool, FfiType::F64].into()),
+            ]
+            .into(),
+        );
+        register("abinested", "bump", entry.clone(), entry);
+        let native = format!(
+            "{C_PRELUDE}
+typedef struct {{ bool left; bool right; }} Pair;
+typedef struct {{ Pair pair; double weight; }} Tagged;
+typedef struct {{ bool on; double weight; }} Inner;
+typedef struct {{ int64_t id; Inner inner; }} Entry;
+Tagged abinested_mirror(Tagged t) {{ Tagged r = {{ {{ t.pair.right, t.pair.left }}, t.weight + 1 }}; return r; }}
+Entry abinested_bump(Entry e) {{ Entry r = {{ e.id * 2, {{ !e.inner.on, e.inner.weight * 4 }} }}; return r; }}
+"
+        );
+        let source = "use abinested\n\nstruct Pair:\n    left: bool\n    right: bool\n\nstruct Tagged:\n    pair: Pair\n    weight: float\n\nstruct Inner:\n    on: bool\n    weight: float\n\nstruct Entry:\n    id: int\n    inner: Inner\n\nfn mirrored() -> float:\n    let t: Tagged = abinested.mirror(Tagged(pair=Pair(left=true, right=false), weight=0.5))\n    if t.pair.left or not t.pair.right:\n        return 0.0\n    return t.weight\n\nfn bumped_id() -> int:\n    let e: Entry = abinested.bump(Entry(id=7, inner=Inner(on=false, weight=2.5)))\n    if not e.inner.on:\n        return 0\n    return e.id\n\nfn bumped_weight() -> float:\n    let e: Entry = abinested.bump(Entry(id=7, inner=Inner(on=false, weight=2.5)))\n    return e.inner.weight\n";
+
+        let (_dir, library) = link_with_c(source, &native).unwrap();
+        unsafe {
+            let mirrored = library.get::<extern "C" fn() -> f64>(b"mirrored").unwrap();
+            let bumped_id = library.get::<extern "C" fn() -> i64>(b"bumped_id").unwrap();
+            let bumped_weight = library
+                .get::<extern "C" fn() -> f64>(b"bumped_weight")
+                .unwrap();
+            assert_eq!(mirrored(), 1.5);
+            assert_eq!(bumped_id(), 14);
+            assert_eq!(bumped_weight(), 10.0);
+        }
+    }
+}
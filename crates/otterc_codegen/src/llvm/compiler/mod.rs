@@ -1,29 +1,37 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::AtomicUsize;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
 use inkwell::builder::Builder;
 use inkwell::context::Context as InkwellContext;
 use inkwell::module::Module;
 use inkwell::passes::{PassBuilderOptions, PassManager};
 use inkwell::targets::TargetMachine;
-use inkwell::types::{BasicType, BasicTypeEnum, PointerType, StructType};
-use inkwell::values::{FunctionValue, PointerValue};
+use inkwell::types::{AnyType, BasicType, BasicTypeEnum, PointerType, StructType};
+use inkwell::values::{FunctionValue, GlobalValue, PointerValue};
 
 use crate::llvm::bridges::prepare_rust_bridges;
+use crate::llvm::error::CodegenError;
 use otterc_ast::nodes::{Block, Expr, FStringPart, Function, Node, Program, Statement};
 use otterc_config::CodegenOptLevel;
 use otterc_config::TargetTriple;
 use otterc_span::Span;
-use otterc_symbol::registry::SymbolRegistry;
+use otterc_symbol::registry::{AliasMap, SymbolRegistry};
 use otterc_typecheck::{EnumLayout, TypeInfo};
 
+pub mod constants;
 pub mod expr;
+pub(crate) mod ffi_abi;
+pub mod ownership;
+pub mod params;
 pub mod stmt;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod types;
 
-use self::types::{FunctionContext, OtterType};
+use self::ffi_abi::{AggregateAbi, Passing, Piece};
+use self::types::{FunctionContext, FunctionSignature, OtterType};
 
 struct StructInfo<'ctx> {
     name: String,
@@ -39,15 +47,31 @@ pub struct Compiler<'ctx> {
     #[expect(dead_code, reason = "Work in progress")]
     pub(crate) fpm: PassManager<FunctionValue<'ctx>>,
     pub(crate) symbol_registry: &'static SymbolRegistry,
+    /// Module aliases from the program's `use` imports
+    pub(crate) module_aliases: AliasMap,
     pub(crate) string_ptr_type: PointerType<'ctx>,
     pub(crate) declared_functions: HashMap<String, FunctionValue<'ctx>>,
-    pub(crate) function_return_types: HashMap<String, OtterType>,
+    /// FFI functions that return an aggregate through a hidden `sret` first
+    /// parameter (aggregates the C ABI returns in memory), keyed like
+    /// `declared_functions`.
+    pub(crate) sret_return_types: HashMap<String, BasicTypeEnum<'ctx>>,
+    /// FFI functions that return an aggregate reinterpreted as register
+    /// pieces, with the aggregate's own type, keyed like `declared_functions`.
+    pub(crate) coerced_return_types: HashMap<String, BasicTypeEnum<'ctx>>,
+    /// One global per distinct string literal, shared by every use site.
+    pub(crate) string_literals: HashMap<String, GlobalValue<'ctx>>,
+    /// Resolved signatures of user functions and methods
+    pub(crate) function_signatures: HashMap<String, FunctionSignature>,
     pub(crate) expr_types: HashMap<usize, TypeInfo>,
     expr_types_by_span: HashMap<Span, TypeInfo>,
     pub(crate) comprehension_var_types: HashMap<Span, TypeInfo>,
     expr_spans: HashMap<usize, Span>,
     pub(crate) enum_layouts: HashMap<String, EnumLayout>,
     pub(crate) function_defaults: HashMap<String, Vec<Option<Expr>>>,
+    /// Parameter names of user functions, used to bind keyword arguments
+    pub(crate) function_param_names: HashMap<String, Vec<String>>,
+    /// User functions whose last parameter is `*args`
+    pub(crate) variadic_functions: HashSet<String>,
     #[expect(dead_code, reason = "Work in progress")]
     pub(crate) lambda_counter: AtomicUsize,
     next_spawn_id: u64,
@@ -56,6 +80,10 @@ pub struct Compiler<'ctx> {
     pub cached_ir: Option<String>,
     /// Target triple for platform-specific ABI handling
     target_triple: Option<TargetTriple>,
+    /// Emit index bounds checks (see `CodegenOptions::bounds_checks`)
+    pub(crate) bounds_checks: bool,
+    /// Infer untyped parameters (see `CodegenOptions::infer_param_types`)
+    pub(crate) infer_param_types: bool,
 }
 
 impl<'ctx> Compiler<'ctx> {
@@ -74,10 +102,11 @@ impl<'ctx> Compiler<'ctx> {
             Statement::Expr(expr)
             | Statement::Let { expr, .. }
             | Statement::Assignment { expr, .. }
+            | Statement::CompoundAssignment { expr, .. }
             | Statement::Return(Some(expr)) => self.record_expr_spans(expr),
             Statement::Return(None)
-            | Statement::Break
-            | Statement::Continue
+            | Statement::Break(_)
+            | Statement::Continue(_)
             | Statement::Pass
             | Statement::Use { .. }
             | Statement::PubUse { .. }
@@ -123,16 +152,20 @@ impl<'ctx> Compiler<'ctx> {
 
         match expr.as_ref() {
             Expr::Literal(_) | Expr::Identifier(_) => {}
-            Expr::Binary { left, right, .. } => {
+            Expr::Binary { left, right, .. }
+            | Expr::Index {
+                base: left,
+                index: right,
+            } => {
                 self.record_expr_spans(left);
                 self.record_expr_spans(right);
             }
             Expr::Unary { expr, .. } | Expr::Await(expr) | Expr::Spawn(expr) => {
                 self.record_expr_spans(expr);
             }
-            Expr::Call { func, args } => {
+            Expr::Call { func, args, kwargs } => {
                 self.record_expr_spans(func);
-                for arg in args {
+                for arg in args.iter().chain(kwargs.iter().map(|(_, arg)| arg)) {
                     self.record_expr_spans(arg);
                 }
             }
@@ -157,7 +190,7 @@ impl<'ctx> Compiler<'ctx> {
                     self.record_block_spans(arm.as_ref().body.as_ref());
                 }
             }
-            Expr::Range { start, end } => {
+            Expr::Range { start, end, .. } => {
                 self.record_expr_spans(start);
                 self.record_expr_spans(end);
             }
@@ -245,32 +278,32 @@ impl<'ctx> Compiler<'ctx> {
             module,
             fpm,
             symbol_registry,
+            module_aliases: AliasMap::new(),
             string_ptr_type,
             declared_functions: HashMap::new(),
-            function_return_types: HashMap::new(),
+            sret_return_types: HashMap::new(),
+            coerced_return_types: HashMap::new(),
+            string_literals: HashMap::new(),
+            function_signatures: HashMap::new(),
             expr_types,
             expr_types_by_span,
             comprehension_var_types,
             expr_spans: HashMap::new(),
             enum_layouts,
             function_defaults: HashMap::new(),
+            function_param_names: HashMap::new(),
+            variadic_functions: HashSet::new(),
             lambda_counter: AtomicUsize::new(0),
             next_spawn_id: 0,
             struct_ids: HashMap::new(),
             struct_infos: Vec::new(),
             cached_ir: None,
             target_triple,
+            bounds_checks: true,
+            infer_param_types: false,
         }
     }
 
-    /// Check if we're targeting Windows x64, which has different struct passing ABI
-    fn is_windows_x64(&self) -> bool {
-        self.target_triple
-            .as_ref()
-            .map(|t| t.is_windows() && t.arch == "x86_64")
-            .unwrap_or(false)
-    }
-
     pub fn lower_program(&mut self, program: &Program, _require_main: bool) -> Result<()> {
         self.compile_module(program)
     }
@@ -381,7 +414,8 @@ impl<'ctx> Compiler<'ctx> {
         for statement in &program.statements {
             match statement.as_ref() {
                 Statement::Function(func) => {
-                    self.register_function_prototype(func.as_ref())?;
+                    self.register_function_prototype(func.as_ref())
+                        .map_err(|err| CodegenError::in_function(err, &func.as_ref().name))?;
                 }
                 Statement::Struct {
                     name,
@@ -411,7 +445,18 @@ impl<'ctx> Compiler<'ctx> {
                         let mut method_func = method.as_ref().clone();
                         method_func.name = format!("{}_{}", name, method_func.name);
                         self.rewrite_method_self_param(&mut method_func, name);
-                        self.register_function_prototype(&method_func)?;
+                        self.register_function_prototype(&method_func)
+                            .map_err(|err| CodegenError::in_function(err, &method_func.name))?;
+                    }
+                }
+                Statement::Use { imports } => {
+                    for import in imports {
+                        let import = import.as_ref();
+                        self.module_aliases.insert_import(
+                            &import.module,
+                            import.alias.as_deref(),
+                            self.symbol_registry,
+                        )?;
                     }
                 }
                 _ => {}
@@ -423,7 +468,8 @@ impl<'ctx> Compiler<'ctx> {
             match statement.as_ref() {
                 Statement::Function(func) => {
                     self.record_function_spans(func.as_ref());
-                    self.compile_function(func.as_ref())?;
+                    self.compile_function(func.as_ref())
+                        .map_err(|err| CodegenError::in_function(err, &func.as_ref().name))?;
                 }
                 Statement::Struct { name, methods, .. } => {
                     for method in methods {
@@ -431,7 +477,8 @@ impl<'ctx> Compiler<'ctx> {
                         method_func.name = format!("{}_{}", name, method_func.name);
                         self.rewrite_method_self_param(&mut method_func, name);
                         self.record_function_spans(&method_func);
-                        self.compile_function(&method_func)?;
+                        self.compile_function(&method_func)
+                            .map_err(|err| CodegenError::in_function(err, &method_func.name))?;
                     }
                 }
                 _ => {}
@@ -455,36 +502,6 @@ impl<'ctx> Compiler<'ctx> {
     ) -> Result<FunctionValue<'ctx>> {
         use otterc_symbol::registry::FfiType;
 
-        // Helper to calculate struct size in bytes
-        fn ffi_type_size(ffi_ty: &FfiType) -> usize {
-            match ffi_ty {
-                FfiType::Unit | FfiType::Bool => 1,
-                FfiType::I32 => 4,
-                FfiType::I64
-                | FfiType::F64
-                | FfiType::Str
-                | FfiType::Opaque
-                | FfiType::List
-                | FfiType::Map => 8,
-                FfiType::Struct { fields } | FfiType::Tuple(fields) => {
-                    fields.iter().map(ffi_type_size).sum()
-                }
-            }
-        }
-
-        // Check if a struct type needs pointer passing on Windows x64
-        fn needs_ptr_passing(ffi_ty: &FfiType, is_windows_x64: bool) -> bool {
-            if !is_windows_x64 {
-                return false;
-            }
-            match ffi_ty {
-                FfiType::Struct { .. } | FfiType::Tuple(_) => ffi_type_size(ffi_ty) > 8,
-                _ => false,
-            }
-        }
-
-        let is_win64 = self.is_windows_x64();
-
         // Map FFI types to LLVM types
         fn map_ffi_type<'ctx>(
             context: &'ctx InkwellContext,
@@ -512,8 +529,26 @@ impl<'ctx> Compiler<'ctx> {
 
         let map_type = |ffi_ty: &FfiType| map_ffi_type(self.context, self.string_ptr_type, ffi_ty);
 
-        // Check if return type needs sret on Windows x64
-        let ret_needs_sret = needs_ptr_passing(&ffi_func.signature.result, is_win64);
+        // The register pieces of a coerced aggregate, as one LLVM type
+        let coerced_type = |pieces: &[Piece]| -> BasicTypeEnum<'ctx> {
+            let piece_types: Vec<BasicTypeEnum> = pieces
+                .iter()
+                .map(|piece| match piece {
+                    Piece::Int(bytes) => {
+                        self.context.custom_width_int_type(*bytes as u32 * 8).into()
+                    }
+                    Piece::Float => self.context.f64_type().into(),
+                })
+                .collect();
+            match piece_types.as_slice() {
+                [single] => *single,
+                _ => self.context.struct_type(&piece_types, false).into(),
+            }
+        };
+
+        let abi = AggregateAbi::for_target(self.target_triple.as_ref());
+        let result_passing = ffi_abi::classify(abi, &ffi_func.signature.result);
+        let ret_needs_sret = result_passing == Passing::Indirect;
 
         // Build parameter types
         let mut param_types: Vec<inkwell::types::BasicMetadataTypeEnum> = Vec::new();
@@ -523,13 +558,19 @@ impl<'ctx> Compiler<'ctx> {
             param_types.push(self.string_ptr_type.into());
         }
 
-        // Map regular parameter types
+        // Map regular parameter types; System V copies aggregates passed in
+        // memory (`byval`), Windows x64 passes a pointer to a caller copy
+        let mut byval_params = Vec::new();
         for param_ty in &ffi_func.signature.params {
-            if needs_ptr_passing(param_ty, is_win64) {
-                // Pass large struct by pointer
-                param_types.push(self.string_ptr_type.into());
-            } else {
-                param_types.push(map_type(param_ty).into());
+            match ffi_abi::classify(abi, param_ty) {
+                Passing::Direct => param_types.push(map_type(param_ty).into()),
+                Passing::Coerced(pieces) => param_types.push(coerced_type(&pieces).into()),
+                Passing::Indirect => {
+                    if abi == AggregateAbi::SysV64 {
+                        byval_params.push((param_types.len(), map_type(param_ty)));
+                    }
+                    param_types.push(self.string_ptr_type.into());
+                }
             }
         }
 
@@ -538,34 +579,46 @@ impl<'ctx> Compiler<'ctx> {
             // With sret, return void (result goes through hidden first param)
             self.context.void_type().fn_type(&param_types, false)
         } else {
-            match &ffi_func.signature.result {
-                FfiType::Unit => self.context.void_type().fn_type(&param_types, false),
-                result_ty => {
+            match (&ffi_func.signature.result, &result_passing) {
+                (FfiType::Unit, _) => self.context.void_type().fn_type(&param_types, false),
+                (_, Passing::Coerced(pieces)) => coerced_type(pieces).fn_type(&param_types, false),
+                (result_ty, _) => {
                     let ret_type = map_type(result_ty);
                     ret_type.fn_type(&param_types, false)
                 }
             }
         };
 
-        // Declare the function using the symbol name (not the user-facing name)
-        let function = self.module.add_function(&ffi_func.symbol, fn_type, None);
+        // Declare the function using the symbol name (not the user-facing name).
+        // Several registry names may share a symbol; reuse its declaration.
+        let function = self
+            .module
+            .get_function(&ffi_func.symbol)
+            .unwrap_or_else(|| self.module.add_function(&ffi_func.symbol, fn_type, None));
 
         // Add sret attribute if needed
         if ret_needs_sret {
-            let ret_type: inkwell::types::AnyTypeEnum = match map_type(&ffi_func.signature.result) {
-                BasicTypeEnum::StructType(s) => s.into(),
-                BasicTypeEnum::IntType(i) => i.into(),
-                BasicTypeEnum::FloatType(f) => f.into(),
-                BasicTypeEnum::PointerType(p) => p.into(),
-                BasicTypeEnum::ArrayType(a) => a.into(),
-                BasicTypeEnum::VectorType(v) => v.into(),
-                BasicTypeEnum::ScalableVectorType(v) => v.into(),
-            };
+            let sret_type = map_type(&ffi_func.signature.result);
+            self.sret_return_types.insert(name.to_string(), sret_type);
             function.add_attribute(
                 inkwell::attributes::AttributeLoc::Param(0),
                 self.context.create_type_attribute(
                     inkwell::attributes::Attribute::get_named_enum_kind_id("sret"),
-                    ret_type,
+                    sret_type.as_any_type_enum(),
+                ),
+            );
+        }
+        if let Passing::Coerced(_) = result_passing {
+            let result_type = map_type(&ffi_func.signature.result);
+            self.coerced_return_types
+                .insert(name.to_string(), result_type);
+        }
+        for (index, param_type) in byval_params {
+            function.add_attribute(
+                inkwell::attributes::AttributeLoc::Param(index as u32),
+                self.context.create_type_attribute(
+                    inkwell::attributes::Attribute::get_named_enum_kind_id("byval"),
+                    param_type.as_any_type_enum(),
                 ),
             );
         }
@@ -600,6 +653,7 @@ impl<'ctx> Compiler<'ctx> {
                 "bool" => Ok(self.context.bool_type().into()),
                 "string" | "str" => Ok(self.string_ptr_type.into()),
                 "void" | "unit" => Ok(self.context.i8_type().into()), // Unit as i8 (or void for return)
+                "range" | "Range" => Ok(self.range_struct_type().into()),
                 other => {
                     if let Some(id) = self.struct_id(other) {
                         Ok(self.struct_info(id).ty.into())
@@ -622,6 +676,7 @@ impl<'ctx> Compiler<'ctx> {
                 "unit" | "void" => OtterType::Unit,
                 "list" | "List" => OtterType::opaque_list(),
                 "map" | "Map" => OtterType::Map,
+                "range" | "Range" => OtterType::Range,
                 other => self
                     .struct_id(other)
                     .map(OtterType::Struct)
@@ -701,12 +756,31 @@ impl<'ctx> Compiler<'ctx> {
         };
 
         let mut param_types = Vec::new();
+        let mut param_otter_types = Vec::new();
         for param in &func.params {
-            if let Some(ty) = &param.as_ref().ty {
+            if param.as_ref().variadic {
+                // `*args` arrives as a list handle packed by the caller
+                let element = param
+                    .as_ref()
+                    .ty
+                    .as_ref()
+                    .map(|ty| self.otter_type_from_annotation(ty.as_ref()))
+                    .unwrap_or(OtterType::Opaque);
+                param_types.push(self.context.i64_type().into());
+                param_otter_types.push(OtterType::list_of(element));
+            } else if let Some(ty) = &param.as_ref().ty {
                 param_types.push(self.map_ast_type(ty.as_ref())?.into());
+                param_otter_types.push(self.otter_type_from_annotation(ty.as_ref()));
             } else {
-                // Default to i64 if no type specified
-                param_types.push(self.context.i64_type().into());
+                let otter_ty = self.untyped_param_type(func, param.as_ref())?;
+                let llvm_ty = self.basic_type(otter_ty.clone())?.ok_or_else(|| {
+                    anyhow!(
+                        "parameter '{}' has no value type",
+                        param.as_ref().name.as_ref()
+                    )
+                })?;
+                param_types.push(llvm_ty.into());
+                param_otter_types.push(otter_ty);
             }
         }
 
@@ -724,14 +798,19 @@ impl<'ctx> Compiler<'ctx> {
         let function = self.module.add_function(llvm_name, fn_type, None);
         self.declared_functions.insert(func.name.clone(), function);
 
-        // Store return type for later use in eval_call_expr
+        // Store the signature for checking and typing calls in eval_call
         let ret_otter_type = if let Some(ret_ty) = &func.ret_ty {
             self.otter_type_from_annotation(ret_ty.as_ref())
         } else {
             OtterType::Unit
         };
-        self.function_return_types
-            .insert(func.name.clone(), ret_otter_type);
+        self.function_signatures.insert(
+            func.name.clone(),
+            FunctionSignature {
+                params: param_otter_types,
+                ret: ret_otter_type,
+            },
+        );
 
         // Store default values
         let defaults: Vec<Option<Expr>> = func
@@ -740,6 +819,16 @@ impl<'ctx> Compiler<'ctx> {
             .map(|p| p.as_ref().default.as_ref().map(|e| e.as_ref().clone()))
             .collect();
         self.function_defaults.insert(func.name.clone(), defaults);
+        self.function_param_names.insert(
+            func.name.clone(),
+            func.params
+                .iter()
+                .map(|p| p.as_ref().name.as_ref().clone())
+                .collect(),
+        );
+        if func.params.last().is_some_and(|p| p.as_ref().variadic) {
+            self.variadic_functions.insert(func.name.clone());
+        }
 
         Ok(())
     }
@@ -754,21 +843,19 @@ impl<'ctx> Compiler<'ctx> {
         self.builder.position_at_end(entry);
 
         let mut ctx = FunctionContext::new();
+        ctx.function_name = Some(func.name.clone());
+        ctx.constant_names = constants::constant_bindings(&func.body);
 
-        // Bind arguments
-        for (i, param) in func.params.iter().enumerate() {
+        // Bind arguments with the types resolved for the prototype
+        let param_otter_types = self
+            .function_signatures
+            .get(&func.name)
+            .map(|signature| signature.params.clone())
+            .ok_or_else(|| anyhow!("Function {} has no signature", func.name))?;
+        for ((i, param), otter_type) in func.params.iter().enumerate().zip(param_otter_types) {
             let arg_val = function.get_nth_param(i as u32).unwrap();
             let param_name = &param.as_ref().name;
 
-            // Determine type from AST or default to I64
-            let (_llvm_type, otter_type) = if let Some(ty) = &param.as_ref().ty {
-                let llvm_ty = self.map_ast_type(ty.as_ref())?;
-                let otter_ty = self.otter_type_from_annotation(ty.as_ref());
-                (llvm_ty, otter_ty)
-            } else {
-                (self.context.i64_type().into(), OtterType::I64)
-            };
-
             // Allocate stack space for parameter
             let alloca = self.create_entry_block_alloca(
                 *function,
@@ -790,13 +877,8 @@ impl<'ctx> Compiler<'ctx> {
         // Compile body
         self.lower_block(func.body.as_ref(), *function, &mut ctx)?;
 
-        // Add implicit return if needed
-        if self
-            .builder
-            .get_insert_block()
-            .and_then(|b| b.get_terminator())
-            .is_none()
-        {
+        // Add implicit return if control can reach the end of the body
+        if !Self::block_diverges(func.body.as_ref()) && !self.current_block_terminated() {
             match func.ret_ty {
                 None => {
                     self.builder.build_return(None)?;
@@ -827,12 +909,48 @@ impl<'ctx> Compiler<'ctx> {
         Ok(())
     }
 
+    /// Type of a parameter declared without an annotation, which is only
+    /// accepted when parameter inference is enabled.
+    fn untyped_param_type(
+        &self,
+        func: &otterc_ast::nodes::Function,
+        param: &otterc_ast::nodes::Param,
+    ) -> Result<OtterType> {
+        let name = param.name.as_ref();
+        if !self.infer_param_types {
+            bail!(CodegenError::Other {
+                message: format!(
+                    "parameter '{}' of function '{}' needs a type annotation (or pass --infer to infer it from its uses)",
+                    name, func.name
+                ),
+                span: Some(*param.name.span()),
+                function: Some(func.name.clone()),
+            });
+        }
+        params::infer_param_type(name, &func.body)
+    }
+
     /// Creates a new stack allocation instruction in the entry block of the function.
     pub(super) fn create_entry_block_alloca(
         &self,
         function: FunctionValue<'ctx>,
         name: &str,
         otter_type: OtterType,
+    ) -> Result<PointerValue<'ctx>> {
+        let llvm_type: BasicTypeEnum = self
+            .basic_type(otter_type)?
+            .unwrap_or_else(|| self.context.i8_type().into());
+
+        self.create_entry_block_alloca_of_type(function, name, llvm_type)
+    }
+
+    /// Like `create_entry_block_alloca`, for LLVM types with no `OtterType`
+    /// counterpart (e.g. anonymous FFI aggregates).
+    pub(super) fn create_entry_block_alloca_of_type(
+        &self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+        llvm_type: BasicTypeEnum<'ctx>,
     ) -> Result<PointerValue<'ctx>> {
         let builder = self.context.create_builder();
         let entry_block = function.get_first_basic_block().unwrap();
@@ -844,10 +962,6 @@ impl<'ctx> Compiler<'ctx> {
             None => builder.position_at_end(entry_block),
         }
 
-        let llvm_type: BasicTypeEnum = self
-            .basic_type(otter_type)?
-            .unwrap_or_else(|| self.context.i8_type().into());
-
         Ok(builder.build_alloca(llvm_type, name)?)
     }
 
@@ -858,19 +972,26 @@ impl<'ctx> Compiler<'ctx> {
         _pgo_profile_file: Option<&Path>,
         _inline_threshold: Option<u32>,
         target_machine: &TargetMachine,
-    ) {
-        if matches!(level, CodegenOptLevel::None) {
-            return;
-        }
+    ) -> Result<()> {
+        let Some(pipeline) = level.pass_pipeline() else {
+            return Ok(());
+        };
 
-        // Simplified pass running for now
         let pass_options = PassBuilderOptions::create();
-        pass_options.set_loop_interleaving(true);
-        pass_options.set_loop_vectorization(true);
-
-        let _ = self
-            .module
-            .run_passes("default<O2>", target_machine, pass_options);
+        pass_options.set_loop_interleaving(pipeline.loop_interleaving);
+        pass_options.set_loop_vectorization(pipeline.loop_vectorization);
+        pass_options.set_loop_slp_vectorization(pipeline.slp_vectorization);
+        pass_options.set_loop_unrolling(pipeline.loop_unrolling);
+        pass_options.set_merge_functions(pipeline.merge_functions);
+
+        self.module
+            .run_passes(pipeline.passes, target_machine, pass_options)
+            .map_err(|e| CodegenError::Other {
+                message: format!("pass pipeline `{}` failed: {e}", pipeline.passes),
+                span: None,
+                function: None,
+            })?;
+        Ok(())
     }
 
     /// Build a heap allocation using the GC
@@ -915,3 +1036,55 @@ impl<'ctx> Compiler<'ctx> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use otterc_config::CodegenOptLevel;
+
+    use super::test_support::{compile_to_ir, function_ir};
+
+    // A constant-trip loop whose result has no closed form, so it survives
+    // O2 and is only unrolled at O3
+    const SOURCE: &str = "fn scaled(x: int) -> int:\n    let total = x\n    let i = 0\n    while i < 6:\n        total = total * total + i\n        i = i + 1\n    return total\n";
+
+    fn scaled_ir(level: CodegenOptLevel) -> String {
+        let ir = compile_to_ir(SOURCE, level).unwrap();
+        function_ir(&ir, "scaled").to_string()
+    }
+
+    fn instruction_count(level: CodegenOptLevel) -> usize {
+        scaled_ir(level)
+            .lines()
+            .filter(|line| line.starts_with("  "))
+            .count()
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn optimization_shrinks_functions() {
+        let unoptimized = instruction_count(CodegenOptLevel::None);
+        let default = instruction_count(CodegenOptLevel::Default);
+        let aggressive = instruction_count(CodegenOptLevel::Aggressive);
+        assert!(default < unoptimized, "O2 {default} vs O0 {unoptimized}");
+        assert!(
+            aggressive < unoptimized,
+            "O3 {aggressive} vs O0 {unoptimized}"
+        );
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn aggressive_optimization_unrolls_loops() {
+        let default = scaled_ir(CodegenOptLevel::Default);
+        let aggressive = scaled_ir(CodegenOptLevel::Aggressive);
+        assert_ne!(
+            instruction_count(CodegenOptLevel::Default),
+            instruction_count(CodegenOptLevel::Aggressive)
+        );
+        assert!(default.contains("br i1"), "O2 keeps the loop:\n{default}");
+        assert!(
+            !aggressive.contains("br i1"),
+            "O3 unrolls the loop:\n{aggressive}"
+        );
+    }
+}
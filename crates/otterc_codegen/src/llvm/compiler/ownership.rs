@@ -0,0 +1,288 @@
+//! Freeing caller-owned strings returned by runtime functions.
+//!
+//! A registry function whose signature names a `release` symbol hands its
+//! `str` result over to the caller. When such a result is passed straight
+//! into a runtime call that only reads it, it is freed right after that call.
+//! When it is bound with `let`, it is freed as control leaves the enclosing
+//! block (fall-through, `break`, `continue` or `return`) unless the binding
+//! escapes: it is returned, reassigned, copied into another binding, stored
+//! in a collection or struct, or handed to an Otter function. Escaping
+//! strings are never freed.
+
+use anyhow::Result;
+use inkwell::values::{BasicValueEnum, FunctionValue};
+use otterc_ast::nodes::{Block, Expr, FStringPart, Node, Statement};
+use otterc_symbol::registry::FfiType;
+
+use super::Compiler;
+use super::types::{FunctionContext, OtterType, OwnedString};
+
+impl<'ctx> Compiler<'ctx> {
+    /// Registry name of the function a call to `func` resolves to.
+    fn callee_name(&self, func: &Expr) -> Option<String> {
+        match func {
+            Expr::Identifier(name) => Some(name.clone()),
+            Expr::Member { object, field } => {
+                self.resolve_member_function_name(object.as_ref().as_ref(), field)
+            }
+            _ => None,
+        }
+    }
+
+    /// The runtime function `str(x)` calls for an argument of type `ty`.
+    pub(crate) fn str_overload(ty: &OtterType) -> Option<&'static str> {
+        match ty {
+            OtterType::Str => Some("stringify<string>"),
+            OtterType::I32 | OtterType::I64 => Some("stringify<int>"),
+            OtterType::F64 => Some("stringify<float>"),
+            OtterType::Bool => Some("stringify<bool>"),
+            OtterType::List(_) => Some("stringify<list>"),
+            OtterType::Map => Some("stringify<map>"),
+            _ => None,
+        }
+    }
+
+    /// Whether `name` is a runtime function that reads its string arguments
+    /// without keeping them. Functions returning `str` may hand back their
+    /// argument, so they are not treated as borrowing, and neither are
+    /// overloaded names, whose signature depends on the call.
+    pub(crate) fn is_borrowing_callee(&self, name: &str) -> bool {
+        !self.function_signatures.contains_key(name)
+            && !self.symbol_registry.is_overloaded(name)
+            && self
+                .symbol_registry
+                .resolve(name)
+                .is_some_and(|function| function.signature.result != FfiType::Str)
+    }
+
+    /// The function freeing the string `expr` evaluates to, when `expr` is a
+    /// call that hands ownership of its result to the caller.
+    pub(crate) fn owned_result_release(&self, expr: &Expr) -> Option<FunctionValue<'ctx>> {
+        let Expr::Call { func, args, .. } = expr else {
+            return None;
+        };
+        let mut name = self.callee_name(func.as_ref().as_ref())?;
+        if name == "str"
+            && let [arg] = args.as_slice()
+        {
+            let arg_ty = self
+                .expr_type(arg.as_ref())
+                .and_then(|ty| self.typeinfo_to_otter_type(ty))?;
+            name = Self::str_overload(&arg_ty)?.to_string();
+        }
+        if self.function_signatures.contains_key(&name) {
+            return None;
+        }
+        self.release_function(&name)
+    }
+
+    /// The function freeing results of the registry function `name`, if it
+    /// hands them to the caller. Results of overloaded functions are never
+    /// freed.
+    pub(crate) fn release_function(&self, name: &str) -> Option<FunctionValue<'ctx>> {
+        if self.symbol_registry.is_overloaded(name) {
+            return None;
+        }
+        let symbol = self.symbol_registry.resolve(name)?.signature.release?;
+        Some(self.module.get_function(&symbol).unwrap_or_else(|| {
+            let fn_type = self
+                .context
+                .void_type()
+                .fn_type(&[self.string_ptr_type.into()], false);
+            self.module.add_function(&symbol, fn_type, None)
+        }))
+    }
+
+    pub(crate) fn release_string(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        release: FunctionValue<'ctx>,
+    ) -> Result<()> {
+        self.builder.build_call(release, &[value.into()], "")?;
+        Ok(())
+    }
+
+    /// Starts tracking the string bound by `let name = expr` for release at
+    /// the end of the current block, provided the binding does not escape in
+    /// `rest`, the statements following it.
+    pub(crate) fn track_owned_binding(
+        &self,
+        name: &str,
+        expr: &Expr,
+        rest: &[Node<Statement>],
+        ctx: &mut FunctionContext<'ctx>,
+    ) {
+        let Some(release) = self.owned_result_release(expr) else {
+            return;
+        };
+        let Some(var) = ctx.get(name).filter(|var| var.ty == OtterType::Str) else {
+            return;
+        };
+        if rest
+            .iter()
+            .any(|stmt| self.string_escapes_in_statement(name, stmt.as_ref()))
+        {
+            return;
+        }
+        let owned = OwnedString {
+            ptr: var.ptr,
+            release,
+        };
+        if let Some(scope) = ctx.owned_strings.last_mut() {
+            scope.push(owned);
+        }
+    }
+
+    /// Frees the strings tracked in every scope from `depth` inwards, as
+    /// control jumps out of them. The scopes themselves stay open.
+    pub(crate) fn release_scopes_from(
+        &self,
+        ctx: &FunctionContext<'ctx>,
+        depth: usize,
+    ) -> Result<()> {
+        for scope in ctx.owned_strings.iter().skip(depth).rev() {
+            self.release_scope(scope)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn release_scope(&self, scope: &[OwnedString<'ctx>]) -> Result<()> {
+        for owned in scope.iter().rev() {
+            let value = self
+                .builder
+                .build_load(self.string_ptr_type, owned.ptr, "owned_str")?;
+            self.release_string(value, owned.release)?;
+        }
+        Ok(())
+    }
+
+    fn string_escapes_in_block(&self, name: &str, block: &Block) -> bool {
+        block
+            .statements
+            .iter()
+            .any(|stmt| self.string_escapes_in_statement(name, stmt.as_ref()))
+    }
+
+    fn string_escapes_in_statement(&self, name: &str, stmt: &Statement) -> bool {
+        let escapes = |expr: &Node<Expr>| self.string_escapes_in_expr(name, expr.as_ref());
+        match stmt {
+            Statement::Assignment { name: target, expr }
+            | Statement::CompoundAssignment {
+                name: target, expr, ..
+            } => target.as_ref() == name || escapes(expr),
+            Statement::Let { expr, .. } | Statement::Expr(expr) | Statement::Return(Some(expr)) => {
+                escapes(expr)
+            }
+            Statement::If {
+                cond,
+                then_block,
+                elif_blocks,
+                else_block,
+            } => {
+                escapes(cond)
+                    || self.string_escapes_in_block(name, then_block.as_ref())
+                    || elif_blocks.iter().any(|(cond, block)| {
+                        escapes(cond) || self.string_escapes_in_block(name, block.as_ref())
+                    })
+                    || else_block
+                        .as_ref()
+                        .is_some_and(|block| self.string_escapes_in_block(name, block.as_ref()))
+            }
+            Statement::For { iterable, body, .. } => {
+                escapes(iterable) || self.string_escapes_in_block(name, body.as_ref())
+            }
+            Statement::While { cond, body } => {
+                escapes(cond) || self.string_escapes_in_block(name, body.as_ref())
+            }
+            Statement::Block(block) => self.string_escapes_in_block(name, block.as_ref()),
+            Statement::Function(function) => {
+                self.string_escapes_in_block(name, function.as_ref().body.as_ref())
+            }
+            Statement::Return(None)
+            | Statement::Break(_)
+            | Statement::Continue(_)
+            | Statement::Pass
+            | Statement::Struct { .. }
+            | Statement::Enum { .. }
+            | Statement::TypeAlias { .. }
+            | Statement::Use { .. }
+            | Statement::PubUse { .. } => false,
+        }
+    }
+
+    /// Whether `expr` uses `name` anywhere other than as a value that is
+    /// only read: an operand, an f-string part, or an argument to a
+    /// borrowing runtime function.
+    fn string_escapes_in_expr(&self, name: &str, expr: &Expr) -> bool {
+        let escapes = |expr: &Node<Expr>| self.string_escapes_in_expr(name, expr.as_ref());
+        let read = |expr: &Node<Expr>| match expr.as_ref() {
+            Expr::Identifier(_) => false,
+            other => self.string_escapes_in_expr(name, other),
+        };
+        match expr {
+            Expr::Identifier(ident) => ident == name,
+            Expr::Literal(_) => false,
+            Expr::Member { object, .. } => escapes(object),
+            Expr::Call { func, args, kwargs } => {
+                let borrowing = self
+                    .callee_name(func.as_ref().as_ref())
+                    .is_some_and(|callee| self.is_borrowing_callee(&callee));
+                let arg = |expr: &Node<Expr>| if borrowing { read(expr) } else { escapes(expr) };
+                escapes(func) || args.iter().any(arg) || kwargs.iter().any(|(_, expr)| arg(expr))
+            }
+            Expr::Binary { left, right, .. } => read(left) || read(right),
+            Expr::Index { base, index } => read(base) || read(index),
+            Expr::Unary { expr, .. } => read(expr),
+            Expr::If {
+                cond,
+                then_branch,
+                else_branch,
+            } => {
+                read(cond)
+                    || escapes(then_branch)
+                    || else_branch.as_ref().is_some_and(|expr| escapes(expr))
+            }
+            Expr::Match { value, arms } => {
+                escapes(value)
+                    || arms.iter().any(|arm| {
+                        let arm = arm.as_ref();
+                        arm.guard.as_ref().is_some_and(escapes)
+                            || self.string_escapes_in_block(name, arm.body.as_ref())
+                    })
+            }
+            Expr::Range { start, end, .. } => escapes(start) || escapes(end),
+            Expr::Array(items) => items.iter().any(escapes),
+            Expr::Dict(pairs) => pairs
+                .iter()
+                .any(|(key, value)| escapes(key) || escapes(value)),
+            Expr::ListComprehension {
+                element,
+                iterable,
+                condition,
+                ..
+            } => {
+                escapes(element)
+                    || escapes(iterable)
+                    || condition.as_ref().is_some_and(|cond| escapes(cond))
+            }
+            Expr::DictComprehension {
+                key,
+                value,
+                iterable,
+                condition,
+                ..
+            } => {
+                escapes(key)
+                    || escapes(value)
+                    || escapes(iterable)
+                    || condition.as_ref().is_some_and(|cond| escapes(cond))
+            }
+            Expr::FString { parts } => parts.iter().any(|part| match part.as_ref() {
+                FStringPart::Expr(expr) => read(expr),
+                FStringPart::Text(_) => false,
+            }),
+            Expr::Await(expr) | Expr::Spawn(expr) => escapes(expr),
+            Expr::Struct { fields, .. } => fields.iter().any(|(_, expr)| escapes(expr)),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Type inference for unannotated parameters.
+//!
+//! Only used when `CodegenOptions::infer_param_types` is set; otherwise an
+//! untyped parameter is rejected. The inference is local to the function
+//! body: a use of the parameter next to a literal (`name + "!"`, `name > 1.5`,
+//! `name and flag`) says what the parameter holds. When every such use agrees
+//! the parameter takes that type, and with no evidence it falls back to `int`.
+
+use anyhow::{Result, bail};
+use otterc_ast::nodes::{BinaryOp, Block, Expr, Literal, Node, Statement, UnaryOp};
+use otterc_ast::visit::{Visitor, walk_expr, walk_statement};
+
+use super::types::OtterType;
+
+/// The type `name` is used as in `body`.
+pub(crate) fn infer_param_type(name: &str, body: &Node<Block>) -> Result<OtterType> {
+    let mut collector = UseCollector {
+        name,
+        found: Vec::new(),
+    };
+    collector.visit_block(body);
+
+    let mut found = collector.found.into_iter();
+    let Some(first) = found.next() else {
+        return Ok(OtterType::I64);
+    };
+    for other in found {
+        if other != first {
+            bail!(
+                "cannot infer a type for parameter '{}': it is used as both {} and {}",
+                name,
+                first,
+                other
+            );
+        }
+    }
+    Ok(first)
+}
+
+struct UseCollector<'a> {
+    name: &'a str,
+    found: Vec<OtterType>,
+}
+
+impl UseCollector<'_> {
+    fn is_param(&self, expr: &Node<Expr>) -> bool {
+        matches!(expr.as_ref(), Expr::Identifier(ident) if ident == self.name)
+    }
+
+    fn literal_type(expr: &Node<Expr>) -> Option<OtterType> {
+        match expr.as_ref() {
+            Expr::Literal(lit) => match lit.as_ref() {
+                Literal::String(_) => Some(OtterType::Str),
+                Literal::Number(num) if num.is_float_literal => Some(OtterType::F64),
+                Literal::Number(_) => Some(OtterType::I64),
+                Literal::Bool(_) => Some(OtterType::Bool),
+                Literal::None | Literal::Unit => None,
+            },
+            Expr::FString { .. } => Some(OtterType::Str),
+            _ => None,
+        }
+    }
+
+    fn note_condition(&mut self, cond: &Node<Expr>) {
+        if self.is_param(cond) {
+            self.found.push(OtterType::Bool);
+        }
+    }
+}
+
+impl Visitor for UseCollector<'_> {
+    fn visit_statement(&mut self, stmt: &Node<Statement>) {
+        match stmt.as_ref() {
+            Statement::If {
+                cond, elif_blocks, ..
+            } => {
+                self.note_condition(cond);
+                for (elif_cond, _) in elif_blocks {
+                    self.note_condition(elif_cond);
+                }
+            }
+            Statement::While { cond, .. } => self.note_condition(cond),
+            _ => {}
+        }
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Node<Expr>) {
+        match expr.as_ref() {
+            Expr::Binary { op, left, right } => {
+                let logical = matches!(op, BinaryOp::And | BinaryOp::Or);
+                for (side, other) in [(left, right), (right, left)] {
+                    if !self.is_param(side) {
+                        continue;
+                    }
+                    if logical {
+                        self.found.push(OtterType::Bool);
+                    } else if let Some(ty) = Self::literal_type(other) {
+                        self.found.push(ty);
+                    }
+                }
+            }
+            Expr::Unary {
+                op: UnaryOp::Not,
+                expr: inner,
+            } if self.is_param(inner) => self.found.push(OtterType::Bool),
+            Expr::If { cond, .. } => self.note_condition(cond),
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use otterc_ast::nodes::Statement;
+
+    use super::*;
+
+    /// The inferred type of parameter `param` of the only function in `source`
+    fn infer(source: &str, param: &str) -> Result<OtterType> {
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let Some(Statement::Function(func)) = program.statements.first().map(|stmt| stmt.as_ref())
+        else {
+            unreachable!("source starts with a function");
+        };
+        infer_param_type(param, &func.as_ref().body)
+    }
+
+    #[test]
+    fn infers_from_uses_next_to_literals() {
+        let cases = [
+            ("fn f(x):\n    return x + \"!\"\n", OtterType::Str),
+            ("fn f(x):\n    return x > 1.5\n", OtterType::F64),
+            (
+                "fn f(x):\n    if x:\n        return 1\n    return 2\n",
+                OtterType::Bool,
+            ),
+            ("fn f(x, y):\n    return not x or y\n", OtterType::Bool),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(infer(source, "x").unwrap(), expected, "{source}");
+        }
+    }
+
+    #[test]
+    fn falls_back_to_int_without_evidence() {
+        assert_eq!(
+            infer("fn f(x, y):\n    return x + y\n", "x").unwrap(),
+            OtterType::I64
+        );
+    }
+
+    #[test]
+    fn rejects_conflicting_uses() {
+        let err = infer("fn f(x):\n    let s = x + \"!\"\n    return x * 2.0\n", "x").unwrap_err();
+        assert!(err.to_string().contains("used as both"), "{err}");
+    }
+}
@@ -1,8 +1,13 @@
-use anyhow::{Result, bail};
-use inkwell::values::{BasicValueEnum, FunctionValue};
+use anyhow::{Result, anyhow, bail};
+use inkwell::basic_block::BasicBlock;
+use inkwell::values::{BasicValueEnum, FunctionValue, IntValue};
 
 use crate::llvm::compiler::Compiler;
-use crate::llvm::compiler::types::{EvaluatedValue, FunctionContext, OtterType, Variable};
+use crate::llvm::compiler::constants;
+use crate::llvm::compiler::types::{
+    EvaluatedValue, FunctionContext, LoopIterator, OtterType, Variable,
+};
+use crate::llvm::error::CodegenError;
 use otterc_ast::nodes::{Block, Expr, Statement};
 use otterc_typecheck::TypeInfo;
 
@@ -21,9 +26,88 @@ impl<'ctx> Compiler<'ctx> {
         function: FunctionValue<'ctx>,
         ctx: &mut FunctionContext<'ctx>,
     ) -> Result<()> {
-        for stmt in &block.statements {
-            self.lower_statement(stmt.as_ref(), function, ctx)?;
+        ctx.owned_strings.push(Vec::new());
+        for (index, stmt) in block.statements.iter().enumerate() {
+            self.lower_statement(stmt.as_ref(), function, ctx)
+                .map_err(|err| CodegenError::locate(err, *stmt.span()))?;
+            if let Statement::Let { name, expr, .. } = stmt.as_ref() {
+                let rest = &block.statements[index + 1..];
+                self.track_owned_binding(name.as_ref(), expr.as_ref(), rest, ctx);
+            }
+            // Anything after a diverging statement is unreachable; lowering it
+            // would append instructions after a terminator.
+            if Self::statement_diverges(stmt.as_ref()) || self.current_block_terminated() {
+                break;
+            }
+        }
+        let scope = ctx.owned_strings.pop().unwrap_or_default();
+        if !self.current_block_terminated() {
+            self.release_scope(&scope)?;
+        }
+        Ok(())
+    }
+
+    /// Whether control can never fall off the end of `block`.
+    pub(crate) fn block_diverges(block: &Block) -> bool {
+        block
+            .statements
+            .iter()
+            .any(|stmt| Self::statement_diverges(stmt.as_ref()))
+    }
+
+    /// Whether `stmt` always transfers control elsewhere (return, break,
+    /// continue, or an `if` whose every branch does).
+    pub(crate) fn statement_diverges(stmt: &Statement) -> bool {
+        match stmt {
+            Statement::Return(_) | Statement::Break(_) | Statement::Continue(_) => true,
+            Statement::If {
+                then_block,
+                elif_blocks,
+                else_block,
+                ..
+            } => {
+                else_block
+                    .as_ref()
+                    .is_some_and(|block| Self::block_diverges(block.as_ref()))
+                    && Self::block_diverges(then_block.as_ref())
+                    && elif_blocks
+                        .iter()
+                        .all(|(_, block)| Self::block_diverges(block.as_ref()))
+            }
+            Statement::Block(block) => Self::block_diverges(block.as_ref()),
+            _ => false,
+        }
+    }
+
+    /// Whether `expr` calls the function named `function` directly.
+    fn is_self_call(expr: &Expr, function: &str) -> bool {
+        matches!(
+            expr,
+            Expr::Call { func, .. }
+                if matches!(func.as_ref().as_ref(), Expr::Identifier(name) if name == function)
+        )
+    }
+
+    pub(crate) fn current_block_terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .and_then(|b| b.get_terminator())
+            .is_some()
+    }
+
+    /// Branch to the `if` merge block, creating it the first time a branch
+    /// falls through.
+    fn branch_to_merge(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        merge_bb: &mut Option<BasicBlock<'ctx>>,
+    ) -> Result<()> {
+        if self.current_block_terminated() {
+            return Ok(());
         }
+        let merge =
+            *merge_bb.get_or_insert_with(|| self.context.append_basic_block(function, "merge"));
+        self.builder.build_unconditional_branch(merge)?;
         Ok(())
     }
 
@@ -35,19 +119,53 @@ impl<'ctx> Compiler<'ctx> {
     ) -> Result<()> {
         match stmt {
             Statement::Expr(expr) => {
-                self.eval_expr(expr.as_ref(), ctx)?;
+                let value = self.eval_expr(expr.as_ref(), ctx)?;
+                if let Some(v) = value.value
+                    && let Some(release) = self.owned_result_release(expr.as_ref())
+                {
+                    self.release_string(v, release)?;
+                }
                 Ok(())
             }
             Statement::Return(expr) => {
-                if let Some(expr) = expr {
-                    let val = self.eval_expr(expr.as_ref(), ctx)?;
-                    if let Some(v) = val.value {
-                        self.builder.build_return(Some(&v))?;
-                    } else {
+                let name = ctx.function_name.clone().unwrap_or_default();
+                let declared = self
+                    .function_signatures
+                    .get(&name)
+                    .map(|signature| signature.ret.clone())
+                    .unwrap_or(OtterType::Unit);
+                let value = match expr {
+                    Some(expr) => {
+                        ctx.tail_call = Self::is_self_call(expr.as_ref(), &name)
+                            && ctx.owned_strings.iter().all(Vec::is_empty)
+                            && ctx.loop_stack.iter().all(|loop_ctx| loop_ctx.iterator.is_none());
+                        let value = self.eval_expr(expr.as_ref(), ctx)?.value;
+                        ctx.tail_call = false;
+                        value
+                    }
+                    None => None,
+                };
+                match (value, declared) {
+                    (Some(v), OtterType::Unit) => bail!(
+                        "function `{}` has no return type but `return` has a value of type {}",
+                        name,
+                        self.otter_type_from_basic_type(v.get_type())
+                    ),
+                    (None, OtterType::Unit) => {
+                        self.free_iterators_from(ctx, 0)?;
+                        self.release_scopes_from(ctx, 0)?;
                         self.builder.build_return(None)?;
                     }
-                } else {
-                    self.builder.build_return(None)?;
+                    (None, declared) => bail!(
+                        "`return` without a value in function `{}` declared to return {}",
+                        name,
+                        declared
+                    ),
+                    (Some(v), _) => {
+                        self.free_iterators_from(ctx, 0)?;
+                        self.release_scopes_from(ctx, 0)?;
+                        self.builder.build_return(Some(&v))?;
+                    }
                 }
                 Ok(())
             }
@@ -70,6 +188,19 @@ impl<'ctx> Compiler<'ctx> {
                     val_ty.clone()
                 };
 
+                // A literal that is never reassigned needs no stack slot
+                if ctx.constant_names.contains(name.as_ref())
+                    && constants::is_constant_literal(expr.as_ref())
+                    && let Some(v) = val_value
+                {
+                    let coerced_val = self.coerce_type(v, val_ty, var_ty.clone())?;
+                    ctx.insert_constant(
+                        name.as_ref().to_string(),
+                        EvaluatedValue::with_value(coerced_val, var_ty),
+                    );
+                    return Ok(());
+                }
+
                 // Skip allocation for Unit types
                 if let Some(_basic_ty) = self.basic_type(var_ty.clone())? {
                     // Use create_entry_block_alloca to ensure alloca is in the entry block
@@ -119,10 +250,16 @@ impl<'ctx> Compiler<'ctx> {
                     }
                     // Unit type assignments are no-ops
                 } else {
-                    bail!("Variable {} not declared", name.as_ref());
+                    bail!(CodegenError::unknown_identifier(name.as_ref().as_str()));
                 }
                 Ok(())
             }
+            Statement::CompoundAssignment { name, .. } => {
+                bail!(
+                    "Compound assignment to {} was not desugared before code generation",
+                    name.as_ref()
+                )
+            }
             Statement::If {
                 cond,
                 then_block,
@@ -139,20 +276,42 @@ impl<'ctx> Compiler<'ctx> {
             Statement::While { cond, body } => {
                 self.lower_while_loop(function, ctx, cond.as_ref(), body.as_ref())
             }
-            Statement::Break => {
-                if let Some(loop_ctx) = ctx.current_loop() {
-                    self.builder.build_unconditional_branch(loop_ctx.exit_bb)?;
-                } else {
-                    bail!("break statement outside of loop");
-                }
+            Statement::Break(label) => {
+                let position = match label {
+                    Some(label) => match ctx.labeled_loop(label.as_ref()) {
+                        Some(position) => position,
+                        None => bail!("unknown loop label `{}`", label.as_ref()),
+                    },
+                    None => match ctx.current_loop() {
+                        Some(position) => position,
+                        None => bail!("break statement outside of loop"),
+                    },
+                };
+                let loop_ctx = &ctx.loop_stack[position];
+                let (target, depth) = (loop_ctx.exit_bb, loop_ctx.scope_depth);
+                // Loops nested inside the target are left for good
+                self.free_iterators_from(ctx, position + 1)?;
+                self.release_scopes_from(ctx, depth)?;
+                self.builder.build_unconditional_branch(target)?;
                 Ok(())
             }
-            Statement::Continue => {
-                if let Some(loop_ctx) = ctx.current_loop() {
-                    self.builder.build_unconditional_branch(loop_ctx.cond_bb)?;
-                } else {
-                    bail!("continue statement outside of loop");
-                }
+            Statement::Continue(label) => {
+                let position = match label {
+                    Some(label) => match ctx.labeled_loop(label.as_ref()) {
+                        Some(position) => position,
+                        None => bail!("unknown loop label `{}`", label.as_ref()),
+                    },
+                    None => match ctx.current_loop() {
+                        Some(position) => position,
+                        None => bail!("continue statement outside of loop"),
+                    },
+                };
+                let loop_ctx = &ctx.loop_stack[position];
+                let (target, depth) = (loop_ctx.cond_bb, loop_ctx.scope_depth);
+                // Loops nested inside the target are left for good
+                self.free_iterators_from(ctx, position + 1)?;
+                self.release_scopes_from(ctx, depth)?;
+                self.builder.build_unconditional_branch(target)?;
                 Ok(())
             }
             Statement::Pass
@@ -195,7 +354,9 @@ impl<'ctx> Compiler<'ctx> {
 
         let then_bb = self.context.append_basic_block(function, "then");
         let else_bb = self.context.append_basic_block(function, "else");
-        let merge_bb = self.context.append_basic_block(function, "merge");
+        // Only created if some branch falls through; when every branch
+        // diverges there is nothing after the `if` to reach.
+        let mut merge_bb = None;
 
         self.builder
             .build_conditional_branch(cond_bool, then_bb, else_bb)?;
@@ -203,14 +364,7 @@ impl<'ctx> Compiler<'ctx> {
         // Then block
         self.builder.position_at_end(then_bb);
         self.lower_block(then_block, function, ctx)?;
-        if self
-            .builder
-            .get_insert_block()
-            .and_then(|b| b.get_terminator())
-            .is_none()
-        {
-            self.builder.build_unconditional_branch(merge_bb)?;
-        }
+        self.branch_to_merge(function, &mut merge_bb)?;
 
         // Else block (handle elifs recursively or iteratively)
         self.builder.position_at_end(else_bb);
@@ -226,19 +380,12 @@ impl<'ctx> Compiler<'ctx> {
             )?;
         } else if let Some(block) = else_block {
             self.lower_block(block, function, ctx)?;
-            if self
-                .builder
-                .get_insert_block()
-                .and_then(|b| b.get_terminator())
-                .is_none()
-            {
-                self.builder.build_unconditional_branch(merge_bb)?;
-            }
-        } else {
-            self.builder.build_unconditional_branch(merge_bb)?;
         }
+        self.branch_to_merge(function, &mut merge_bb)?;
 
-        self.builder.position_at_end(merge_bb);
+        if let Some(merge_bb) = merge_bb {
+            self.builder.position_at_end(merge_bb);
+        }
         Ok(())
     }
 
@@ -264,7 +411,7 @@ impl<'ctx> Compiler<'ctx> {
 
         // Body
         self.builder.position_at_end(body_bb);
-        ctx.push_loop(cond_bb, exit_bb);
+        ctx.push_loop(None, cond_bb, exit_bb, None);
         self.lower_block(body, function, ctx)?;
         ctx.pop_loop();
 
@@ -289,133 +436,126 @@ impl<'ctx> Compiler<'ctx> {
         function: FunctionValue<'ctx>,
         ctx: &mut FunctionContext<'ctx>,
     ) -> Result<()> {
-        use otterc_ast::nodes::Expr;
-
-        // Iterator protocol implementation for range-based for loops
-
-        // Check if iterable is a range expression
-        if let Expr::Range { start, end } = iterable {
-            // Evaluate start and end
-            let start_val = self.eval_expr(start.as_ref().as_ref(), ctx)?;
-            let end_val = self.eval_expr(end.as_ref().as_ref(), ctx)?;
-            let start_ty = start_val.ty.clone();
-
-            // Determine if we're using I64 or F64 range
-            let is_float = start_ty == OtterType::F64;
-
-            // Call the appropriate range function to get a list handle
-            let range_fn_name = if is_float {
-                "range<float>"
-            } else {
-                "range<int>"
-            };
-
-            let range_fn = self.get_or_declare_ffi_function(range_fn_name)?;
-
-            let list_handle = self.builder.build_call(
-                range_fn,
-                &[
-                    start_val.value.unwrap().into(),
-                    end_val.value.unwrap().into(),
-                ],
-                "range_list",
-            )?;
-
-            let list_val = list_handle.try_as_basic_value().left().unwrap();
+        let iterable_val = self.eval_expr(iterable, ctx)?;
+        let iterable_ty = iterable_val.ty.clone();
+
+        match iterable_ty {
+            OtterType::Range => {
+                let list_val = self.range_to_list(&iterable_val)?;
+                let iter_create_fn = self.get_or_declare_ffi_function("__otter_iter_array")?;
+                let iter_has_next_fn =
+                    self.get_or_declare_ffi_function("__otter_iter_has_next_array")?;
+                let iter_next_fn = self.get_or_declare_ffi_function("__otter_iter_next_array")?;
+                let iter_free_fn = self.get_or_declare_ffi_function("__otter_iter_free_array")?;
+
+                self.lower_collection_for_loop(
+                    var,
+                    EvaluatedValue::with_value(list_val.into(), OtterType::list_of(OtterType::I64)),
+                    body,
+                    function,
+                    ctx,
+                    IteratorRuntime {
+                        create_fn: iter_create_fn,
+                        has_next_fn: iter_has_next_fn,
+                        next_fn: iter_next_fn,
+                        free_fn: iter_free_fn,
+                        element_type: OtterType::I64,
+                    },
+                )
+            }
+            OtterType::Str => {
+                // String iteration (character by character)
+                let (iter_create_fn, iter_has_next_fn, iter_next_fn, iter_free_fn) = (
+                    self.get_or_declare_ffi_function("__otter_iter_string")?,
+                    self.get_or_declare_ffi_function("__otter_iter_has_next_string")?,
+                    self.get_or_declare_ffi_function("__otter_iter_next_string")?,
+                    self.get_or_declare_ffi_function("__otter_iter_free_string")?,
+                );
+
+                self.lower_collection_for_loop(
+                    var,
+                    iterable_val,
+                    body,
+                    function,
+                    ctx,
+                    IteratorRuntime {
+                        create_fn: iter_create_fn,
+                        has_next_fn: iter_has_next_fn,
+                        next_fn: iter_next_fn,
+                        free_fn: iter_free_fn,
+                        element_type: OtterType::Str, // Each character is a string
+                    },
+                )
+            }
+            OtterType::List(_) => {
+                // Array/list iteration
+                let iter_create_fn = self.get_or_declare_ffi_function("__otter_iter_array")?;
+                let iter_has_next_fn =
+                    self.get_or_declare_ffi_function("__otter_iter_has_next_array")?;
+                let iter_next_fn = self.get_or_declare_ffi_function("__otter_iter_next_array")?;
+                let iter_free_fn = self.get_or_declare_ffi_function("__otter_iter_free_array")?;
+
+                let element_type = self
+                    .list_element_type(iterable)
+                    .or_else(|| iterable_val.ty.list_element().cloned())
+                    .unwrap_or(OtterType::Opaque);
 
-            // Now treat it as a list iteration
-            let iter_create_fn = self.get_or_declare_ffi_function("__otter_iter_array")?;
-            let iter_has_next_fn =
-                self.get_or_declare_ffi_function("__otter_iter_has_next_array")?;
-            let iter_next_fn = self.get_or_declare_ffi_function("__otter_iter_next_array")?;
-            let iter_free_fn = self.get_or_declare_ffi_function("__otter_iter_free_array")?;
+                self.lower_collection_for_loop(
+                    var,
+                    iterable_val,
+                    body,
+                    function,
+                    ctx,
+                    IteratorRuntime {
+                        create_fn: iter_create_fn,
+                        has_next_fn: iter_has_next_fn,
+                        next_fn: iter_next_fn,
+                        free_fn: iter_free_fn,
+                        element_type,
+                    },
+                )
+            }
+            OtterType::Map => {
+                // Map iteration is not yet implemented
+                bail!("Map iteration is not yet supported")
+            }
+            _ => bail!("For loops over type {} are not supported yet", iterable_ty),
+        }
+    }
 
-            self.lower_collection_for_loop(
-                var,
-                EvaluatedValue::with_value(list_val, OtterType::list_of(start_ty.clone())),
-                body,
-                function,
-                ctx,
-                IteratorRuntime {
-                    create_fn: iter_create_fn,
-                    has_next_fn: iter_has_next_fn,
-                    next_fn: iter_next_fn,
-                    free_fn: iter_free_fn,
-                    element_type: start_ty, // Elements of range have same type as start
-                },
-            )?;
+    /// Materialize a range value as the list of integers it covers.
+    fn range_to_list(&mut self, range: &EvaluatedValue<'ctx>) -> Result<IntValue<'ctx>> {
+        let range = range
+            .value
+            .ok_or_else(|| anyhow!("range produced no value"))?
+            .into_struct_value();
+        let start = self
+            .builder
+            .build_extract_value(range, 0, "range_start")?
+            .into_int_value();
+        let end = self
+            .builder
+            .build_extract_value(range, 1, "range_end")?
+            .into_int_value();
+        let inclusive = self
+            .builder
+            .build_extract_value(range, 2, "range_inclusive")?
+            .into_int_value();
 
-            Ok(())
-        } else {
-            // Handle other iterable types (arrays, strings, etc.)
-            let iterable_val = self.eval_expr(iterable, ctx)?;
-            let iterable_ty = iterable_val.ty.clone();
-
-            match iterable_ty {
-                OtterType::Str => {
-                    // String iteration (character by character)
-                    let (iter_create_fn, iter_has_next_fn, iter_next_fn, iter_free_fn) = (
-                        self.get_or_declare_ffi_function("__otter_iter_string")?,
-                        self.get_or_declare_ffi_function("__otter_iter_has_next_string")?,
-                        self.get_or_declare_ffi_function("__otter_iter_next_string")?,
-                        self.get_or_declare_ffi_function("__otter_iter_free_string")?,
-                    );
+        // `range<int>` excludes its end, so an inclusive range extends it by one.
+        let extra =
+            self.builder
+                .build_int_z_extend(inclusive, self.context.i64_type(), "range_extra")?;
+        let end = self.builder.build_int_add(end, extra, "range_end_excl")?;
 
-                    self.lower_collection_for_loop(
-                        var,
-                        iterable_val,
-                        body,
-                        function,
-                        ctx,
-                        IteratorRuntime {
-                            create_fn: iter_create_fn,
-                            has_next_fn: iter_has_next_fn,
-                            next_fn: iter_next_fn,
-                            free_fn: iter_free_fn,
-                            element_type: OtterType::Str, // Each character is a string
-                        },
-                    )
-                }
-                OtterType::List(_) => {
-                    // Array/list iteration
-                    let iter_create_fn = self.get_or_declare_ffi_function("__otter_iter_array")?;
-                    let iter_has_next_fn =
-                        self.get_or_declare_ffi_function("__otter_iter_has_next_array")?;
-                    let iter_next_fn =
-                        self.get_or_declare_ffi_function("__otter_iter_next_array")?;
-                    let iter_free_fn =
-                        self.get_or_declare_ffi_function("__otter_iter_free_array")?;
-
-                    let element_type = self
-                        .list_element_type(iterable)
-                        .or_else(|| iterable_val.ty.list_element().cloned())
-                        .unwrap_or(OtterType::Opaque);
-
-                    self.lower_collection_for_loop(
-                        var,
-                        iterable_val,
-                        body,
-                        function,
-                        ctx,
-                        IteratorRuntime {
-                            create_fn: iter_create_fn,
-                            has_next_fn: iter_has_next_fn,
-                            next_fn: iter_next_fn,
-                            free_fn: iter_free_fn,
-                            element_type,
-                        },
-                    )
-                }
-                OtterType::Map => {
-                    // Map iteration is not yet implemented
-                    bail!("Map iteration is not yet supported")
-                }
-                _ => bail!(
-                    "For loops over type {:?} are not supported yet",
-                    iterable_ty
-                ),
-            }
-        }
+        let range_fn = self.get_or_declare_ffi_function("range<int>")?;
+        Ok(self
+            .builder
+            .build_call(range_fn, &[start.into(), end.into()], "range_list")?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| anyhow!("range<int> returned void"))?
+            .into_int_value())
     }
 
     fn lower_collection_for_loop(
@@ -453,8 +593,8 @@ impl<'ctx> Compiler<'ctx> {
         let element_ty = element_type.clone();
         let var_alloca = self.create_entry_block_alloca(function, var, element_ty.clone())?;
 
-        // Insert variable into context
-        ctx.insert(
+        // Insert variable into context, remembering any outer binding it shadows
+        let shadowed = ctx.insert(
             var.to_string(),
             Variable {
                 ptr: var_alloca,
@@ -509,7 +649,15 @@ impl<'ctx> Compiler<'ctx> {
         }
 
         // Execute loop body
-        ctx.push_loop(loop_cond_bb, cleanup_bb);
+        ctx.push_loop(
+            Some(var.to_string()),
+            loop_cond_bb,
+            cleanup_bb,
+            Some(LoopIterator {
+                handle: iter_val,
+                free_fn,
+            }),
+        );
         self.lower_block(body, function, ctx)?;
         ctx.pop_loop();
 
@@ -532,6 +680,28 @@ impl<'ctx> Compiler<'ctx> {
 
         self.builder.position_at_end(exit_bb);
 
+        // The loop variable is scoped to the body; restore the outer binding.
+        match shadowed {
+            Some(outer) => {
+                ctx.insert(var.to_string(), outer);
+            }
+            None => {
+                ctx.remove(var);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Frees the iterators of the loops at `position` and above in
+    /// `loop_stack`, innermost first, before a jump leaves them.
+    fn free_iterators_from(&self, ctx: &FunctionContext<'ctx>, position: usize) -> Result<()> {
+        for loop_ctx in ctx.loop_stack.iter().skip(position).rev() {
+            if let Some(iterator) = &loop_ctx.iterator {
+                self.builder
+                    .build_call(iterator.free_fn, &[iterator.handle.into()], "")?;
+            }
+        }
         Ok(())
     }
 
@@ -574,6 +744,7 @@ impl<'ctx> Compiler<'ctx> {
                 Some(OtterType::list_of(element))
             }
             TypeInfo::Dict { .. } => Some(OtterType::Map),
+            TypeInfo::Range(..) => Some(OtterType::Range),
             TypeInfo::Struct { name, .. } => self.struct_id(name).map(OtterType::Struct),
             TypeInfo::Alias { underlying, .. } => self.typeinfo_to_otter_type(underlying),
             TypeInfo::Generic { base, args } => {
@@ -916,6 +1087,8 @@ impl<'ctx> Compiler<'ctx> {
                         .build_call(decode_fn, &[encoded_int.into()], "decoded_handle")?;
                 result.try_as_basic_value().left().unwrap()
             }
+
+            OtterType::Range => bail!("collections of range values are not supported yet"),
         };
 
         Ok(Some(decoded_value))
@@ -934,7 +1107,8 @@ impl<'ctx> Compiler<'ctx> {
             | OtterType::List(_)
             | OtterType::Map
             | OtterType::Struct(_)
-            | OtterType::Tuple(_) => raw_value,
+            | OtterType::Tuple(_)
+            | OtterType::Range => raw_value,
             OtterType::I32 => {
                 let int_val = raw_value.into_int_value();
                 self.builder
@@ -965,3 +1139,40 @@ impl<'ctx> Compiler<'ctx> {
         Ok(Some(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    // Links the runtime so its functions are in the stdlib registry
+    use otterc_runtime as _;
+
+    use crate::llvm::compiler::test_support::{function_ir, lower_to_ir};
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn returned_self_call_is_musttail() {
+        let source = "fn sum_to(n: int, acc: int) -> int:\n    if n == 0:\n        return acc\n    return sum_to(n - 1, acc + n)\n\nfn fact(n: int) -> int:\n    if n <= 1:\n        return 1\n    return n * fact(n - 1)\n";
+        let ir = lower_to_ir(source).unwrap();
+
+        let tail = function_ir(&ir, "sum_to");
+        assert!(tail.contains("musttail call"), "{tail}");
+        // The product is computed after the call returns
+        let not_tail = function_ir(&ir, "fact");
+        assert!(not_tail.contains("call"), "{not_tail}");
+        assert!(!not_tail.contains("musttail"), "{not_tail}");
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn labeled_jumps_free_the_inner_iterators() {
+        let source = "fn total(rows: list<list<int>>) -> int:\n    let found = 0\n    for row in rows:\n        for cell in row:\n            if cell == 0:\n                continue row\n            if cell < 0:\n                break row\n            found = found + cell\n    return found\n";
+        let ir = lower_to_ir(source).unwrap();
+
+        // Each loop's own cleanup frees its iterator; `continue row` and
+        // `break row` also leave the inner loop, so each frees its iterator
+        let total = function_ir(&ir, "total");
+        let frees = total
+            .matches("call void @otter_builtin_iter_free_array")
+            .count();
+        assert_eq!(frees, 4, "{total}");
+    }
+}
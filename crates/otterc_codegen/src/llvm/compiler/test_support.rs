@@ -0,0 +1,126 @@
+//! Lowering Otter source to IR text or a loadable library for codegen tests.
+//! These need LLVM, so tests using them are `#[ignore]`d like the other
+//! LLVM-backed tests.
+
+use std::fs;
+use std::process::Command;
+
+use anyhow::{Result, anyhow};
+use inkwell::OptimizationLevel;
+use inkwell::context::Context as LlvmContext;
+use inkwell::module::Module;
+use inkwell::targets::{
+    CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine,
+};
+use libloading::Library;
+use otterc_config::CodegenOptLevel;
+use otterc_typecheck::TypeChecker;
+use tempfile::TempDir;
+
+use super::Compiler;
+
+/// The module's IR after lowering `source`, before any optimization
+pub(crate) fn lower_to_ir(source: &str) -> Result<String> {
+    compile_to_ir(source, CodegenOptLevel::None)
+}
+
+/// The module's IR after lowering `source` and running the pass pipeline
+/// for `level`
+pub(crate) fn compile_to_ir(source: &str, level: CodegenOptLevel) -> Result<String> {
+    with_compiled(source, level, RelocMode::Default, |module, _| {
+        Ok(module.print_to_string().to_string())
+    })
+}
+
+/// A shared library of `source`, lowered without optimization, linked with
+/// the C code in `c_source` by the system C compiler. The library's file
+/// lives in the returned directory.
+pub(crate) fn link_with_c(source: &str, c_source: &str) -> Result<(TempDir, Library)> {
+    let dir = TempDir::new()?;
+    let object = dir.path().join("otter.o");
+    with_compiled(
+        source,
+        CodegenOptLevel::None,
+        RelocMode::PIC,
+        |module, machine| {
+            machine
+                .write_to_file(module, FileType::Object, &object)
+                .map_err(|e| anyhow!("failed to emit object file: {e}"))
+        },
+    )?;
+    let c_file = dir.path().join("native.c");
+    fs::write(&c_file, c_source)?;
+    let library = dir.path().join("libotter_test.so");
+    let status = Command::new("cc")
+        .args(["-shared", "-fPIC", "-o"])
+        .arg(&library)
+        .arg(&object)
+        .arg(&c_file)
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("cc failed with {status}"));
+    }
+    let library = unsafe { Library::new(&library)? };
+    Ok((dir, library))
+}
+
+/// Lowers `source`, runs the pass pipeline for `level` and hands the module
+/// to `finish`
+pub(crate) fn with_compiled<T>(
+    source: &str,
+    level: CodegenOptLevel,
+    reloc_mode: RelocMode,
+    finish: impl FnOnce(&Module<'_>, &TargetMachine) -> Result<T>,
+) -> Result<T> {
+    let tokens = otterc_lexer::tokenize(source).map_err(|errors| anyhow!("{errors:?}"))?;
+    let program = otterc_parser::parse(&tokens).map_err(|errors| anyhow!("{errors:?}"))?;
+    let mut checker = TypeChecker::new().with_registry(otterc_ffi::bootstrap_stdlib());
+    checker.check_program(&program)?;
+    let enum_layouts = checker.enum_layouts();
+    let (expr_types, expr_types_by_span, comprehension_var_types) = checker.into_type_maps();
+
+    let context = LlvmContext::create();
+    let mut compiler = Compiler::new(
+        &context,
+        context.create_module("otter_test"),
+        context.create_builder(),
+        otterc_ffi::bootstrap_stdlib(),
+        expr_types,
+        expr_types_by_span,
+        comprehension_var_types,
+        enum_layouts,
+        None,
+    );
+    compiler.lower_program(&program, false)?;
+    compiler
+        .module
+        .verify()
+        .map_err(|e| anyhow!("LLVM module verification failed: {e}"))?;
+
+    Target::initialize_native(&InitializationConfig::default()).map_err(|e| anyhow!(e))?;
+    let triple = TargetMachine::get_default_triple();
+    let target_machine = Target::from_triple(&triple)
+        .map_err(|e| anyhow!("{e}"))?
+        .create_target_machine(
+            &triple,
+            "generic",
+            "",
+            OptimizationLevel::from(level),
+            reloc_mode,
+            CodeModel::Default,
+        )
+        .ok_or_else(|| anyhow!("failed to create target machine"))?;
+    compiler.run_default_passes(level, false, None, None, &target_machine)?;
+
+    finish(&compiler.module, &target_machine)
+}
+
+/// The definition of the function `name` in `ir`
+pub(crate) fn function_ir<'a>(ir: &'a str, name: &str) -> &'a str {
+    let start = ir
+        .find(&format!("@{name}("))
+        .and_then(|at| ir[..at].rfind("define"))
+        .expect("function is defined");
+    let end = ir[start..].find("\n}").map_or(ir.len(), |end| start + end);
+    &ir[start..end]
+}
@@ -1,6 +1,7 @@
 use inkwell::basic_block::BasicBlock;
-use inkwell::values::{BasicValueEnum, PointerValue};
-use std::collections::HashMap;
+use inkwell::values::{BasicValueEnum, FunctionValue, PointerValue};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OtterType {
@@ -15,6 +16,8 @@ pub enum OtterType {
     Map,
     Struct(u32),
     Tuple(Vec<OtterType>),
+    /// Integer range value, lowered to `{ i64 start, i64 end, i1 inclusive }`
+    Range,
 }
 
 impl OtterType {
@@ -35,6 +38,44 @@ impl OtterType {
             _ => None,
         }
     }
+
+    /// The name of this type as written in Otter source, for user-facing errors.
+    /// Element types are omitted; use `Display` for the full form.
+    pub fn otter_name(&self) -> &'static str {
+        match self {
+            OtterType::Unit => "unit",
+            OtterType::Bool => "bool",
+            OtterType::I32 => "i32",
+            OtterType::I64 => "int",
+            OtterType::F64 => "float",
+            OtterType::Str => "str",
+            OtterType::Opaque => "opaque",
+            OtterType::List(_) => "list",
+            OtterType::Map => "map",
+            OtterType::Struct(_) => "struct",
+            OtterType::Tuple(_) => "tuple",
+            OtterType::Range => "range",
+        }
+    }
+}
+
+impl fmt::Display for OtterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OtterType::List(inner) if **inner != OtterType::Opaque => write!(f, "list<{inner}>"),
+            OtterType::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, ")")
+            }
+            other => f.write_str(other.otter_name()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,10 +99,38 @@ pub struct Variable<'ctx> {
     pub ty: OtterType,
 }
 
+/// Parameter and return types of a user function, resolved from its declaration
+#[derive(Debug, Clone)]
+pub struct FunctionSignature {
+    /// Includes `self` for methods; a `*args` parameter is its list type
+    pub params: Vec<OtterType>,
+    pub ret: OtterType,
+}
+
 #[derive(Debug, Clone)]
 pub struct LoopContext<'ctx> {
+    /// Loop variable of a `for` loop, usable as a `break`/`continue` label
+    pub label: Option<String>,
     pub cond_bb: BasicBlock<'ctx>,
     pub exit_bb: BasicBlock<'ctx>,
+    /// Number of owned-string scopes open outside the loop body
+    pub scope_depth: usize,
+    /// Runtime iterator of a `for` loop over a collection
+    pub iterator: Option<LoopIterator<'ctx>>,
+}
+
+/// A `for` loop's iterator handle, freed whenever the loop is left.
+#[derive(Debug, Clone)]
+pub struct LoopIterator<'ctx> {
+    pub handle: BasicValueEnum<'ctx>,
+    pub free_fn: FunctionValue<'ctx>,
+}
+
+/// A caller-owned string held in a local, freed when its block is left.
+#[derive(Debug, Clone)]
+pub struct OwnedString<'ctx> {
+    pub ptr: PointerValue<'ctx>,
+    pub release: FunctionValue<'ctx>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +138,17 @@ pub struct FunctionContext<'ctx> {
     pub variables: HashMap<String, Variable<'ctx>>,
     pub loop_stack: Vec<LoopContext<'ctx>>,
     pub exception_landingpad: Option<BasicBlock<'ctx>>,
+    /// Source name of the function being lowered, for diagnostics
+    pub function_name: Option<String>,
+    /// Caller-owned strings bound in each enclosing block, innermost last
+    pub owned_strings: Vec<Vec<OwnedString<'ctx>>>,
+    /// Names whose single `let` binds a literal that is never reassigned
+    pub constant_names: HashSet<String>,
+    /// Values of those bindings lowered so far, used in place of a load
+    pub constants: HashMap<String, EvaluatedValue<'ctx>>,
+    /// Set while lowering `return f(...)` where `f` is the function itself
+    /// and nothing runs between the call and the return
+    pub tail_call: bool,
 }
 
 impl<'ctx> FunctionContext<'ctx> {
@@ -77,11 +157,17 @@ impl<'ctx> FunctionContext<'ctx> {
             variables: HashMap::new(),
             loop_stack: Vec::new(),
             exception_landingpad: None,
+            function_name: None,
+            owned_strings: Vec::new(),
+            constant_names: HashSet::new(),
+            constants: HashMap::new(),
+            tail_call: false,
         }
     }
 
-    pub fn insert(&mut self, name: String, var: Variable<'ctx>) {
-        self.variables.insert(name, var);
+    /// Binds `name`, returning the binding it replaces, if any.
+    pub fn insert(&mut self, name: String, var: Variable<'ctx>) -> Option<Variable<'ctx>> {
+        self.variables.insert(name, var)
     }
 
     pub fn get(&self, name: &str) -> Option<&Variable<'ctx>> {
@@ -92,16 +178,46 @@ impl<'ctx> FunctionContext<'ctx> {
         self.variables.remove(name)
     }
 
-    pub fn push_loop(&mut self, cond_bb: BasicBlock<'ctx>, exit_bb: BasicBlock<'ctx>) {
-        self.loop_stack.push(LoopContext { cond_bb, exit_bb });
+    /// Binds `name` to a constant, shadowing any variable of that name.
+    pub fn insert_constant(&mut self, name: String, value: EvaluatedValue<'ctx>) {
+        self.variables.remove(&name);
+        self.constants.insert(name, value);
+    }
+
+    pub fn constant(&self, name: &str) -> Option<&EvaluatedValue<'ctx>> {
+        self.constants.get(name)
+    }
+
+    pub fn push_loop(
+        &mut self,
+        label: Option<String>,
+        cond_bb: BasicBlock<'ctx>,
+        exit_bb: BasicBlock<'ctx>,
+        iterator: Option<LoopIterator<'ctx>>,
+    ) {
+        self.loop_stack.push(LoopContext {
+            label,
+            cond_bb,
+            exit_bb,
+            scope_depth: self.owned_strings.len(),
+            iterator,
+        });
     }
 
     pub fn pop_loop(&mut self) -> Option<LoopContext<'ctx>> {
         self.loop_stack.pop()
     }
 
-    pub fn current_loop(&self) -> Option<&LoopContext<'ctx>> {
-        self.loop_stack.last()
+    /// Position in `loop_stack` of the innermost enclosing loop.
+    pub fn current_loop(&self) -> Option<usize> {
+        self.loop_stack.len().checked_sub(1)
+    }
+
+    /// Position in `loop_stack` of the innermost enclosing loop carrying `label`.
+    pub fn labeled_loop(&self, label: &str) -> Option<usize> {
+        self.loop_stack
+            .iter()
+            .rposition(|loop_ctx| loop_ctx.label.as_deref() == Some(label))
     }
 }
 
@@ -35,7 +35,7 @@ fn driver_prefers_clang_style(driver: &str) -> bool {
     }
 }
 
-fn compiler_reports_clang(driver: &str) -> bool {
+pub(crate) fn compiler_reports_clang(driver: &str) -> bool {
     Command::new(driver)
         .arg("--version")
         .output()
@@ -0,0 +1,187 @@
+use otterc_span::Span;
+use otterc_utils::errors::{Diagnostic, DiagnosticSeverity};
+use thiserror::Error;
+
+/// A code generation failure, located at the expression or statement that
+/// caused it when that is known.
+///
+/// Lowering functions return `anyhow::Result`; a `CodegenError` travels inside
+/// the `anyhow::Error` and is recovered with `downcast_ref`. Errors that are
+/// not raised as a `CodegenError` are wrapped as [`CodegenError::Other`] by
+/// the innermost expression or statement that has a span. The function being
+/// lowered is recorded too, so callers that link several source files into
+/// one program can tell which file the span points into.
+#[derive(Debug, Clone, Error)]
+pub enum CodegenError {
+    #[error("unknown identifier `{name}`")]
+    UnknownIdentifier {
+        name: String,
+        span: Option<Span>,
+        function: Option<String>,
+    },
+    #[error("{message}")]
+    TypeMismatch {
+        message: String,
+        span: Option<Span>,
+        function: Option<String>,
+    },
+    #[error("{message}")]
+    UnsupportedExpr {
+        message: String,
+        span: Option<Span>,
+        function: Option<String>,
+    },
+    #[error("{message}")]
+    Other {
+        message: String,
+        span: Option<Span>,
+        function: Option<String>,
+    },
+}
+
+impl CodegenError {
+    pub fn unknown_identifier(name: impl Into<String>) -> Self {
+        CodegenError::UnknownIdentifier {
+            name: name.into(),
+            span: None,
+            function: None,
+        }
+    }
+
+    pub fn type_mismatch(message: impl Into<String>) -> Self {
+        CodegenError::TypeMismatch {
+            message: message.into(),
+            span: None,
+            function: None,
+        }
+    }
+
+    pub fn unsupported_expr(message: impl Into<String>) -> Self {
+        CodegenError::UnsupportedExpr {
+            message: message.into(),
+            span: None,
+            function: None,
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CodegenError::UnknownIdentifier { span, .. }
+            | CodegenError::TypeMismatch { span, .. }
+            | CodegenError::UnsupportedExpr { span, .. }
+            | CodegenError::Other { span, .. } => *span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Option<Span> {
+        match self {
+            CodegenError::UnknownIdentifier { span, .. }
+            | CodegenError::TypeMismatch { span, .. }
+            | CodegenError::UnsupportedExpr { span, .. }
+            | CodegenError::Other { span, .. } => span,
+        }
+    }
+
+    /// Locate `err` at `span` unless it already carries a span from a more
+    /// specific node.
+    pub fn locate(err: anyhow::Error, span: Span) -> anyhow::Error {
+        let mut located = match err.downcast::<CodegenError>() {
+            Ok(codegen) => codegen,
+            Err(other) => CodegenError::Other {
+                message: format!("{other:#}"),
+                span: None,
+                function: None,
+            },
+        };
+        located.span_mut().get_or_insert(span);
+        located.into()
+    }
+
+    /// Records that `err` arose while lowering the function `name`.
+    pub fn in_function(err: anyhow::Error, name: &str) -> anyhow::Error {
+        let mut codegen = match err.downcast::<CodegenError>() {
+            Ok(codegen) => codegen,
+            Err(other) => CodegenError::Other {
+                message: format!("{other:#}"),
+                span: None,
+                function: None,
+            },
+        };
+        codegen
+            .function_mut()
+            .get_or_insert_with(|| name.to_string());
+        codegen.into()
+    }
+
+    /// Name of the function whose lowering failed, when known
+    pub fn function(&self) -> Option<&str> {
+        match self {
+            CodegenError::UnknownIdentifier { function, .. }
+            | CodegenError::TypeMismatch { function, .. }
+            | CodegenError::UnsupportedExpr { function, .. }
+            | CodegenError::Other { function, .. } => function.as_deref(),
+        }
+    }
+
+    fn function_mut(&mut self) -> &mut Option<String> {
+        match self {
+            CodegenError::UnknownIdentifier { function, .. }
+            | CodegenError::TypeMismatch { function, .. }
+            | CodegenError::UnsupportedExpr { function, .. }
+            | CodegenError::Other { function, .. } => function,
+        }
+    }
+
+    /// The diagnostic for this error. Without a span it points at the start
+    /// of the file.
+    pub fn to_diagnostic(&self, source_id: &str) -> Diagnostic {
+        let span = self.span().unwrap_or_else(|| Span::new(0, 0));
+        let diag = Diagnostic::new(DiagnosticSeverity::Error, source_id, span, self.to_string());
+        match self {
+            CodegenError::UnknownIdentifier { .. } => {
+                diag.with_help("The name is not bound in this scope when code is generated.")
+            }
+            CodegenError::UnsupportedExpr { .. } => {
+                diag.with_help("This construct is not supported by the LLVM backend yet.")
+            }
+            CodegenError::TypeMismatch { .. } | CodegenError::Other { .. } => diag,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn innermost_span_wins() {
+        let err: anyhow::Error = CodegenError::type_mismatch("bad operands").into();
+        let err = CodegenError::locate(err, Span::new(4, 9));
+        let err = CodegenError::locate(err, Span::new(0, 20));
+
+        let codegen = err.downcast_ref::<CodegenError>().unwrap();
+        assert!(matches!(codegen, CodegenError::TypeMismatch { .. }));
+        assert_eq!(codegen.span(), Some(Span::new(4, 9)));
+        assert_eq!(codegen.to_diagnostic("main").span(), Span::new(4, 9));
+    }
+
+    #[test]
+    fn plain_errors_become_located() {
+        let err = CodegenError::locate(anyhow::anyhow!("no insert block"), Span::new(2, 3));
+        let codegen = err.downcast_ref::<CodegenError>().unwrap();
+        assert!(matches!(codegen, CodegenError::Other { .. }));
+        assert_eq!(codegen.to_string(), "no insert block");
+        assert_eq!(codegen.span(), Some(Span::new(2, 3)));
+    }
+
+    #[test]
+    fn innermost_function_wins() {
+        let err = CodegenError::locate(anyhow::anyhow!("bad call"), Span::new(2, 3));
+        let err = CodegenError::in_function(err, "helpers.shout");
+        let err = CodegenError::in_function(err, "main");
+
+        let codegen = err.downcast_ref::<CodegenError>().unwrap();
+        assert_eq!(codegen.function(), Some("helpers.shout"));
+        assert_eq!(codegen.span(), Some(Span::new(2, 3)));
+    }
+}
@@ -2,6 +2,8 @@ pub mod bridges;
 pub mod build;
 pub mod compiler;
 pub mod config;
+pub mod error;
 
 pub use build::{build_executable, build_shared_library, current_llvm_version};
 pub use config::BuildArtifact;
+pub use error::CodegenError;
@@ -4,6 +4,7 @@
 #include <stdint.h>
 #include <stdbool.h>
 #include <ctype.h>
+#include <math.h>
 #ifndef _WIN32
 #include <sys/time.h>
 #include <sys/types.h>
@@ -192,22 +193,219 @@ int64_t otter_std_time_now_ms() {
     return (int64_t)tv.tv_sec * 1000 + tv.tv_usec / 1000;
 }
 
-char* otter_format_float(double value) {
-    char* buffer = (char*)malloc(64);
-    if (buffer) {
-        int len = snprintf(buffer, 64, "%.9f", value);
-        if (len > 0) {
-            char* p = buffer + len - 1;
-            while (p > buffer && *p == '0') {
-                *p = '\0';
-                p--;
+// Fixed-width unsigned integers wide enough for the scaled values in
+// otter_shortest_digits, which need up to about 1130 bits for a double
+#define OTTER_BIG_WORDS 40
+
+typedef struct {
+    uint32_t words[OTTER_BIG_WORDS];
+} otter_big;
+
+static void otter_big_set(otter_big* a, uint64_t value) {
+    memset(a->words, 0, sizeof a->words);
+    a->words[0] = (uint32_t)value;
+    a->words[1] = (uint32_t)(value >> 32);
+}
+
+static void otter_big_shl(otter_big* a, int bits) {
+    int shift_words = bits / 32;
+    int shift_bits = bits % 32;
+    for (int i = OTTER_BIG_WORDS - 1; i >= 0; --i) {
+        uint32_t word = 0;
+        int src = i - shift_words;
+        if (src >= 0) {
+            word = a->words[src] << shift_bits;
+            if (shift_bits != 0 && src > 0) {
+                word |= a->words[src - 1] >> (32 - shift_bits);
             }
-            if (p > buffer && *p == '.') *p = '\0';
         }
+        a->words[i] = word;
+    }
+}
+
+static void otter_big_mul_small(otter_big* a, uint32_t factor) {
+    uint64_t carry = 0;
+    for (int i = 0; i < OTTER_BIG_WORDS; ++i) {
+        uint64_t product = (uint64_t)a->words[i] * factor + carry;
+        a->words[i] = (uint32_t)product;
+        carry = product >> 32;
+    }
+}
+
+static void otter_big_add(otter_big* out, const otter_big* a, const otter_big* b) {
+    uint64_t carry = 0;
+    for (int i = 0; i < OTTER_BIG_WORDS; ++i) {
+        uint64_t sum = (uint64_t)a->words[i] + b->words[i] + carry;
+        out->words[i] = (uint32_t)sum;
+        carry = sum >> 32;
+    }
+}
+
+static void otter_big_sub(otter_big* a, const otter_big* b) {
+    int64_t borrow = 0;
+    for (int i = 0; i < OTTER_BIG_WORDS; ++i) {
+        int64_t diff = (int64_t)a->words[i] - b->words[i] - borrow;
+        borrow = diff < 0;
+        a->words[i] = (uint32_t)(diff + (borrow << 32));
+    }
+}
+
+static int otter_big_cmp(const otter_big* a, const otter_big* b) {
+    for (int i = OTTER_BIG_WORDS - 1; i >= 0; --i) {
+        if (a->words[i] != b->words[i]) return a->words[i] < b->words[i] ? -1 : 1;
+    }
+    return 0;
+}
+
+// Shortest digits that read back as `value` (finite, > 0), so that
+// value ~ d.ddd x 10^exp. This is the free-format algorithm of Burger and
+// Dybvig, rounding the same way as Rust's `{}` for f64.
+static int otter_shortest_digits(double value, char* digits, int* exp) {
+    uint64_t bits;
+    memcpy(&bits, &value, sizeof bits);
+    int biased = (int)((bits >> 52) & 0x7ff);
+    uint64_t mantissa = bits & ((1ULL << 52) - 1);
+    int e = -1074;
+    if (biased != 0) {
+        mantissa |= 1ULL << 52;
+        e = biased - 1075;
+    }
+    // Boundaries halfway to the neighbours read back as `value` when the
+    // mantissa is even, and the gap below a power of two is half as wide
+    bool inclusive = (mantissa & 1) == 0;
+    int unequal = biased > 1 && mantissa == (1ULL << 52);
+
+    otter_big r, s, plus, minus, high;
+    otter_big_set(&r, mantissa);
+    if (e >= 0) {
+        otter_big_shl(&r, e + 1 + unequal);
+        otter_big_set(&s, unequal ? 4 : 2);
+        otter_big_set(&plus, 1);
+        otter_big_shl(&plus, e + unequal);
+        otter_big_set(&minus, 1);
+        otter_big_shl(&minus, e);
+    } else {
+        otter_big_shl(&r, 1 + unequal);
+        otter_big_set(&s, 1);
+        otter_big_shl(&s, -e + 1 + unequal);
+        otter_big_set(&plus, unequal ? 2 : 1);
+        otter_big_set(&minus, 1);
+    }
+
+    // Scale so that (r + plus) / s lies in [0.1, 1)
+    int k = 0;
+    for (;;) {
+        otter_big_add(&high, &r, &plus);
+        int c = otter_big_cmp(&high, &s);
+        if (!(inclusive ? c >= 0 : c > 0)) break;
+        otter_big_mul_small(&s, 10);
+        k++;
     }
+    for (;;) {
+        otter_big_add(&high, &r, &plus);
+        otter_big_mul_small(&high, 10);
+        int c = otter_big_cmp(&high, &s);
+        if (!(inclusive ? c < 0 : c <= 0)) break;
+        otter_big_mul_small(&r, 10);
+        otter_big_mul_small(&plus, 10);
+        otter_big_mul_small(&minus, 10);
+        k--;
+    }
+
+    int count = 0;
+    for (;;) {
+        otter_big_mul_small(&r, 10);
+        otter_big_mul_small(&plus, 10);
+        otter_big_mul_small(&minus, 10);
+        int digit = 0;
+        while (otter_big_cmp(&r, &s) >= 0) {
+            otter_big_sub(&r, &s);
+            digit++;
+        }
+        int c_low = otter_big_cmp(&r, &minus);
+        bool down = inclusive ? c_low <= 0 : c_low < 0;
+        otter_big_add(&high, &r, &plus);
+        int c_high = otter_big_cmp(&high, &s);
+        bool up = inclusive ? c_high >= 0 : c_high > 0;
+        if (!down && !up) {
+            digits[count++] = (char)('0' + digit);
+            continue;
+        }
+        if (up && down) {
+            otter_big twice = r;
+            otter_big_mul_small(&twice, 2);
+            up = otter_big_cmp(&twice, &s) >= 0;
+        }
+        digits[count++] = (char)('0' + digit + (up ? 1 : 0));
+        break;
+    }
+
+    // Rounding up a 9 carries into the digits before it
+    for (int i = count - 1; i > 0 && digits[i] > '9'; --i) {
+        digits[i] = '0';
+        digits[i - 1]++;
+    }
+    if (digits[0] > '9') {
+        digits[0] = '1';
+        k++;
+    }
+    while (count > 1 && digits[count - 1] == '0') count--;
+    *exp = k - 1;
+    return count;
+}
+
+// Writes the digits d.ddd x 10^exp without an exponent, the way Rust's `{}`
+// prints an f64
+static char* otter_render_float(bool negative, const char* digits, int count, int exp) {
+    int magnitude = exp < 0 ? -exp : exp;
+    char* buffer = (char*)malloc((size_t)(count + magnitude) + 4);
+    if (!buffer) return NULL;
+    char* out = buffer;
+    if (negative) *out++ = '-';
+    if (exp < 0) {
+        *out++ = '0';
+        *out++ = '.';
+        for (int i = 1; i < magnitude; ++i) *out++ = '0';
+        memcpy(out, digits, (size_t)count);
+        out += count;
+    } else if (exp + 1 >= count) {
+        memcpy(out, digits, (size_t)count);
+        out += count;
+        for (int i = count; i <= exp; ++i) *out++ = '0';
+    } else {
+        memcpy(out, digits, (size_t)exp + 1);
+        out += exp + 1;
+        *out++ = '.';
+        memcpy(out, digits + exp + 1, (size_t)(count - exp - 1));
+        out += count - exp - 1;
+    }
+    *out = '\0';
     return buffer;
 }
 
+// Shortest string that reads back as the same double, as the Rust runtime
+// prints floats: 3.0 becomes "3" and 0.1 + 0.2 "0.30000000000000004"
+static char* otter_float_to_string(double value) {
+    if (isnan(value) || isinf(value)) {
+        const char* text = isnan(value) ? "NaN" : (value > 0 ? "inf" : "-inf");
+        size_t len = strlen(text);
+        char* buffer = (char*)malloc(len + 1);
+        if (buffer) memcpy(buffer, text, len + 1);
+        return buffer;
+    }
+    bool negative = signbit(value);
+    if (negative) value = -value;
+    char digits[20] = { '0' };
+    int count = 1;
+    int exp = 0;
+    if (value != 0.0) count = otter_shortest_digits(value, digits, &exp);
+    return otter_render_float(negative, digits, count, exp);
+}
+
+char* otter_format_float(double value) {
+    return otter_float_to_string(value);
+}
+
 char* otter_format_int(int64_t value) {
     char* buffer = (char*)malloc(32);
     if (buffer) snprintf(buffer, 32, "%lld", (long long)value);
@@ -370,19 +568,7 @@ char* otter_builtin_stringify_int(int64_t value) {
 }
 
 char* otter_builtin_stringify_float(double value) {
-    char* buffer = (char*)malloc(64);
-    if (buffer) {
-        int len = snprintf(buffer, 64, "%.9f", value);
-        if (len > 0) {
-            char* p = buffer + len - 1;
-            while (p > buffer && *p == '0') {
-                *p = '0';
-                p--;
-            }
-            if (p > buffer && *p == '.') *p = '0';
-        }
-    }
-    return buffer;
+    return otter_float_to_string(value);
 }
 
 char* otter_builtin_stringify_bool(int value) {
@@ -431,19 +617,7 @@ void otter_std_fmt_eprintln(const char* msg) {
 }
 
 char* otter_std_fmt_stringify_float(double value) {
-    char* buffer = (char*)malloc(64);
-    if (buffer) {
-        int len = snprintf(buffer, 64, "%.9f", value);
-        if (len > 0) {
-            char* p = buffer + len - 1;
-            while (p > buffer && *p == '0') {
-                *p = '0';
-                p--;
-            }
-            if (p > buffer && *p == '.') *p = '0';
-        }
-    }
-    return buffer;
+    return otter_float_to_string(value);
 }
 
 char* otter_std_fmt_stringify_int(int64_t value) {
@@ -62,6 +62,11 @@ pub struct CodegenOptions {
     pub inline_threshold: Option<u32>,
     /// Target triple for cross-compilation (defaults to native)
     pub target: Option<TargetTriple>,
+    /// Check indices against the collection length before indexing
+    pub bounds_checks: bool,
+    /// Infer the types of unannotated parameters from their uses instead of
+    /// rejecting them
+    pub infer_param_types: bool,
 }
 
 impl Default for CodegenOptions {
@@ -74,6 +79,47 @@ impl Default for CodegenOptions {
             pgo_profile_file: None,
             inline_threshold: None,
             target: None,
+            bounds_checks: true,
+            infer_param_types: false,
+        }
+    }
+}
+
+/// New pass-manager pipeline run for an optimization level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassPipeline {
+    /// Pipeline text in `opt -passes=` syntax
+    pub passes: &'static str,
+    pub loop_interleaving: bool,
+    pub loop_vectorization: bool,
+    pub slp_vectorization: bool,
+    pub loop_unrolling: bool,
+    pub merge_functions: bool,
+}
+
+impl CodegenOptLevel {
+    /// Passes run over the lowered module, or `None` to leave it as emitted.
+    /// `Aggressive` adds another inlining round and full loop unrolling on top
+    /// of the O3 pipeline.
+    pub fn pass_pipeline(self) -> Option<PassPipeline> {
+        match self {
+            CodegenOptLevel::None => None,
+            CodegenOptLevel::Default => Some(PassPipeline {
+                passes: "default<O2>",
+                loop_interleaving: true,
+                loop_vectorization: true,
+                slp_vectorization: false,
+                loop_unrolling: false,
+                merge_functions: false,
+            }),
+            CodegenOptLevel::Aggressive => Some(PassPipeline {
+                passes: "default<O3>,cgscc(inline),function(loop-unroll<O3>,instcombine,simplifycfg)",
+                loop_interleaving: true,
+                loop_vectorization: true,
+                slp_vectorization: true,
+                loop_unrolling: true,
+                merge_functions: true,
+            }),
         }
     }
 }
@@ -87,3 +133,23 @@ impl From<CodegenOptLevel> for OptimizationLevel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pass_pipeline_per_level() {
+        assert_eq!(CodegenOptLevel::None.pass_pipeline(), None);
+
+        let default = CodegenOptLevel::Default.pass_pipeline().unwrap();
+        let aggressive = CodegenOptLevel::Aggressive.pass_pipeline().unwrap();
+        assert_eq!(default.passes, "default<O2>");
+        assert!(!default.loop_unrolling);
+
+        assert!(aggressive.passes.starts_with("default<O3>"));
+        assert!(aggressive.passes.contains("cgscc(inline)"));
+        assert!(aggressive.passes.contains("loop-unroll"));
+        assert!(aggressive.loop_unrolling);
+    }
+}
@@ -179,6 +179,18 @@ impl FunctionEntry {
             }
         };
 
+        if let Some(index) = call.max_placeholder()
+            && index >= params.len()
+        {
+            bail!(
+                "call.expr for {}:{} references `{{{}}}` but the function only takes {} parameter(s)",
+                dependency.name,
+                self.name,
+                index,
+                params.len()
+            );
+        }
+
         Ok(FunctionSpec {
             name: self.name,
             symbol,
@@ -249,3 +261,23 @@ fn resolve_dependency_path(base_dir: &Path, path: String) -> PathBuf {
 pub fn load_bridge_functions(crate_name: &str) -> Result<Vec<FunctionSpec>> {
     Ok(load_bridge_metadata(crate_name)?.functions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(yaml: &str) -> FunctionEntry {
+        serde_yaml::from_str(yaml).expect("valid function entry")
+    }
+
+    #[test]
+    fn expr_placeholder_past_last_param_is_rejected() {
+        let dependency = DependencyConfig::from_raw("demo", Path::new("."), None);
+        let entry = entry(
+            "name: demo.wrap\nparams: [i64, i64]\nresult: i64\ncall:\n  kind: expr\n  expr: \"{0}.add({5})\"\n",
+        );
+
+        let err = entry.try_into_spec(&dependency).unwrap_err().to_string();
+        assert!(err.contains("`{5}`"), "unexpected error: {err}");
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt::Write as _;
 
 use super::types::{
     CallTemplate, CrateSpec, DependencyConfig, FunctionSpec, PublicItem, RustTypeRef, StubSource,
-    TypeSpec,
+    TemplatePiece, TypeSpec, expr_template_pieces,
 };
 
 enum ArgContext<'a> {
@@ -670,10 +670,18 @@ impl RustStubGenerator {
     }
 
     fn render_expr_invocation(&self, expr: &str, call_args: &[String]) -> String {
-        let mut out = expr.to_string();
-        for (i, arg) in call_args.iter().enumerate() {
-            let ph = format!("{{{}}}", i);
-            out = out.replace(&ph, arg);
+        // Substitute in a single pass so argument text containing `{N}` is never re-expanded.
+        let mut out = String::with_capacity(expr.len());
+        for piece in expr_template_pieces(expr) {
+            match piece {
+                TemplatePiece::Text(text) => out.push_str(text),
+                TemplatePiece::Arg(index) => match call_args.get(index) {
+                    Some(arg) => out.push_str(arg),
+                    None => {
+                        let _ = write!(out, "{{{}}}", index);
+                    }
+                },
+            }
         }
         out
     }
@@ -6,9 +6,70 @@ use std::path::PathBuf;
 pub enum CallTemplate {
     Direct,
     Result,
+    /// A custom Rust expression. `{0}`, `{1}`, … are replaced with the stub's
+    /// converted arguments in parameter order; any other brace is copied verbatim.
     Expr(String),
 }
 
+impl CallTemplate {
+    /// Returns the highest argument placeholder index referenced by an `Expr` template.
+    pub fn max_placeholder(&self) -> Option<usize> {
+        match self {
+            CallTemplate::Expr(expr) => expr_template_pieces(expr)
+                .into_iter()
+                .filter_map(|piece| match piece {
+                    TemplatePiece::Arg(index) => Some(index),
+                    TemplatePiece::Text(_) => None,
+                })
+                .max(),
+            CallTemplate::Direct | CallTemplate::Result => None,
+        }
+    }
+}
+
+/// A segment of an `Expr` call template.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TemplatePiece<'a> {
+    Text(&'a str),
+    Arg(usize),
+}
+
+/// Splits an `Expr` call template into literal text and `{N}` argument placeholders.
+pub(crate) fn expr_template_pieces(expr: &str) -> Vec<TemplatePiece<'_>> {
+    let bytes = expr.as_bytes();
+    let mut pieces = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'{' {
+            let digits = bytes[i + 1..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            let close = i + 1 + digits;
+            if digits > 0
+                && bytes.get(close) == Some(&b'}')
+                && let Ok(index) = expr[i + 1..close].parse()
+            {
+                if text_start < i {
+                    pieces.push(TemplatePiece::Text(&expr[text_start..i]));
+                }
+                pieces.push(TemplatePiece::Arg(index));
+                i = close + 1;
+                text_start = i;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    if text_start < expr.len() {
+        pieces.push(TemplatePiece::Text(&expr[text_start..]));
+    }
+    pieces
+}
+
 /// Describes a single extern "C" function to be exposed through the bridge.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FunctionSpec {
@@ -128,9 +189,11 @@ impl DependencyConfig {
     }
 
     /// Compute a deterministic hash for caching based on crate name, version, path, and features.
+    ///
+    /// The key is a hex-encoded SHA-256 digest, so it can be used directly as a directory name.
     pub fn cache_hash(&self) -> String {
-        use sha1::{Digest, Sha1};
-        let mut hasher = Sha1::new();
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
         hasher.update(self.name.as_bytes());
         if let Some(version) = &self.version {
             hasher.update(b"version:");
@@ -433,3 +496,52 @@ impl RustTypeRef {
         matches!(self, RustTypeRef::Unit)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(features: &[&str]) -> DependencyConfig {
+        DependencyConfig {
+            name: "serde".to_string(),
+            version: Some("1.0".to_string()),
+            path: None,
+            features: features.iter().map(|f| f.to_string()).collect(),
+            default_features: true,
+        }
+    }
+
+    #[test]
+    fn expr_template_splits_placeholders() {
+        let pieces = expr_template_pieces("{0}.method({1}, { x })");
+        assert_eq!(
+            pieces,
+            vec![
+                TemplatePiece::Arg(0),
+                TemplatePiece::Text(".method("),
+                TemplatePiece::Arg(1),
+                TemplatePiece::Text(", { x })"),
+            ]
+        );
+        assert_eq!(
+            CallTemplate::Expr("{0}.method({1})".to_string()).max_placeholder(),
+            Some(1)
+        );
+        assert_eq!(CallTemplate::Direct.max_placeholder(), None);
+    }
+
+    #[test]
+    fn cache_hash_is_hex_sha256() {
+        let hash = dependency(&["derive"]).cache_hash();
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn cache_hash_ignores_feature_order() {
+        let a = dependency(&["derive", "std"]).cache_hash();
+        let b = dependency(&["std", "derive"]).cache_hash();
+        assert_eq!(a, b);
+        assert_ne!(a, dependency(&["derive"]).cache_hash());
+    }
+}
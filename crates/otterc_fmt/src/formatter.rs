@@ -60,6 +60,15 @@ impl Formatter {
                     self.format_expr(expr, indent)
                 )
             }
+            Statement::CompoundAssignment { name, op, expr } => {
+                format!(
+                    "{}{} {}= {}\n",
+                    self.indent(indent),
+                    name,
+                    self.format_binary_op(op),
+                    self.format_expr(expr, indent)
+                )
+            }
             Statement::Function(f) => self.format_function(f, indent),
             Statement::If {
                 cond,
@@ -100,8 +109,14 @@ impl Formatter {
                     format!("{}return\n", self.indent(indent))
                 }
             }
-            Statement::Break => format!("{}break\n", self.indent(indent)),
-            Statement::Continue => format!("{}continue\n", self.indent(indent)),
+            Statement::Break(label) => match label {
+                Some(label) => format!("{}break {}\n", self.indent(indent), label.as_ref()),
+                None => format!("{}break\n", self.indent(indent)),
+            },
+            Statement::Continue(label) => match label {
+                Some(label) => format!("{}continue {}\n", self.indent(indent), label.as_ref()),
+                None => format!("{}continue\n", self.indent(indent)),
+            },
             Statement::Pass => format!("{}pass\n", self.indent(indent)),
             Statement::Expr(expr) => {
                 format!(
@@ -246,10 +261,11 @@ impl Formatter {
             .params
             .iter()
             .map(|p| {
+                let star = if p.as_ref().variadic { "*" } else { "" };
                 let base = if let Some(ref ty) = p.as_ref().ty {
-                    format!("{}: {}", p.as_ref().name, self.format_type(ty))
+                    format!("{}{}: {}", star, p.as_ref().name, self.format_type(ty))
                 } else {
-                    p.as_ref().name.as_ref().clone()
+                    format!("{}{}", star, p.as_ref().name)
                 };
                 if let Some(default) = &p.as_ref().default {
                     format!("{} = {}", base, self.format_expr(default, indent))
@@ -334,38 +350,53 @@ impl Formatter {
                     self.format_expr(expr, indent)
                 )
             }
-            Expr::Call { func, args } => {
-                let args_str = args
-                    .iter()
-                    .map(|arg| self.format_expr(arg, indent))
-                    .collect::<Vec<_>>()
-                    .join(", ");
+            Expr::Call { func, args, kwargs } => {
+                let args_str =
+                    args.iter()
+                        .map(|arg| self.format_expr(arg, indent))
+                        .chain(kwargs.iter().map(|(name, val)| {
+                            format!("{}={}", name, self.format_expr(val, indent))
+                        }))
+                        .collect::<Vec<_>>()
+                        .join(", ");
                 format!("{}({})", self.format_expr(func, indent), args_str)
             }
             Expr::Member { object, field } => {
                 format!("{}.{}", self.format_expr(object, indent), field)
             }
+            Expr::Index { base, index } => {
+                format!(
+                    "{}[{}]",
+                    self.format_expr(base, indent),
+                    self.format_expr(index, indent)
+                )
+            }
             Expr::If {
                 cond,
                 then_branch,
                 else_branch,
             } => {
                 let else_str = if let Some(else_expr) = else_branch {
-                    format!(" else {}", self.format_expr(else_expr, indent))
+                    format!(" else: {}", self.format_expr(else_expr, indent))
                 } else {
                     String::new()
                 };
                 format!(
-                    "{} if {} else {}",
-                    self.format_expr(then_branch, indent),
+                    "if {}: {}{}",
                     self.format_expr(cond, indent),
+                    self.format_expr(then_branch, indent),
                     else_str
                 )
             }
-            Expr::Range { start, end } => {
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+            } => {
                 format!(
-                    "{}..{}",
+                    "{}{}{}",
                     self.format_expr(start, indent),
+                    if *inclusive { "..=" } else { ".." },
                     self.format_expr(end, indent)
                 )
             }
@@ -517,6 +548,27 @@ impl Formatter {
                 };
                 format!("[{}{}]", patterns_str, rest_str)
             }
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let start_str = start
+                    .as_ref()
+                    .map(|lit| self.format_literal(lit))
+                    .unwrap_or_default();
+                let end_str = end
+                    .as_ref()
+                    .map(|lit| self.format_literal(lit))
+                    .unwrap_or_default();
+                let op = if *inclusive { "..=" } else { ".." };
+                format!("{}{}{}", start_str, op, end_str)
+            }
+            Pattern::Or(alternatives) => alternatives
+                .iter()
+                .map(|p| self.format_pattern(p))
+                .collect::<Vec<_>>()
+                .join(" | "),
         }
     }
 
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 
 /// Cached JIT-compiled function
 #[derive(Debug, Clone)]
@@ -37,6 +38,9 @@ impl CachedFunction {
 /// Function cache
 pub struct FunctionCache {
     functions: HashMap<String, CachedFunction>,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
 }
 
 impl Default for FunctionCache {
@@ -49,6 +53,9 @@ impl FunctionCache {
     pub fn new() -> Self {
         Self {
             functions: HashMap::new(),
+            hits: 0,
+            misses: 0,
+            evictions: 0,
         }
     }
 
@@ -56,18 +63,37 @@ impl FunctionCache {
         Self::new()
     }
 
-    pub fn get(&self, name: &str) -> Option<&CachedFunction> {
-        self.functions.get(name)
+    /// Look up a function, counting a hit or a miss
+    pub fn get(&mut self, name: &str) -> Option<&CachedFunction> {
+        match self.functions.get_mut(name) {
+            Some(function) => {
+                self.hits += 1;
+                function.touch();
+                Some(function)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
     }
 
+    /// Insert a function, replacing any previous entry with the same name.
+    /// Replacing recompiled code is not an eviction.
     pub fn put(&mut self, function: CachedFunction) {
         self.functions.insert(function.name.clone(), function);
     }
 
     pub fn remove(&mut self, name: &str) -> Option<CachedFunction> {
-        self.functions.remove(name)
+        let removed = self.functions.remove(name);
+        if removed.is_some() {
+            self.evictions += 1;
+        }
+        removed
     }
 
+    /// Drop every entry, as when the program is recompiled. Only entries
+    /// dropped one at a time with `remove` count as evictions.
     pub fn clear(&mut self) {
         self.functions.clear();
     }
@@ -76,6 +102,9 @@ impl FunctionCache {
         CacheStats {
             total_functions: self.functions.len(),
             total_size: self.functions.values().map(|f| f.size).sum(),
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
         }
     }
 }
@@ -85,4 +114,46 @@ impl FunctionCache {
 pub struct CacheStats {
     pub total_functions: usize,
     pub total_size: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cache: {} function(s), {} bytes, {} hit(s), {} miss(es), {} eviction(s)",
+            self.total_functions, self.total_size, self.hits, self.misses, self.evictions
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(name: &str) -> CachedFunction {
+        CachedFunction::new(name.to_string(), 0x1000, 16)
+    }
+
+    #[test]
+    fn test_stats_count_lookups_and_removals() {
+        let mut cache = FunctionCache::new();
+        cache.put(function("a"));
+        cache.put(function("b"));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_none());
+
+        // Recompiled code replaces its entry without evicting it
+        cache.put(function("a"));
+        assert!(cache.remove("b").is_some());
+        assert!(cache.remove("b").is_none());
+        cache.clear();
+
+        let stats = cache.stats();
+        assert_eq!(stats.total_functions, 0);
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 1, 1));
+    }
 }
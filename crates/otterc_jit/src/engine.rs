@@ -1,22 +1,24 @@
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use inkwell::context::Context as LlvmContext;
 use libloading::{Library, Symbol};
 use std::collections::HashMap;
-use std::ffi::CString;
+use std::ffi::{CString, c_char};
 use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
 
-use otterc_ast::nodes::{Program, Statement};
+use otterc_ast::nodes::{Program, Statement, Type};
 use otterc_codegen::build_shared_library;
-use otterc_config::{CodegenOptLevel, CodegenOptions};
-use otterc_metrics::profiler::{FunctionMetrics, GlobalProfiler, HotFunction};
+use otterc_config::{CodegenOptions, CompilationTier, TieredConfig};
+use otterc_metrics::profiler::{FunctionMetrics, GlobalProfiler};
 use otterc_symbol::registry::SymbolRegistry;
 use otterc_typecheck::TypeChecker;
 
 use super::adaptive::{AdaptiveConcurrencyManager, AdaptiveMemoryManager};
 use super::cache::FunctionCache;
+use super::cache::function_cache::CachedFunction;
 use super::optimization::{CallGraph, Inliner, Reoptimizer};
 use super::specialization::{Specializer, TypeTracker};
+use super::tiered_compiler::TieredCompiler;
 
 /// Function pointer type for different signatures
 pub enum FunctionPtr {
@@ -27,6 +29,18 @@ pub enum FunctionPtr {
     VarArgs(unsafe extern "C" fn(*const u64, usize) -> u64),
 }
 
+impl FunctionPtr {
+    fn address(&self) -> usize {
+        match self {
+            FunctionPtr::NoArgs(f) => *f as usize,
+            FunctionPtr::OneArg(f) => *f as usize,
+            FunctionPtr::TwoArgs(f) => *f as usize,
+            FunctionPtr::ThreeArgs(f) => *f as usize,
+            FunctionPtr::VarArgs(f) => *f as usize,
+        }
+    }
+}
+
 /// Compiled function with metadata
 struct CompiledFunction {
     library: Arc<Library>,
@@ -82,6 +96,7 @@ pub struct JitEngine {
     #[expect(dead_code, reason = "Work in progress")]
     memory_manager: AdaptiveMemoryManager,
     concurrency_manager: AdaptiveConcurrencyManager,
+    tiered_compiler: TieredCompiler,
     #[expect(dead_code, reason = "Work in progress")]
     symbol_registry: &'static SymbolRegistry,
     // Runtime state
@@ -90,6 +105,8 @@ pub struct JitEngine {
     temp_dir: TempDir,
     program: Option<Program>,
     library_path: Arc<Mutex<Option<std::path::PathBuf>>>,
+    /// Infer untyped parameters (see `CodegenOptions::infer_param_types`)
+    infer_param_types: bool,
 }
 
 impl JitEngine {
@@ -111,12 +128,14 @@ impl JitEngine {
             reoptimizer: Reoptimizer::new(),
             memory_manager: AdaptiveMemoryManager::new(),
             concurrency_manager: AdaptiveConcurrencyManager::new(),
+            tiered_compiler: TieredCompiler::with_config(TieredConfig::from_env()),
             symbol_registry,
             compiled_library: Arc::new(Mutex::new(None)),
             compiled_functions: Arc::new(Mutex::new(HashMap::new())),
             temp_dir,
             program: None,
             library_path: Arc::new(Mutex::new(None)),
+            infer_param_types: false,
         })
     }
 
@@ -139,11 +158,13 @@ impl JitEngine {
         let options = CodegenOptions {
             target: None,
             emit_ir: false,
-            opt_level: CodegenOptLevel::Default,
+            opt_level: CompilationTier::Optimized.to_opt_level(),
             enable_lto: false,
             enable_pgo: false,
             pgo_profile_file: None,
             inline_threshold: None,
+            bounds_checks: true,
+            infer_param_types: self.infer_param_types,
         };
 
         let mut type_checker = TypeChecker::new().with_registry(SymbolRegistry::global());
@@ -183,7 +204,10 @@ impl JitEngine {
         *self.library_path.lock().unwrap() = Some(lib_path);
 
         // Extract function symbols from the program
+        self.function_cache.clear();
         self.load_functions(program)?;
+        let names = self.get_function_names();
+        self.cache_functions(&names);
 
         Ok(())
     }
@@ -208,6 +232,8 @@ impl JitEngine {
 
                 // Try to load function with different signatures
                 let func_ptr = self.load_function_symbol(&library, func_name, arg_count)?;
+                self.tiered_compiler
+                    .register_function(func_name, CompilationTier::Optimized);
 
                 functions.insert(
                     func_name.clone(),
@@ -230,8 +256,10 @@ impl JitEngine {
         name: &str,
         arg_count: usize,
     ) -> Result<FunctionPtr> {
+        // `main` is emitted as `otter_entry` so it does not clash with the C entry point
+        let symbol = if name == "main" { "otter_entry" } else { name };
         let name_cstr =
-            CString::new(name).map_err(|e| anyhow!("Invalid function name '{}': {}", name, e))?;
+            CString::new(symbol).map_err(|e| anyhow!("Invalid function name '{}': {}", name, e))?;
 
         // Try different function signatures based on argument count
         unsafe {
@@ -275,7 +303,11 @@ impl JitEngine {
     pub fn execute_function(&mut self, function_name: &str, args: &[u64]) -> Result<u64> {
         let start = std::time::Instant::now();
 
-        // Get compiled function
+        // Every compiled function is cached, so a miss means there is no
+        // code to run
+        if self.function_cache.get(function_name).is_none() {
+            bail!("Function '{}' not found or not compiled", function_name);
+        }
         let compiled_func = {
             let functions = self.compiled_functions.lock().unwrap();
             functions
@@ -291,46 +323,144 @@ impl JitEngine {
         let duration = start.elapsed();
         self.profiler.record_call(function_name, duration);
 
-        // Check for hot functions periodically
-        if self
-            .profiler
-            .get_metrics(function_name)
-            .map(|m| m.call_count % 1000 == 0)
-            .unwrap_or(false)
-        {
-            let hot_functions = self.profiler.check_hot_functions();
-            if !hot_functions.is_empty() {
-                self.optimize_hot_functions(&hot_functions)?;
-            }
+        // Recompile the function once it is hot enough for the next tier
+        if let Some(tier) = self.tiered_compiler.record_call(function_name) {
+            self.recompile_functions(&[function_name.to_string()], tier)?;
         }
 
         Ok(result)
     }
 
+    /// Execute `main`, passing `args` as a `list<str>` when it takes a
+    /// parameter, and return its exit code: the value `main` returns, or 0
+    /// when it returns nothing.
+    pub fn execute_main(&mut self, args: &[String]) -> Result<i32> {
+        let main = self
+            .program
+            .as_ref()
+            .and_then(|program| {
+                program
+                    .statements
+                    .iter()
+                    .find_map(|stmt| match stmt.as_ref() {
+                        Statement::Function(f) if f.as_ref().name == "main" => Some(f.as_ref()),
+                        _ => None,
+                    })
+            })
+            .ok_or_else(|| anyhow!("Program has no `main` function"))?;
+        let takes_args = match main.params.len() {
+            0 => false,
+            1 => true,
+            n => {
+                return Err(anyhow!(
+                    "`main` must take at most one parameter, found {}",
+                    n
+                ));
+            }
+        };
+        let returns_code = main.ret_ty.as_ref().is_some_and(
+            |ty| !matches!(ty.as_ref(), Type::Simple(name) if name == "void" || name == "unit"),
+        );
+
+        let call_args = if takes_args {
+            vec![self.build_args_list(args)?]
+        } else {
+            Vec::new()
+        };
+        let result = self.execute_function("main", &call_args)?;
+
+        Ok(if returns_code { result as i32 } else { 0 })
+    }
+
+    /// Build a runtime list handle holding `args`, using the list functions
+    /// of the loaded program.
+    fn build_args_list(&self, args: &[String]) -> Result<u64> {
+        let library = self
+            .compiled_library
+            .lock()
+            .unwrap()
+            .as_ref()
+            .ok_or_else(|| anyhow!("Library not loaded"))?
+            .clone();
+
+        unsafe {
+            let list_new: unsafe extern "C" fn() -> u64 =
+                runtime_symbol(&library, "otter_builtin_list_new")?;
+            let append: unsafe extern "C" fn(u64, *const c_char) -> i32 =
+                runtime_symbol(&library, "otter_builtin_append_list_string")?;
+
+            let handle = list_new();
+            for arg in args {
+                let arg = CString::new(arg.as_str())
+                    .map_err(|e| anyhow!("Invalid program argument '{}': {}", arg, e))?;
+                append(handle, arg.as_ptr());
+            }
+            Ok(handle)
+        }
+    }
+
     /// Force optimization of the provided functions by recompiling the module
     pub fn optimize_functions(&mut self, function_names: &[String]) -> Result<()> {
+        self.recompile_functions(function_names, CompilationTier::Aggressive)
+    }
+
+    /// Recompile the module at `tier` and swap in the new code for
+    /// `function_names`
+    fn recompile_functions(
+        &mut self,
+        function_names: &[String],
+        tier: CompilationTier,
+    ) -> Result<()> {
         if function_names.is_empty() {
             return Ok(());
         }
 
+        let start = std::time::Instant::now();
         let options = CodegenOptions {
             target: None,
             emit_ir: false,
-            opt_level: CodegenOptLevel::Aggressive,
-            enable_lto: true,
+            opt_level: tier.to_opt_level(),
+            enable_lto: tier == CompilationTier::Aggressive,
             enable_pgo: false,
             pgo_profile_file: None,
             inline_threshold: None,
+            bounds_checks: true,
+            infer_param_types: self.infer_param_types,
         };
 
-        let library = self.rebuild_library("jit_program_optimized", &options)?;
-        self.reload_named_functions(&library, function_names)
+        let file_stem = format!("jit_program_{}", tier.name().to_lowercase());
+        let library = self.rebuild_library(&file_stem, &options)?;
+        self.reload_named_functions(&library, function_names)?;
+        self.cache_functions(function_names);
+
+        let compilation_time_us = start.elapsed().as_micros() as u64;
+        for name in function_names {
+            self.tiered_compiler
+                .record_compilation(name, tier, compilation_time_us);
+        }
+        Ok(())
+    }
+
+    /// Infer the types of unannotated parameters from their uses, as the
+    /// `--infer` flag does for ahead-of-time builds. Applies to the next
+    /// compilation.
+    pub fn set_infer_param_types(&mut self, infer: bool) {
+        self.infer_param_types = infer;
     }
 
-    /// Optimize hot functions by recompiling with aggressive optimizations
-    fn optimize_hot_functions(&mut self, hot_functions: &[HotFunction]) -> Result<()> {
-        let function_names: Vec<String> = hot_functions.iter().map(|f| f.name.clone()).collect();
-        self.optimize_functions(&function_names)
+    /// Set the tiering policy, including the call count at which a function
+    /// is recompiled at `Aggressive` optimization
+    pub fn set_tiered_config(&mut self, config: TieredConfig) {
+        self.tiered_compiler.set_config(config);
+    }
+
+    /// Number of functions recompiled at a higher tier since they were loaded
+    pub fn retiered_function_count(&self) -> usize {
+        self.tiered_compiler
+            .get_all_function_info()
+            .values()
+            .filter(|info| info.recompilation_count > 0)
+            .count()
     }
 
     /// Get profiler statistics
@@ -394,6 +524,20 @@ impl JitEngine {
         Ok(library)
     }
 
+    /// Record the current code of `function_names` in the function cache
+    fn cache_functions(&mut self, function_names: &[String]) {
+        let functions = self.compiled_functions.lock().unwrap();
+        for name in function_names {
+            if let Some(compiled) = functions.get(name) {
+                self.function_cache.put(CachedFunction::new(
+                    name.clone(),
+                    compiled.function_ptr.address(),
+                    0,
+                ));
+            }
+        }
+    }
+
     fn reload_named_functions(
         &self,
         library: &Arc<Library>,
@@ -430,6 +574,30 @@ impl JitEngine {
     }
 }
 
+/// Look up a runtime function in the loaded program, falling back to the
+/// runtime linked into the current process.
+///
+/// # Safety
+///
+/// `T` must be a function pointer type matching the runtime function, and
+/// must not be called after `library` is unloaded.
+unsafe fn runtime_symbol<T: Copy>(library: &Library, name: &str) -> Result<T> {
+    let symbol = CString::new(name)?;
+    unsafe {
+        if let Ok(sym) = library.get::<T>(symbol.as_bytes()) {
+            return Ok(*sym);
+        }
+        #[cfg(unix)]
+        let this = Library::from(libloading::os::unix::Library::this());
+        #[cfg(windows)]
+        let this = Library::from(libloading::os::windows::Library::this()?);
+        let sym = this
+            .get::<T>(symbol.as_bytes())
+            .map_err(|e| anyhow!("Failed to load runtime function '{}': {}", name, e))?;
+        Ok(*sym)
+    }
+}
+
 impl Clone for CompiledFunction {
     fn clone(&self) -> Self {
         Self {
@@ -1,15 +1,14 @@
 use crate::engine::JitEngine;
 use anyhow::Result;
 use otterc_ast::nodes::Program;
+use otterc_config::TieredConfig;
 use otterc_metrics::profiler::FunctionMetrics;
 use otterc_symbol::registry::SymbolRegistry;
-use std::collections::HashMap;
+use std::fmt;
 
 /// Simplified JIT executor for running programs
 pub struct JitExecutor {
     engine: JitEngine,
-    hotness_counters: HashMap<String, usize>,
-    optimization_threshold: usize,
 }
 
 impl JitExecutor {
@@ -25,15 +24,22 @@ impl JitExecutor {
     pub fn new_with_backend(
         program: &Program,
         symbol_registry: &'static SymbolRegistry,
+    ) -> anyhow::Result<Self> {
+        Self::new_with_param_inference(program, symbol_registry, false)
+    }
+
+    /// Create a new JIT executor, inferring the types of unannotated
+    /// parameters when `infer_param_types` is set
+    pub fn new_with_param_inference(
+        program: &Program,
+        symbol_registry: &'static SymbolRegistry,
+        infer_param_types: bool,
     ) -> anyhow::Result<Self> {
         let mut engine = JitEngine::new_with_backend(symbol_registry)?;
+        engine.set_infer_param_types(infer_param_types);
         engine.compile_program(program)?;
 
-        Ok(Self {
-            engine,
-            hotness_counters: HashMap::new(),
-            optimization_threshold: 100, // Default threshold
-        })
+        Ok(Self { engine })
     }
 
     /// Recompile the current program without rebuilding the entire engine
@@ -41,35 +47,20 @@ impl JitExecutor {
         self.engine.compile_program(program)
     }
 
-    /// Execute the main function
-    pub fn execute_main(&mut self) -> Result<()> {
-        self.execute_with_profiling("main", &[])
+    /// Execute the main function with `args` and return its exit code
+    pub fn execute_main(&mut self, args: &[String]) -> Result<i32> {
+        self.engine.execute_main(args)
     }
 
-    /// Execute a function with profiling and hotness tracking
-    pub fn execute_with_profiling(&mut self, name: &str, args: &[u64]) -> Result<()> {
-        // Update hotness counter
-        let count = {
-            let counter = self.hotness_counters.entry(name.to_string()).or_insert(0);
-            *counter += 1;
-            *counter
-        };
-
-        // Check for optimization
-        if count >= self.optimization_threshold {
-            self.optimize_function(name)?;
-            // Reset counter after optimization to avoid repeated optimization
-            self.hotness_counters.insert(name.to_string(), 0);
-        }
-
-        // Execute
-        self.engine.execute_function(name, args)?;
-        Ok(())
+    /// Execute a function with profiling; hot functions are recompiled at a
+    /// higher tier by the engine
+    pub fn execute_with_profiling(&mut self, name: &str, args: &[u64]) -> Result<u64> {
+        self.engine.execute_function(name, args)
     }
 
-    /// Trigger optimization for a hot function
-    fn optimize_function(&mut self, name: &str) -> Result<()> {
-        self.engine.optimize_functions(&[name.to_string()])
+    /// Set the tiering policy used to recompile hot functions
+    pub fn set_tiered_config(&mut self, config: TieredConfig) {
+        self.engine.set_tiered_config(config);
     }
 
     /// Get performance statistics
@@ -77,6 +68,7 @@ impl JitExecutor {
         ExecutorStats {
             profiler_metrics: self.engine.get_profiler_stats(),
             cache_stats: self.engine.get_cache_stats(),
+            retiered_functions: self.engine.retiered_function_count(),
         }
     }
 }
@@ -85,4 +77,116 @@ impl JitExecutor {
 pub struct ExecutorStats {
     pub profiler_metrics: Vec<FunctionMetrics>,
     pub cache_stats: super::cache::function_cache::CacheStats,
+    /// Functions recompiled at a higher optimization tier
+    pub retiered_functions: usize,
+}
+
+/// One row per function, hottest first, followed by cache and tiering totals
+impl fmt::Display for ExecutorStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "JIT profile:")?;
+        if self.profiler_metrics.is_empty() {
+            writeln!(f, "  No functions executed yet")?;
+        } else {
+            let mut metrics: Vec<&FunctionMetrics> = self.profiler_metrics.iter().collect();
+            metrics.sort_by(|a, b| b.total_time.cmp(&a.total_time).then(a.name.cmp(&b.name)));
+            for metric in metrics {
+                writeln!(f, "  {metric}")?;
+            }
+        }
+        writeln!(f, "  {}", self.cache_stats)?;
+        write!(f, "  Re-tiered: {} function(s)", self.retiered_functions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheStats;
+    // Links the runtime so its functions are in the stdlib registry
+    use otterc_runtime as _;
+    use std::time::Duration;
+
+    fn metrics(name: &str, calls: u64, total_ms: u64) -> FunctionMetrics {
+        let mut metrics = FunctionMetrics::new(name.to_string());
+        for _ in 0..calls {
+            metrics.record_call(Duration::from_millis(total_ms / calls));
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_stats_report_lists_hottest_functions_first() {
+        let stats = ExecutorStats {
+            profiler_metrics: vec![metrics("helper", 2, 2), metrics("main", 1, 10)],
+            cache_stats: CacheStats {
+                total_functions: 2,
+                total_size: 0,
+                hits: 3,
+                misses: 1,
+                evictions: 0,
+            },
+            retiered_functions: 1,
+        };
+
+        let report = stats.to_string();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "JIT profile:");
+        assert!(lines[1].trim_start().starts_with("main"));
+        assert!(lines[2].trim_start().starts_with("helper"));
+        assert!(lines[2].contains("calls:      2"));
+        assert_eq!(
+            lines[3],
+            "  Cache: 2 function(s), 0 bytes, 3 hit(s), 1 miss(es), 0 eviction(s)"
+        );
+        assert_eq!(lines[4], "  Re-tiered: 1 function(s)");
+    }
+
+    fn parse(source: &str) -> Program {
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        otterc_parser::parse(&tokens).expect("parse")
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn test_untyped_parameters_need_param_inference() {
+        let program =
+            parse("fn twice(x) -> int:\n    return x * 2\n\nfn main():\n    let y = twice(21)\n");
+        let registry = otterc_ffi::bootstrap_stdlib();
+
+        let err = JitExecutor::new(&program, registry)
+            .err()
+            .expect("untyped parameter is rejected");
+        assert!(format!("{err:#}").contains("--infer"), "{err:#}");
+
+        let mut executor = JitExecutor::new_with_param_inference(&program, registry, true)
+            .expect("compiles with inference");
+        assert_eq!(executor.execute_main(&[]).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn test_cache_stats_count_calls_not_recompiles() {
+        let program = parse(
+            "fn add(a: int, b: int) -> int:\n    return a + b\n\nfn main():\n    let c = add(1, 2)\n",
+        );
+        let mut executor = JitExecutor::new(&program, otterc_ffi::bootstrap_stdlib()).unwrap();
+        executor.recompile(&program).unwrap();
+
+        assert_eq!(executor.execute_with_profiling("add", &[2, 3]).unwrap(), 5);
+        assert_eq!(executor.execute_with_profiling("add", &[4, 5]).unwrap(), 9);
+        assert!(executor.execute_with_profiling("missing", &[]).is_err());
+
+        let stats = executor.get_stats().cache_stats;
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 1, 0));
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn test_main_receives_arguments_and_sets_exit_code() {
+        let program = parse("fn main(args: list<str>) -> int:\n    return len(args) + 1\n");
+        let mut executor = JitExecutor::new(&program, otterc_ffi::bootstrap_stdlib()).unwrap();
+        let args = ["a".to_string(), "b".to_string()];
+        assert_eq!(executor.execute_main(&args).unwrap(), 3);
+    }
 }
@@ -397,11 +397,14 @@ impl Inliner {
         current_name: &str,
     ) {
         match expr.as_mut() {
-            Expr::Call { func, args } => {
+            Expr::Call { func, args, kwargs } => {
                 self.inline_expr(func, ctx, stack, stats, depth, current_hot, current_name);
                 for arg in args {
                     self.inline_expr(arg, ctx, stack, stats, depth, current_hot, current_name);
                 }
+                for (_, arg) in kwargs {
+                    self.inline_expr(arg, ctx, stack, stats, depth, current_hot, current_name);
+                }
             }
             Expr::Binary { left, right, .. } => {
                 self.inline_expr(left, ctx, stack, stats, depth, current_hot, current_name);
@@ -542,7 +545,9 @@ impl Inliner {
         current_hot: bool,
         current_name: &str,
     ) -> Option<InlineSnippet> {
-        if let Expr::Call { func, args } = expr.as_mut()
+        // Keyword calls are left alone; inlining binds arguments by position.
+        if let Expr::Call { func, args, kwargs } = expr.as_mut()
+            && kwargs.is_empty()
             && let Expr::Identifier(name) = func.as_ref().as_ref()
         {
             return self.try_inline_call(
@@ -583,7 +588,10 @@ impl Inliner {
             return None;
         };
 
-        if args.len() != callee.as_ref().params.len() {
+        // `*args` callees need their arguments packed into a list first.
+        if args.len() != callee.as_ref().params.len()
+            || callee.as_ref().params.iter().any(|p| p.as_ref().variadic)
+        {
             stats.skipped_complex += 1;
             return None;
         }
@@ -879,9 +887,13 @@ impl InlineBuilder {
                 op,
                 expr: Box::new(self.rewrite_expr(&expr)),
             },
-            Expr::Call { func, args } => Expr::Call {
+            Expr::Call { func, args, kwargs } => Expr::Call {
                 func: Box::new(self.rewrite_expr(&func)),
                 args: args.iter().map(|arg| self.rewrite_expr(arg)).collect(),
+                kwargs: kwargs
+                    .iter()
+                    .map(|(name, arg)| (name.clone(), self.rewrite_expr(arg)))
+                    .collect(),
             },
             Expr::If {
                 cond,
@@ -991,6 +1003,12 @@ impl InlineBuilder {
                     .collect(),
                 rest: rest.as_ref().map(|name| self.names.rename_local(name)),
             },
+            Pattern::Or(alternatives) => Pattern::Or(
+                alternatives
+                    .into_iter()
+                    .map(|pat| self.rewrite_pattern(&pat))
+                    .collect(),
+            ),
             Pattern::EnumVariant {
                 enum_name,
                 variant,
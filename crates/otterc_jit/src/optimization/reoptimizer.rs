@@ -205,11 +205,14 @@ impl Reoptimizer {
                 }
                 None
             }
-            Expr::Call { func, args } => {
+            Expr::Call { func, args, kwargs } => {
                 self.fold_constants_in_expr(func.as_mut().as_mut());
                 for arg in args {
                     self.fold_constants_in_expr(arg.as_mut());
                 }
+                for (_, arg) in kwargs {
+                    self.fold_constants_in_expr(arg.as_mut());
+                }
                 None
             }
             Expr::Array(values) => {
@@ -435,7 +438,7 @@ impl Reoptimizer {
             }
             terminated = matches!(
                 stmt.as_ref(),
-                Statement::Return(_) | Statement::Break | Statement::Continue
+                Statement::Return(_) | Statement::Break(_) | Statement::Continue(_)
             );
             pruned.push(stmt);
         }
@@ -195,6 +195,26 @@ mod tests {
         assert_eq!(compiler.get_tier("test_fn"), CompilationTier::Optimized);
     }
 
+    #[test]
+    fn test_hot_function_promoted_to_aggressive_at_threshold() {
+        let config = TieredConfig {
+            optimized_to_aggressive_threshold: 5,
+            recompilation_cooldown_ms: 0,
+            ..TieredConfig::default()
+        };
+        let compiler = TieredCompiler::with_config(config);
+        compiler.register_function("hot_fn", CompilationTier::Optimized);
+
+        for _ in 0..4 {
+            assert_eq!(compiler.record_call("hot_fn"), None);
+        }
+        assert_eq!(
+            compiler.record_call("hot_fn"),
+            Some(CompilationTier::Aggressive)
+        );
+        assert_eq!(compiler.record_call("hot_fn"), None);
+    }
+
     #[test]
     fn test_stats_tracking() {
         let compiler = TieredCompiler::new();
@@ -0,0 +1,68 @@
+//! Lexer throughput over the bundled example programs.
+//!
+//! Run with `cargo bench -p otterc_lexer --bench tokenize`. Criterion
+//! reports bytes/s and tokens/s per input; compare against a baseline saved
+//! with `--save-baseline main` when reviewing lexer changes. The target on
+//! a release build is at least 50 MB/s on the `large` input.
+
+#![expect(clippy::panic, reason = "A benchmark input that fails to lex is a bug")]
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use otterc_lexer::tokenize;
+
+const EXAMPLES: &[&str] = &[
+    include_str!("../../../examples/basic/advanced_pipeline.ot"),
+    include_str!("../../../examples/basic/enum_demo.ot"),
+    include_str!("../../../examples/basic/error_handling_advanced.ot"),
+    include_str!("../../../examples/basic/error_handling_basics.ot"),
+    include_str!("../../../examples/basic/error_handling_resource.ot"),
+    include_str!("../../../examples/basic/error_handling_validation.ot"),
+    include_str!("../../../examples/basic/fibonacci.ot"),
+    include_str!("../../../examples/basic/generic_struct_test.ot"),
+    include_str!("../../../examples/basic/hello.ot"),
+    include_str!("../../../examples/basic/http_request.ot"),
+    include_str!("../../../examples/basic/sqrt_approx.ot"),
+    include_str!("../../../examples/basic/struct_demo.ot"),
+    include_str!("../../../examples/basic/struct_methods_demo.ot"),
+    include_str!("../../../examples/basic/task_benchmark.ot"),
+    include_str!("../../../examples/basic/yaml_demo.ot"),
+];
+
+/// Times the large input repeats the whole example set, giving roughly 1 MB.
+const LARGE_REPEAT: usize = 64;
+
+fn inputs() -> Vec<(&'static str, String)> {
+    let all = EXAMPLES.join("\n");
+    vec![
+        (
+            "small",
+            include_str!("../../../examples/basic/fibonacci.ot").to_string(),
+        ),
+        ("medium", all.clone()),
+        ("large", vec![all; LARGE_REPEAT].join("\n")),
+    ]
+}
+
+fn bench_tokenize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenize");
+    for (name, source) in inputs() {
+        let tokens = tokenize(&source)
+            .unwrap_or_else(|errors| panic!("{name} input does not lex: {errors:?}"))
+            .len();
+
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(BenchmarkId::new("bytes", name), &source, |b, source| {
+            b.iter(|| tokenize(black_box(source)));
+        });
+        group.throughput(Throughput::Elements(tokens as u64));
+        group.bench_with_input(BenchmarkId::new("tokens", name), &source, |b, source| {
+            b.iter(|| tokenize(black_box(source)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_tokenize);
+criterion_main!(benches);
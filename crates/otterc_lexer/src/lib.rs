@@ -1,5 +1,5 @@
 pub mod token;
 pub mod tokenizer;
 
-pub use token::{Token, TokenKind};
-pub use tokenizer::{LexResult, LexerError, tokenize};
+pub use token::{FStringSegment, Token, TokenKind};
+pub use tokenizer::{LexResult, LexStats, Lexer, LexerError, tokenize};
@@ -42,7 +42,7 @@ pub enum TokenKind {
     // Literals
     Number(String),
     StringLiteral(String),
-    FString(String), // Raw f-string content like "π ≈ {result}"
+    FString(Vec<FStringSegment>),
     Bool(bool),
 
     // Structural
@@ -86,6 +86,11 @@ pub enum TokenKind {
     // Range operator
     DoubleDot,
 
+    /// A `#` comment up to the end of its line, `#` included. Only the
+    /// [`Lexer`](crate::Lexer) produces these; [`tokenize`](crate::tokenize)
+    /// drops comments.
+    Comment(String),
+
     Eof,
 }
 
@@ -142,9 +147,9 @@ impl Hash for TokenKind {
                 201u16.hash(state);
                 value.hash(state);
             }
-            TokenKind::FString(content) => {
+            TokenKind::FString(segments) => {
                 202u16.hash(state);
-                content.hash(state);
+                segments.hash(state);
             }
             TokenKind::Bool(value) => {
                 203u16.hash(state);
@@ -192,12 +197,35 @@ impl Hash for TokenKind {
             // Range operator
             TokenKind::DoubleDot => 600u16.hash(state),
 
+            TokenKind::Comment(text) => {
+                700u16.hash(state);
+                text.hash(state);
+            }
+
             TokenKind::Eof => 999u16.hash(state),
         }
     }
 }
 
-// FStringPart is now defined in the AST module
+/// A piece of an f-string literal such as `f"π ≈ {result}"`.
+///
+/// Adjacent text is merged into a single `Text` segment with escapes and
+/// `{{`/`}}` already decoded. Each `{...}` interpolation becomes an `Expr`
+/// segment carrying the tokens of the enclosed expression; both the segment
+/// and its tokens are spanned against the original source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FStringSegment {
+    Text { value: String, span: Span },
+    Expr { tokens: Vec<Token>, span: Span },
+}
+
+impl FStringSegment {
+    pub fn span(&self) -> Span {
+        match self {
+            FStringSegment::Text { span, .. } | FStringSegment::Expr { span, .. } => *span,
+        }
+    }
+}
 
 impl TokenKind {
     pub fn name(&self) -> &'static str {
@@ -284,11 +312,36 @@ impl TokenKind {
             // Range operator
             TokenKind::DoubleDot => "..",
 
+            TokenKind::Comment(_) => "comment",
+
             TokenKind::Eof => "eof",
         }
     }
 }
 
+impl TokenKind {
+    /// The source spelling of a fixed token: a keyword, delimiter or
+    /// operator. `None` for tokens that carry content (identifiers and
+    /// literals) and for the layout tokens, which have no spelling of their
+    /// own.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            TokenKind::Identifier(_)
+            | TokenKind::UnicodeIdentifier(_)
+            | TokenKind::Number(_)
+            | TokenKind::StringLiteral(_)
+            | TokenKind::FString(_)
+            | TokenKind::Bool(_)
+            | TokenKind::Newline
+            | TokenKind::Indent
+            | TokenKind::Dedent
+            | TokenKind::Comment(_)
+            | TokenKind::Eof => None,
+            kind => Some(kind.name()),
+        }
+    }
+}
+
 impl fmt::Debug for TokenKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -296,8 +349,9 @@ impl fmt::Debug for TokenKind {
             TokenKind::UnicodeIdentifier(name) => write!(f, "UnicodeIdentifier({name})"),
             TokenKind::Number(number) => write!(f, "Number({number})"),
             TokenKind::StringLiteral(value) => write!(f, "StringLiteral(\"{value}\")"),
-            TokenKind::FString(content) => write!(f, "FString(\"{}\")", content),
+            TokenKind::FString(segments) => write!(f, "FString({segments:?})"),
             TokenKind::Bool(value) => write!(f, "Bool({value})"),
+            TokenKind::Comment(text) => write!(f, "Comment({text})"),
             kind => f.write_str(kind.name()),
         }
     }
@@ -330,6 +384,24 @@ impl Token {
         &mut self.span
     }
 
+    /// Moves the token, and any tokens nested in it, `offset` bytes later.
+    pub fn shift(&mut self, offset: usize) {
+        self.span = self.span.shifted(offset);
+        if let TokenKind::FString(segments) = &mut self.kind {
+            for segment in segments {
+                match segment {
+                    FStringSegment::Text { span, .. } => *span = span.shifted(offset),
+                    FStringSegment::Expr { tokens, span } => {
+                        *span = span.shifted(offset);
+                        for token in tokens {
+                            token.shift(offset);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn is_keyword(&self) -> bool {
         matches!(
             self.kind,
@@ -412,6 +484,48 @@ impl Token {
         )
     }
 
+    /// `=` and the compound assignments `+=`, `-=`, `*=`, `/=`.
+    pub fn is_assignment_operator(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::Equals
+                | TokenKind::PlusEq
+                | TokenKind::MinusEq
+                | TokenKind::StarEq
+                | TokenKind::SlashEq
+        )
+    }
+
+    /// `==`, `!=`, `<`, `>`, `<=`, `>=` and the identity operator `is`.
+    pub fn is_comparison_operator(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::EqEq
+                | TokenKind::Neq
+                | TokenKind::Lt
+                | TokenKind::Gt
+                | TokenKind::LtEq
+                | TokenKind::GtEq
+                | TokenKind::Is
+        )
+    }
+
+    /// `+`, `-`, `*`, `/` and `%`.
+    pub fn is_arithmetic_operator(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Star
+                | TokenKind::Slash
+                | TokenKind::Percent
+        )
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(self.kind, TokenKind::Comment(_))
+    }
+
     pub fn is_structural(&self) -> bool {
         matches!(
             self.kind,
@@ -434,3 +548,273 @@ impl Hash for Token {
         self.span.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(kind: TokenKind) -> Token {
+        Token::new(kind, Span::new(0, 0))
+    }
+
+    /// Every `TokenKind` variant. The exhaustive match below stops compiling when a
+    /// variant is added, so the new kind has to be listed (and categorised) here.
+    fn all_kinds() -> Vec<TokenKind> {
+        let kinds = vec![
+            TokenKind::Fn,
+            TokenKind::Let,
+            TokenKind::Return,
+            TokenKind::If,
+            TokenKind::Else,
+            TokenKind::Elif,
+            TokenKind::For,
+            TokenKind::While,
+            TokenKind::Break,
+            TokenKind::Continue,
+            TokenKind::Pass,
+            TokenKind::In,
+            TokenKind::Is,
+            TokenKind::Not,
+            TokenKind::Use,
+            TokenKind::As,
+            TokenKind::Pub,
+            TokenKind::Await,
+            TokenKind::Spawn,
+            TokenKind::Match,
+            TokenKind::Case,
+            TokenKind::True,
+            TokenKind::False,
+            TokenKind::Print,
+            TokenKind::None,
+            TokenKind::Struct,
+            TokenKind::Enum,
+            TokenKind::And,
+            TokenKind::Or,
+            TokenKind::Identifier("x".to_string()),
+            TokenKind::UnicodeIdentifier("π".to_string()),
+            TokenKind::Number("1".to_string()),
+            TokenKind::StringLiteral("s".to_string()),
+            TokenKind::FString(vec![FStringSegment::Text {
+                value: "x".to_string(),
+                span: Span::new(0, 1),
+            }]),
+            TokenKind::Bool(true),
+            TokenKind::Colon,
+            TokenKind::Newline,
+            TokenKind::Indent,
+            TokenKind::Dedent,
+            TokenKind::LParen,
+            TokenKind::RParen,
+            TokenKind::LBrace,
+            TokenKind::RBrace,
+            TokenKind::LBracket,
+            TokenKind::RBracket,
+            TokenKind::Comma,
+            TokenKind::Dot,
+            TokenKind::Arrow,
+            TokenKind::Equals,
+            TokenKind::EqEq,
+            TokenKind::Neq,
+            TokenKind::Lt,
+            TokenKind::Gt,
+            TokenKind::LtEq,
+            TokenKind::GtEq,
+            TokenKind::Plus,
+            TokenKind::Minus,
+            TokenKind::Star,
+            TokenKind::Slash,
+            TokenKind::Percent,
+            TokenKind::Pipe,
+            TokenKind::Amp,
+            TokenKind::Bang,
+            TokenKind::PlusEq,
+            TokenKind::MinusEq,
+            TokenKind::StarEq,
+            TokenKind::SlashEq,
+            TokenKind::DoubleDot,
+            TokenKind::Comment("# note".to_string()),
+            TokenKind::Eof,
+        ];
+
+        for kind in &kinds {
+            match kind {
+                TokenKind::Fn
+                | TokenKind::Let
+                | TokenKind::Return
+                | TokenKind::If
+                | TokenKind::Else
+                | TokenKind::Elif
+                | TokenKind::For
+                | TokenKind::While
+                | TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::Pass
+                | TokenKind::In
+                | TokenKind::Is
+                | TokenKind::Not
+                | TokenKind::Use
+                | TokenKind::As
+                | TokenKind::Pub
+                | TokenKind::Await
+                | TokenKind::Spawn
+                | TokenKind::Match
+                | TokenKind::Case
+                | TokenKind::True
+                | TokenKind::False
+                | TokenKind::Print
+                | TokenKind::None
+                | TokenKind::Struct
+                | TokenKind::Enum
+                | TokenKind::And
+                | TokenKind::Or
+                | TokenKind::Identifier(_)
+                | TokenKind::UnicodeIdentifier(_)
+                | TokenKind::Number(_)
+                | TokenKind::StringLiteral(_)
+                | TokenKind::FString(_)
+                | TokenKind::Bool(_)
+                | TokenKind::Colon
+                | TokenKind::Newline
+                | TokenKind::Indent
+                | TokenKind::Dedent
+                | TokenKind::LParen
+                | TokenKind::RParen
+                | TokenKind::LBrace
+                | TokenKind::RBrace
+                | TokenKind::LBracket
+                | TokenKind::RBracket
+                | TokenKind::Comma
+                | TokenKind::Dot
+                | TokenKind::Arrow
+                | TokenKind::Equals
+                | TokenKind::EqEq
+                | TokenKind::Neq
+                | TokenKind::Lt
+                | TokenKind::Gt
+                | TokenKind::LtEq
+                | TokenKind::GtEq
+                | TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Star
+                | TokenKind::Slash
+                | TokenKind::Percent
+                | TokenKind::Pipe
+                | TokenKind::Amp
+                | TokenKind::Bang
+                | TokenKind::PlusEq
+                | TokenKind::MinusEq
+                | TokenKind::StarEq
+                | TokenKind::SlashEq
+                | TokenKind::DoubleDot
+                | TokenKind::Comment(_)
+                | TokenKind::Eof => {}
+            }
+        }
+
+        kinds
+    }
+
+    #[test]
+    fn every_kind_has_exactly_one_category() {
+        // Keywords that double as an operator or literal are classified as keywords.
+        let keyword_overlaps = [TokenKind::Is, TokenKind::Not, TokenKind::None];
+        let layout = [
+            TokenKind::Newline,
+            TokenKind::Indent,
+            TokenKind::Dedent,
+            TokenKind::Eof,
+        ];
+
+        for kind in all_kinds() {
+            let tok = token(kind.clone());
+            let categories = [
+                tok.is_keyword(),
+                tok.is_identifier(),
+                tok.is_literal() && !keyword_overlaps.contains(&kind),
+                tok.is_operator() && !keyword_overlaps.contains(&kind),
+                tok.is_structural(),
+                tok.is_comment(),
+                layout.contains(&kind),
+            ];
+            let count = categories.iter().filter(|&&hit| hit).count();
+            assert_eq!(count, 1, "{kind:?} matched {count} categories");
+        }
+    }
+
+    #[test]
+    fn fixed_tokens_lex_back_from_their_spelling() {
+        for kind in all_kinds() {
+            let Some(spelling) = kind.as_str() else {
+                let tok = token(kind.clone());
+                assert!(
+                    tok.is_identifier()
+                        || tok.is_comment()
+                        || (tok.is_literal() && kind != TokenKind::None)
+                        || matches!(
+                            kind,
+                            TokenKind::Newline
+                                | TokenKind::Indent
+                                | TokenKind::Dedent
+                                | TokenKind::Eof
+                        ),
+                    "{kind:?} has no spelling"
+                );
+                continue;
+            };
+            let tokens = crate::tokenize(spelling).expect("fixed tokens lex");
+            assert_eq!(tokens[0].kind(), &kind, "lexing {spelling:?}");
+        }
+    }
+
+    #[test]
+    fn operator_categories_are_disjoint() {
+        let assignment = [
+            TokenKind::Equals,
+            TokenKind::PlusEq,
+            TokenKind::MinusEq,
+            TokenKind::StarEq,
+            TokenKind::SlashEq,
+        ];
+        let comparison = [
+            TokenKind::EqEq,
+            TokenKind::Neq,
+            TokenKind::Lt,
+            TokenKind::Gt,
+            TokenKind::LtEq,
+            TokenKind::GtEq,
+            TokenKind::Is,
+        ];
+        let arithmetic = [
+            TokenKind::Plus,
+            TokenKind::Minus,
+            TokenKind::Star,
+            TokenKind::Slash,
+            TokenKind::Percent,
+        ];
+
+        for kind in assignment {
+            let tok = token(kind);
+            assert!(tok.is_assignment_operator(), "{:?}", tok.kind());
+            assert!(!tok.is_comparison_operator() && !tok.is_arithmetic_operator());
+        }
+        for kind in comparison {
+            let tok = token(kind);
+            assert!(tok.is_comparison_operator(), "{:?}", tok.kind());
+            assert!(!tok.is_assignment_operator() && !tok.is_arithmetic_operator());
+        }
+        for kind in arithmetic {
+            let tok = token(kind);
+            assert!(tok.is_arithmetic_operator(), "{:?}", tok.kind());
+            assert!(!tok.is_assignment_operator() && !tok.is_comparison_operator());
+        }
+
+        for kind in [TokenKind::Arrow, TokenKind::DoubleDot, TokenKind::Colon] {
+            let tok = token(kind);
+            assert!(
+                !tok.is_assignment_operator()
+                    && !tok.is_comparison_operator()
+                    && !tok.is_arithmetic_operator()
+            );
+        }
+    }
+}
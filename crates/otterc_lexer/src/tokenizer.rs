@@ -1,5 +1,6 @@
-use crate::token::{Token, TokenKind};
+use crate::token::{FStringSegment, Token, TokenKind};
 use otterc_span::Span;
+use std::collections::VecDeque;
 
 use otterc_utils::errors::{Diagnostic, DiagnosticSeverity};
 use thiserror::Error;
@@ -19,12 +20,52 @@ pub enum LexerError {
         found: usize,
         span: Span,
     },
+    #[error(
+        "inconsistent indentation: {found} spaces is not a multiple of the {unit}-space indent unit (line {line})"
+    )]
+    InconsistentIndentation {
+        line: usize,
+        unit: usize,
+        found: usize,
+        span: Span,
+    },
     #[error("unterminated string literal (line {line}, column {column})")]
     UnterminatedString {
         line: usize,
         column: usize,
         span: Span,
     },
+    #[error("unterminated f-string interpolation (line {line}, column {column})")]
+    UnterminatedInterpolation {
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+    #[error(
+        "Unicode line separator U+{:04X} is not a line break (line {line}, column {column})",
+        u32::from(*ch)
+    )]
+    UnicodeLineSeparator {
+        ch: char,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+    #[error("unknown escape sequence `{escape}` (line {line}, column {column})")]
+    InvalidEscape {
+        escape: String,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
+    #[error("invalid {base} literal `{literal}` (line {line}, column {column})")]
+    InvalidRadixLiteral {
+        literal: String,
+        base: &'static str,
+        line: usize,
+        column: usize,
+        span: Span,
+    },
     #[error("unexpected character `{ch}` (line {line}, column {column})")]
     UnexpectedCharacter {
         ch: char,
@@ -35,6 +76,34 @@ pub enum LexerError {
 }
 
 impl LexerError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexerError::TabsNotAllowed { span, .. }
+            | LexerError::IndentationMismatch { span, .. }
+            | LexerError::InconsistentIndentation { span, .. }
+            | LexerError::UnterminatedString { span, .. }
+            | LexerError::UnterminatedInterpolation { span, .. }
+            | LexerError::UnicodeLineSeparator { span, .. }
+            | LexerError::InvalidEscape { span, .. }
+            | LexerError::InvalidRadixLiteral { span, .. }
+            | LexerError::UnexpectedCharacter { span, .. } => *span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            LexerError::TabsNotAllowed { span, .. }
+            | LexerError::IndentationMismatch { span, .. }
+            | LexerError::InconsistentIndentation { span, .. }
+            | LexerError::UnterminatedString { span, .. }
+            | LexerError::UnterminatedInterpolation { span, .. }
+            | LexerError::UnicodeLineSeparator { span, .. }
+            | LexerError::InvalidEscape { span, .. }
+            | LexerError::InvalidRadixLiteral { span, .. }
+            | LexerError::UnexpectedCharacter { span, .. } => span,
+        }
+    }
+
     pub fn to_diagnostic(&self, source_id: &str) -> Diagnostic {
         match self {
             LexerError::TabsNotAllowed { span, .. } => Diagnostic::new(
@@ -60,6 +129,14 @@ impl LexerError {
             )
             .with_suggestion(format!("Indent with {} spaces (found {})", expected, found))
             .with_help("Check that indentation is consistent throughout the file."),
+            LexerError::InconsistentIndentation { span, unit, .. } => Diagnostic::new(
+                DiagnosticSeverity::Error,
+                source_id,
+                *span,
+                self.to_string(),
+            )
+            .with_suggestion(format!("Indent each block by {} spaces", unit))
+            .with_help("The first indented line sets the indent unit for the whole file."),
             LexerError::UnterminatedString { span, .. } => Diagnostic::new(
                 DiagnosticSeverity::Error,
                 source_id,
@@ -68,6 +145,43 @@ impl LexerError {
             )
             .with_suggestion("Add a closing quote (\") to terminate the string")
             .with_help("String literals must be enclosed in double quotes."),
+            LexerError::UnterminatedInterpolation { span, .. } => Diagnostic::new(
+                DiagnosticSeverity::Error,
+                source_id,
+                *span,
+                self.to_string(),
+            )
+            .with_suggestion("Add a closing brace (}) to end the interpolation")
+            .with_help("Write `{{` to include a literal brace in an f-string."),
+            LexerError::UnicodeLineSeparator { span, .. } => Diagnostic::new(
+                DiagnosticSeverity::Error,
+                source_id,
+                *span,
+                self.to_string(),
+            )
+            .with_suggestion("Replace it with a newline")
+            .with_help("Lines end with `\\n`, `\\r\\n` or `\\r`. Unicode separators are only allowed in strings and comments."),
+            LexerError::InvalidEscape { span, .. } => Diagnostic::new(
+                DiagnosticSeverity::Error,
+                source_id,
+                *span,
+                self.to_string(),
+            )
+            .with_suggestion("Write `\\\\` for a literal backslash")
+            .with_help(
+                "Supported escapes are \\n, \\t, \\r, \\0, \\\\, \\\", \\' and \\u{...} with 1 to 6 hex digits.",
+            ),
+            LexerError::InvalidRadixLiteral { span, base, .. } => Diagnostic::new(
+                DiagnosticSeverity::Error,
+                source_id,
+                *span,
+                self.to_string(),
+            )
+            .with_suggestion(match *base {
+                "hexadecimal" => "Use only the digits 0-9 and a-f after `0x`",
+                _ => "Use only the digits 0 and 1 after `0b`",
+            })
+            .with_help("A `0x` or `0b` prefix must be followed by at least one digit."),
             LexerError::UnexpectedCharacter { span, ch, .. } => {
                 let mut diag = Diagnostic::new(
                     DiagnosticSeverity::Error,
@@ -109,6 +223,11 @@ struct LexerState {
     tokens: Vec<Token>,
     errors: Vec<LexerError>,
     indent_stack: Vec<usize>,
+    /// Width of the first indentation in the file; every indentation must
+    /// be a multiple of it.
+    indent_unit: Option<usize>,
+    /// Whether `#` comments become `Comment` tokens rather than being dropped
+    keep_comments: bool,
     source: Vec<u8>,
     offset: usize,
     line: usize,
@@ -121,6 +240,8 @@ impl LexerState {
             tokens: Vec::new(),
             errors: Vec::new(),
             indent_stack: vec![0],
+            indent_unit: None,
+            keep_comments: false,
             source: source.as_bytes().to_vec(),
             offset: 0,
             line: 1,
@@ -175,6 +296,17 @@ impl LexerState {
         }
     }
 
+    /// The Unicode line or paragraph separator (U+0085, U+2028 or U+2029)
+    /// starting at `offset`, with its length in bytes.
+    fn unicode_line_separator_at(&self, offset: usize) -> Option<(char, usize)> {
+        match self.source.get(offset..offset + 3) {
+            Some([0xE2, 0x80, 0xA8]) => Some(('\u{2028}', 3)),
+            Some([0xE2, 0x80, 0xA9]) => Some(('\u{2029}', 3)),
+            _ if self.source.get(offset..offset + 2) == Some(&[0xC2, 0x85]) => Some(('\u{85}', 2)),
+            _ => None,
+        }
+    }
+
     fn current_newline_len(&self) -> Option<usize> {
         self.newline_len_at(self.offset)
     }
@@ -190,6 +322,18 @@ impl LexerState {
         Span::new(start, start + len)
     }
 
+    /// Span of the whole character starting at byte `start`, so that spans
+    /// never end inside a multi-byte character.
+    fn char_span(&self, start: usize) -> Span {
+        let len = self.source[start.min(self.source.len())..]
+            .iter()
+            .skip(1)
+            .take_while(|&&byte| byte & 0xC0 == 0x80)
+            .count()
+            + 1;
+        self.create_span(start, len.min(self.source.len().saturating_sub(start)))
+    }
+
     fn emit_token(&mut self, kind: TokenKind, start: usize, len: usize) {
         let span = Span::new(start, start + len);
         self.tokens.push(Token::new(kind, span));
@@ -211,6 +355,7 @@ pub fn tokenize(source: &str) -> LexResult<Vec<Token>> {
     let estimated_tokens = source.len() / 4; // Rough estimate
     state.tokens.reserve(estimated_tokens);
 
+    state.skip_shebang();
     while !state.is_at_end() {
         state.process_line();
     }
@@ -225,7 +370,208 @@ pub fn tokenize(source: &str) -> LexResult<Vec<Token>> {
     }
 }
 
+/// Tokenizer that lexes one line at a time as tokens are requested.
+///
+/// It yields the same tokens as [`tokenize`] plus a `Comment` token for
+/// each `#` comment, with each error in source order among them instead of
+/// collected at the end.
+pub struct Lexer {
+    state: LexerState,
+    pending: VecDeque<Result<Token, LexerError>>,
+    finished: bool,
+    /// Added to every span, for source that starts partway into a file
+    base: usize,
+    /// Column of each block opened by the tokens yielded so far
+    indents: Vec<usize>,
+}
+
+impl Lexer {
+    pub fn new(source: &str) -> Self {
+        Self::new_at(source, 0)
+    }
+
+    /// A lexer for `source` found `base` bytes into a larger file, such as
+    /// an f-string interpolation. Spans are relative to that file.
+    pub fn new_at(source: &str, base: usize) -> Self {
+        let mut state = LexerState::new(source);
+        state.keep_comments = true;
+        state.skip_shebang();
+        Self {
+            state,
+            pending: VecDeque::new(),
+            finished: false,
+            base,
+            indents: Vec::new(),
+        }
+    }
+
+    /// The item the next call to `next` returns, without consuming it.
+    pub fn peek(&mut self) -> Option<&Result<Token, LexerError>> {
+        while self.pending.is_empty() {
+            if !self.fill() {
+                return None;
+            }
+        }
+        self.pending.front()
+    }
+
+    /// Number of blocks open after the last token yielded.
+    pub fn current_indent(&self) -> usize {
+        self.indents.len()
+    }
+
+    /// Indentation in columns after the last token yielded.
+    pub fn current_indent_width(&self) -> usize {
+        self.indents.last().copied().unwrap_or(0)
+    }
+
+    /// A lexer that drops comments, yielding exactly what [`tokenize`] does.
+    pub fn without_comments(source: &str) -> Self {
+        let mut lexer = Self::new(source);
+        lexer.state.keep_comments = false;
+        lexer
+    }
+
+    /// Lexes the next line into `pending`. Returns `false` once the input
+    /// is exhausted.
+    fn fill(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        if self.state.is_at_end() {
+            self.state.finalize_indentation();
+            self.finished = true;
+        } else {
+            self.state.process_line();
+        }
+
+        let mut produced: Vec<_> = self
+            .state
+            .errors
+            .drain(..)
+            .map(Err)
+            .chain(self.state.tokens.drain(..).map(Ok))
+            .collect();
+        produced.sort_by_key(|item| match item {
+            Ok(token) => token.span().start(),
+            Err(err) => err.span().start(),
+        });
+        let base = self.base;
+        self.pending
+            .extend(produced.into_iter().map(|item| match item {
+                Ok(mut token) => {
+                    token.shift(base);
+                    Ok(token)
+                }
+                Err(mut err) => {
+                    *err.span_mut() = err.span().shifted(base);
+                    Err(err)
+                }
+            }));
+        true
+    }
+
+    /// Drains the lexer and summarizes the file without parsing it.
+    pub fn stats(mut self) -> LexStats {
+        let mut stats = LexStats::default();
+        while let Some(item) = self.next() {
+            stats.max_indent_depth = stats.max_indent_depth.max(self.current_indent());
+            let Ok(token) = item else {
+                stats.errors += 1;
+                continue;
+            };
+            stats.tokens += 1;
+            stats.keywords += usize::from(token.is_keyword());
+            stats.identifiers += usize::from(token.is_identifier());
+            stats.operators += usize::from(token.is_operator());
+            stats.punctuation += usize::from(token.is_structural());
+            stats.comments += usize::from(token.is_comment());
+            match token.kind() {
+                TokenKind::Number(_) => stats.numbers += 1,
+                TokenKind::StringLiteral(_) | TokenKind::FString(_) => stats.strings += 1,
+                _ => {}
+            }
+        }
+
+        let source = &self.state.source;
+        let ends_with_break = matches!(source.last(), Some(b'\n' | b'\r'));
+        stats.lines = if source.is_empty() || ends_with_break {
+            self.state.line - 1
+        } else {
+            self.state.line
+        };
+        stats
+    }
+}
+
+impl Iterator for Lexer {
+    type Item = Result<Token, LexerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                if let Ok(token) = &item {
+                    match token.kind() {
+                        TokenKind::Indent => self
+                            .indents
+                            .push(self.current_indent_width() + token.span().len()),
+                        TokenKind::Dedent => {
+                            self.indents.pop();
+                        }
+                        _ => {}
+                    }
+                }
+                return Some(item);
+            }
+            if !self.fill() {
+                return None;
+            }
+        }
+    }
+}
+
+// Once `fill` reports the end it keeps doing so, and nothing refills `pending`
+impl std::iter::FusedIterator for Lexer {}
+
+/// Summary of a file's tokens, for editor status bars and file overviews.
+///
+/// The categories follow the [`Token`] predicates and so can overlap:
+/// `not` counts as both a keyword and an operator.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LexStats {
+    /// Every token, including comments, layout tokens and the final `Eof`
+    pub tokens: usize,
+    pub keywords: usize,
+    pub identifiers: usize,
+    pub operators: usize,
+    /// Brackets, `:`, `,` and `.`
+    pub punctuation: usize,
+    pub comments: usize,
+    /// String and f-string literals
+    pub strings: usize,
+    pub numbers: usize,
+    pub lines: usize,
+    /// Deepest nesting of indented blocks
+    pub max_indent_depth: usize,
+    pub errors: usize,
+}
+
 impl LexerState {
+    /// Skips a `#!` interpreter line at the very start of the file, newline
+    /// included, without emitting tokens. Spans still count its bytes.
+    fn skip_shebang(&mut self) {
+        if !self.source.starts_with(b"#!") {
+            return;
+        }
+        while self.current_char().is_some() {
+            if self.current_newline_len().is_some() {
+                self.advance(1);
+                return;
+            }
+            self.advance(1);
+        }
+    }
+
     fn process_line(&mut self) {
         let line_start = self.offset;
         let mut indent_width = 0;
@@ -253,8 +599,8 @@ impl LexerState {
                     self.advance(1);
                 }
                 b'#' => {
-                    // Comment line, skip to end
-                    self.skip_to_end_of_line();
+                    // Comment line, no indentation change
+                    self.lex_comment();
                     return;
                 }
                 _ => break,
@@ -302,6 +648,7 @@ impl LexerState {
 
     fn handle_indentation(&mut self, current_indent: usize, line_start: usize) {
         let last_indent = *self.indent_stack.last().unwrap();
+        let consistent = self.check_indent_unit(current_indent, line_start);
 
         if current_indent > last_indent {
             self.indent_stack.push(current_indent);
@@ -311,16 +658,14 @@ impl LexerState {
                 current_indent - last_indent,
             );
         } else if current_indent < last_indent {
+            // Dedents are empty spans where the line's content starts; the
+            // indentation they close belongs to earlier lines
             while current_indent < *self.indent_stack.last().unwrap() {
-                let top = self.indent_stack.pop().unwrap();
-                self.emit_token(
-                    TokenKind::Dedent,
-                    line_start + current_indent,
-                    top - current_indent,
-                );
+                self.indent_stack.pop();
+                self.emit_token(TokenKind::Dedent, line_start + current_indent, 0);
             }
-            if current_indent != *self.indent_stack.last().unwrap() {
-                let span = self.create_span(line_start + current_indent, 1);
+            if consistent && current_indent != *self.indent_stack.last().unwrap() {
+                let span = self.char_span(line_start + current_indent);
                 self.emit_error(LexerError::IndentationMismatch {
                     line: self.line,
                     expected: *self.indent_stack.last().unwrap(),
@@ -331,6 +676,27 @@ impl LexerState {
         }
     }
 
+    /// Records the indent unit on the first indented line and reports
+    /// indentation that is not a multiple of it. Returns whether the
+    /// indentation is consistent.
+    fn check_indent_unit(&mut self, current_indent: usize, line_start: usize) -> bool {
+        if current_indent == 0 {
+            return true;
+        }
+        let unit = *self.indent_unit.get_or_insert(current_indent);
+        if current_indent.is_multiple_of(unit) {
+            return true;
+        }
+        let span = self.create_span(line_start, current_indent);
+        self.emit_error(LexerError::InconsistentIndentation {
+            line: self.line,
+            unit,
+            found: current_indent,
+            span,
+        });
+        false
+    }
+
     fn tokenize_line_content(&mut self, start: usize) {
         while !self.is_at_end() {
             let Some(ch) = self.current_char() else {
@@ -344,7 +710,7 @@ impl LexerState {
 
             match ch {
                 b'#' => {
-                    self.skip_to_end_of_line();
+                    self.lex_comment();
                     return;
                 }
                 b' ' | b'\t' => {
@@ -514,7 +880,18 @@ impl LexerState {
                 self.tokenize_identifier_or_keyword();
             }
             ch if ch > 127 => {
-                self.tokenize_unicode_identifier();
+                if let Some((separator, len)) = self.unicode_line_separator_at(self.offset) {
+                    let span = self.create_span(self.offset, len);
+                    self.emit_error(LexerError::UnicodeLineSeparator {
+                        ch: separator,
+                        line: self.line,
+                        column: self.column,
+                        span,
+                    });
+                    self.advance(len);
+                } else {
+                    self.tokenize_unicode_identifier();
+                }
             }
             _ => {
                 let ch = self.current_char().unwrap();
@@ -534,70 +911,59 @@ impl LexerState {
         let start = self.offset;
         self.advance(1); // Skip opening quote
 
-        let mut result = String::new();
+        let mut result = Vec::new();
 
         while let Some(ch) = self.current_char() {
             if self.current_newline_len().is_some() {
-                let span = self.create_span(start, self.offset - start);
-                self.emit_error(LexerError::UnterminatedString {
-                    line: self.line,
-                    column: self.column,
-                    span,
-                });
+                self.recover_unterminated_string(start, result);
                 return;
             }
 
             match ch {
                 b'"' => {
                     let span = Span::new(start, self.offset + 1);
+                    let value = String::from_utf8_lossy(&result).into_owned();
                     self.tokens
-                        .push(Token::new(TokenKind::StringLiteral(result), span));
+                        .push(Token::new(TokenKind::StringLiteral(value), span));
                     self.advance(1);
                     return;
                 }
-                b'\\' => {
-                    // Escape sequence
-                    self.advance(1);
-                    if let Some(escaped) = self.current_char() {
-                        let escaped_char = match escaped {
-                            b'n' => '\n',
-                            b't' => '\t',
-                            b'r' => '\r',
-                            b'\\' => '\\',
-                            b'"' => '"',
-                            b'\'' => '\'',
-                            _ => escaped as char, // Unknown escape, keep as-is
-                        };
-                        result.push(escaped_char);
-                        self.advance(1);
-                    }
-                }
+                b'\\' => self.lex_escape(&mut result, false),
                 _ => {
-                    result.push(ch as char);
+                    result.push(ch);
                     self.advance(1);
                 }
             }
         }
 
         // Unterminated string at EOF
+        self.recover_unterminated_string(start, result);
+    }
+
+    /// Reports a string missing its closing quote and still emits it, up to
+    /// the end of the line, so lexing carries on with the next line.
+    fn recover_unterminated_string(&mut self, start: usize, value: Vec<u8>) {
+        let value = String::from_utf8_lossy(&value).into_owned();
         let span = self.create_span(start, self.offset - start);
         self.emit_error(LexerError::UnterminatedString {
             line: self.line,
             column: self.column,
             span,
         });
+        self.tokens
+            .push(Token::new(TokenKind::StringLiteral(value), span));
     }
 
     fn tokenize_multiline_string(&mut self) {
         let start = self.offset;
         self.advance(3); // Skip opening """
 
-        let mut result = String::new();
+        let mut result = Vec::new();
 
         while let Some(ch) = self.current_char() {
             if self.current_newline_len().is_some() {
                 // Actual newline in multi-line string
-                result.push('\n');
+                result.push(b'\n');
                 self.advance(1);
                 continue;
             }
@@ -607,35 +973,20 @@ impl LexerState {
                     // Check if this is the closing """
                     if self.peek_char(1) == Some(b'"') && self.peek_char(2) == Some(b'"') {
                         let span = Span::new(start, self.offset + 3);
+                        let value = String::from_utf8_lossy(&result).into_owned();
                         self.tokens
-                            .push(Token::new(TokenKind::StringLiteral(result), span));
+                            .push(Token::new(TokenKind::StringLiteral(value), span));
                         self.advance(3); // Skip closing """
                         return;
                     } else {
                         // Just a regular " in the string
-                        result.push('"');
-                        self.advance(1);
-                    }
-                }
-                b'\\' => {
-                    // Escape sequence (same as regular strings)
-                    self.advance(1);
-                    if let Some(escaped) = self.current_char() {
-                        let escaped_char = match escaped {
-                            b'n' => '\n',
-                            b't' => '\t',
-                            b'r' => '\r',
-                            b'\\' => '\\',
-                            b'"' => '"',
-                            b'\'' => '\'',
-                            _ => escaped as char, // Unknown escape, keep as-is
-                        };
-                        result.push(escaped_char);
+                        result.push(b'"');
                         self.advance(1);
                     }
                 }
+                b'\\' => self.lex_escape(&mut result, false),
                 _ => {
-                    result.push(ch as char);
+                    result.push(ch);
                     self.advance(1);
                 }
             }
@@ -650,11 +1001,88 @@ impl LexerState {
         });
     }
 
+    /// Decodes the escape sequence starting at the current `\\` into `out`.
+    /// `braces` also accepts `\\{` and `\\}`, which only f-strings need.
+    /// A backslash ending the line is left for the caller to report.
+    fn lex_escape(&mut self, out: &mut Vec<u8>, braces: bool) {
+        let start = self.offset;
+        let (line, column) = (self.line, self.column);
+        self.advance(1); // Skip backslash
+        let Some(escaped) = self.current_char() else {
+            return;
+        };
+        if self.current_newline_len().is_some() {
+            return;
+        }
+
+        let decoded = match escaped {
+            b'n' => Some('\n'),
+            b't' => Some('\t'),
+            b'r' => Some('\r'),
+            b'0' => Some('\0'),
+            b'\\' | b'"' | b'\'' => Some(char::from(escaped)),
+            b'{' | b'}' if braces => Some(char::from(escaped)),
+            b'u' => {
+                self.advance(1);
+                self.lex_unicode_escape()
+            }
+            _ => {
+                let len = self.char_span(self.offset).len();
+                self.advance(len);
+                None
+            }
+        };
+        if escaped != b'u' && decoded.is_some() {
+            self.advance(1);
+        }
+
+        match decoded {
+            Some(ch) => out.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+            None => {
+                let span = self.create_span(start, self.offset - start);
+                let escape = String::from_utf8_lossy(&self.source[start..self.offset]).into_owned();
+                self.emit_error(LexerError::InvalidEscape {
+                    escape,
+                    line,
+                    column,
+                    span,
+                });
+            }
+        }
+    }
+
+    /// Decodes the `{1F600}` after `\\u`, consuming as much of it as is
+    /// well-formed.
+    fn lex_unicode_escape(&mut self) -> Option<char> {
+        if self.current_char() != Some(b'{') {
+            return None;
+        }
+        self.advance(1);
+        let digits_start = self.offset;
+        while self.current_char().is_some_and(|ch| ch.is_ascii_hexdigit()) {
+            self.advance(1);
+        }
+        let digits_end = self.offset;
+        if self.current_char() != Some(b'}') {
+            return None;
+        }
+        self.advance(1);
+        if !(1..=6).contains(&(digits_end - digits_start)) {
+            return None;
+        }
+        let digits = std::str::from_utf8(&self.source[digits_start..digits_end]).ok()?;
+        u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+    }
+
     fn tokenize_fstring(&mut self) {
         let start = self.offset;
         self.advance(2); // Skip f"
 
-        let mut result = String::new();
+        let mut segments = Vec::new();
+        let mut text = Vec::new();
+        let mut text_start = self.offset;
 
         while let Some(ch) = self.current_char() {
             if self.current_newline_len().is_some() {
@@ -669,33 +1097,27 @@ impl LexerState {
 
             match ch {
                 b'"' => {
+                    Self::flush_fstring_text(&mut segments, &mut text, text_start, self.offset);
                     let span = Span::new(start, self.offset + 1);
                     self.tokens
-                        .push(Token::new(TokenKind::FString(result), span));
+                        .push(Token::new(TokenKind::FString(segments), span));
                     self.advance(1);
                     return;
                 }
-                b'\\' => {
-                    // Escape sequence
-                    self.advance(1);
-                    if let Some(escaped) = self.current_char() {
-                        let escaped_char = match escaped {
-                            b'n' => '\n',
-                            b't' => '\t',
-                            b'r' => '\r',
-                            b'\\' => '\\',
-                            b'"' => '"',
-                            b'\'' => '\'',
-                            b'{' => '{',
-                            b'}' => '}',
-                            _ => escaped as char, // Unknown escape, keep as-is
-                        };
-                        result.push(escaped_char);
-                        self.advance(1);
+                b'\\' => self.lex_escape(&mut text, true),
+                b'{' | b'}' if self.peek_char(1) == Some(ch) => {
+                    text.push(ch);
+                    self.advance(2);
+                }
+                b'{' => {
+                    Self::flush_fstring_text(&mut segments, &mut text, text_start, self.offset);
+                    if let Some(segment) = self.tokenize_interpolation() {
+                        segments.push(segment);
                     }
+                    text_start = self.offset;
                 }
                 _ => {
-                    result.push(ch as char);
+                    text.push(ch);
                     self.advance(1);
                 }
             }
@@ -710,9 +1132,79 @@ impl LexerState {
         });
     }
 
+    fn flush_fstring_text(
+        segments: &mut Vec<FStringSegment>,
+        text: &mut Vec<u8>,
+        start: usize,
+        end: usize,
+    ) {
+        if text.is_empty() {
+            return;
+        }
+        let value = String::from_utf8_lossy(text).into_owned();
+        text.clear();
+        segments.push(FStringSegment::Text {
+            value,
+            span: Span::new(start, end),
+        });
+    }
+
+    /// Tokenizes a `{...}` interpolation starting at the opening brace. The
+    /// expression ends at the matching `}`; reaching the closing quote or the
+    /// end of the line first reports an unterminated interpolation and leaves
+    /// the remainder of the literal to the caller.
+    fn tokenize_interpolation(&mut self) -> Option<FStringSegment> {
+        let open = self.offset;
+        let mut end = open + 1;
+        let mut depth = 0usize;
+        loop {
+            match self.source.get(end).copied() {
+                Some(b'}') if depth == 0 => break,
+                Some(b'}') => depth -= 1,
+                Some(b'{') => depth += 1,
+                Some(b'"' | b'\n' | b'\r') | None => {
+                    let span = self.create_span(open, end - open);
+                    self.emit_error(LexerError::UnterminatedInterpolation {
+                        line: self.line,
+                        column: self.column,
+                        span,
+                    });
+                    self.advance(end - open);
+                    return None;
+                }
+                Some(_) => {}
+            }
+            end += 1;
+        }
+
+        self.advance(1); // Skip {
+        let outer = std::mem::take(&mut self.tokens);
+        while self.offset < end {
+            match self.current_char() {
+                Some(b' ' | b'\t') => self.advance(1),
+                Some(_) => self.tokenize_token(self.offset),
+                None => break,
+            }
+        }
+        let tokens = std::mem::replace(&mut self.tokens, outer);
+        self.advance(1); // Skip }
+
+        Some(FStringSegment::Expr {
+            tokens,
+            span: Span::new(open, self.offset),
+        })
+    }
+
     fn tokenize_number(&mut self) {
         let start = self.offset;
 
+        if self.current_char() == Some(b'0')
+            && let Some(prefix @ (b'x' | b'X' | b'b' | b'B')) = self.peek_char(1)
+        {
+            self.tokenize_radix_number(prefix.eq_ignore_ascii_case(&b'x'));
+            return;
+        }
+
         // Parse integer part
         while let Some(ch) = self.current_char() {
             if ch.is_ascii_digit() || ch == b'_' {
@@ -722,22 +1214,33 @@ impl LexerState {
             }
         }
 
-        // Parse decimal part
-        if let Some(b'.') = self.current_char()
-            && let Some(next) = self.peek_char(1)
-            && next.is_ascii_digit()
-        {
-            self.advance(1); // Skip decimal point
+        // Parse decimal part. A trailing `.` belongs to the number unless it
+        // starts `..` or a member access such as `5.abs()`.
+        if let Some(b'.') = self.current_char() {
+            let fraction = match self.peek_char(1) {
+                Some(next) if next.is_ascii_digit() => true,
+                Some(b'.') => false,
+                Some(next) if next.is_ascii_alphabetic() || next == b'_' => {
+                    self.exponent_len(1) > 0
+                }
+                _ => true,
+            };
+            if fraction {
+                self.advance(1); // Skip decimal point
 
-            while let Some(ch) = self.current_char() {
-                if ch.is_ascii_digit() || ch == b'_' {
-                    self.advance(1);
-                } else {
-                    break;
+                while let Some(ch) = self.current_char() {
+                    if ch.is_ascii_digit() || ch == b'_' {
+                        self.advance(1);
+                    } else {
+                        break;
+                    }
                 }
             }
         }
 
+        let exponent = self.exponent_len(0);
+        self.advance(exponent);
+
         let value = unsafe { std::str::from_utf8_unchecked(&self.source[start..self.offset]) };
         self.emit_token(
             TokenKind::Number(value.to_string()),
@@ -746,6 +1249,72 @@ impl LexerState {
         );
     }
 
+    /// Length of the exponent (`e10`, `E-4`) starting `ahead` bytes from the
+    /// current position, or 0 if there is none.
+    fn exponent_len(&self, ahead: usize) -> usize {
+        if !matches!(self.peek_char(ahead), Some(b'e' | b'E')) {
+            return 0;
+        }
+        let sign = usize::from(matches!(self.peek_char(ahead + 1), Some(b'+' | b'-')));
+        let digits_start = ahead + 1 + sign;
+        if !self
+            .peek_char(digits_start)
+            .is_some_and(|ch| ch.is_ascii_digit())
+        {
+            return 0;
+        }
+        let mut len = digits_start - ahead;
+        while self
+            .peek_char(ahead + len)
+            .is_some_and(|ch| ch.is_ascii_digit() || ch == b'_')
+        {
+            len += 1;
+        }
+        len
+    }
+
+    /// Lexes a `0x` or `0b` literal. The token keeps the prefix and any `_`
+    /// separators; letters and digits outside the base make it an error
+    /// rather than the start of the next token.
+    fn tokenize_radix_number(&mut self, hex: bool) {
+        let start = self.offset;
+        let (line, column) = (self.line, self.column);
+        self.advance(2);
+
+        let digits_start = self.offset;
+        while let Some(ch) = self.current_char() {
+            if ch.is_ascii_alphanumeric() || ch == b'_' {
+                self.advance(1);
+            } else {
+                break;
+            }
+        }
+
+        let digits = &self.source[digits_start..self.offset];
+        let valid = digits.iter().any(|&ch| ch != b'_')
+            && digits.iter().all(|&ch| {
+                ch == b'_'
+                    || if hex {
+                        ch.is_ascii_hexdigit()
+                    } else {
+                        matches!(ch, b'0' | b'1')
+                    }
+            });
+        let literal = String::from_utf8_lossy(&self.source[start..self.offset]).into_owned();
+        if valid {
+            self.emit_token(TokenKind::Number(literal), start, self.offset - start);
+        } else {
+            let span = self.create_span(start, self.offset - start);
+            self.emit_error(LexerError::InvalidRadixLiteral {
+                literal,
+                base: if hex { "hexadecimal" } else { "binary" },
+                line,
+                column,
+                span,
+            });
+        }
+    }
+
     fn tokenize_identifier_or_keyword(&mut self) {
         let start = self.offset;
 
@@ -798,7 +1367,10 @@ impl LexerState {
         let start = self.offset;
 
         while let Some(ch) = self.current_char() {
-            if ch.is_ascii_alphanumeric() || ch == b'_' || (ch > 127) {
+            if ch.is_ascii_alphanumeric()
+                || ch == b'_'
+                || (ch > 127 && self.unicode_line_separator_at(self.offset).is_none())
+            {
                 self.advance(1);
             } else {
                 break;
@@ -813,7 +1385,17 @@ impl LexerState {
         );
     }
 
-    fn skip_to_end_of_line(&mut self) {
+    /// Consumes a `#` comment and the line break ending it.
+    fn lex_comment(&mut self) {
+        let start = self.offset;
+        let mut end = start;
+        while end < self.source.len() && self.newline_len_at(end).is_none() {
+            end += 1;
+        }
+        if self.keep_comments {
+            let text = String::from_utf8_lossy(&self.source[start..end]).into_owned();
+            self.emit_token(TokenKind::Comment(text), start, end - start);
+        }
         while self.current_char().is_some() {
             if self.current_newline_len().is_some() {
                 self.emit_newline_token();
@@ -850,8 +1432,11 @@ pub fn tokenize_legacy(source: &str) -> LexResult<Vec<Token>> {
 
 #[cfg(test)]
 mod tests {
+    #![expect(clippy::panic, reason = "Panicking on test failures is acceptable")]
+
     use super::*;
     use crate::token::TokenKind;
+    use std::ops::Range;
 
     fn token_kinds(source: &str) -> Vec<TokenKind> {
         tokenize(source)
@@ -885,4 +1470,388 @@ mod tests {
 
         assert_eq!(newline_span, 2);
     }
+
+    #[test]
+    fn fstring_token_carries_text_and_expression_segments() {
+        let source = "f\"{{π}} ≈ {a + b}!\"";
+        let kinds = token_kinds(source);
+        let TokenKind::FString(segments) = &kinds[0] else {
+            panic!("expected an f-string token, got {:?}", kinds[0]);
+        };
+
+        assert_eq!(segments.len(), 3);
+        assert!(
+            matches!(&segments[0], FStringSegment::Text { value, span } if value == "{π} ≈ " && span.start() == 2)
+        );
+        let FStringSegment::Expr { tokens, span } = &segments[1] else {
+            panic!("expected an expression segment");
+        };
+        let expr_kinds: Vec<_> = tokens.iter().map(|token| token.kind().clone()).collect();
+        assert_eq!(
+            expr_kinds,
+            vec![
+                TokenKind::Identifier("a".to_string()),
+                TokenKind::Plus,
+                TokenKind::Identifier("b".to_string()),
+            ]
+        );
+        assert_eq!(
+            &source[tokens[0].span().start()..tokens[0].span().end()],
+            "a"
+        );
+        assert_eq!(&source[span.start()..span.end()], "{a + b}");
+        assert!(matches!(&segments[2], FStringSegment::Text { value, .. } if value == "!"));
+    }
+
+    // Found by the `tokenize` fuzz target: spans ended inside `é`
+    #[test]
+    fn dedent_and_indentation_error_spans_stay_on_char_boundaries() {
+        let source = "fn f():\n    pass\né\n";
+        let tokens = tokenize(source).expect("lexing should succeed");
+        let dedent = tokens
+            .iter()
+            .find(|token| matches!(token.kind(), TokenKind::Dedent))
+            .expect("expected a dedent");
+        assert_eq!(dedent.span(), Span::new(17, 17));
+
+        let source = "fn f():\n  if x:\n      pass\n    é\n";
+        let errors = tokenize(source).expect_err("indentation mismatch");
+        let span = errors[0].to_diagnostic("test").span();
+        assert_eq!(&source[span.start()..span.end()], "é");
+    }
+
+    #[test]
+    fn indentation_must_be_a_multiple_of_the_first_indent() {
+        let source = "fn f():\n  if x:\n    pass\n   pass\n";
+        let errors = tokenize(source).expect_err("inconsistent indentation");
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(matches!(
+            errors[0],
+            LexerError::InconsistentIndentation {
+                line: 4,
+                unit: 2,
+                found: 3,
+                ..
+            }
+        ));
+        assert_eq!(
+            errors[0].to_string(),
+            "inconsistent indentation: 3 spaces is not a multiple of the 2-space indent unit (line 4)"
+        );
+
+        let source = "fn f():\n  if x:\n    pass\n  pass\n";
+        assert!(tokenize(source).is_ok());
+    }
+
+    #[test]
+    fn lf_crlf_and_lone_cr_line_endings_lex_alike() {
+        let lf_source = "fn main():\n    if x:\n        pass\n    pass\n";
+        let lf_tokens = token_kinds(lf_source);
+        assert_eq!(token_kinds(&lf_source.replace('\n', "\r\n")), lf_tokens);
+        assert_eq!(token_kinds(&lf_source.replace('\n', "\r")), lf_tokens);
+
+        let errors = tokenize("let a = 1\rlet b = 2\r  \tlet c = 3\r").expect_err("tab");
+        assert!(matches!(
+            errors[0],
+            LexerError::TabsNotAllowed { line: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn unicode_line_separators_are_rejected_outside_strings() {
+        let source = "let a = 1\u{2028}let b = 2\n";
+        let errors = tokenize(source).expect_err("separator");
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        let LexerError::UnicodeLineSeparator { ch, span, .. } = &errors[0] else {
+            panic!("expected a line separator error, got {:?}", errors[0]);
+        };
+        assert_eq!(*ch, '\u{2028}');
+        assert_eq!(&source[span.start()..span.end()], "\u{2028}");
+
+        // Directly after an identifier, too
+        let errors = tokenize("let π\u{2029} = 1\n").expect_err("separator");
+        assert!(matches!(
+            errors[0],
+            LexerError::UnicodeLineSeparator { ch: '\u{2029}', .. }
+        ));
+
+        assert!(tokenize("let s = \"a\u{2028}b\"  # c\u{2029}d\n").is_ok());
+    }
+
+    #[test]
+    fn leading_shebang_line_is_skipped() {
+        let source = "#!/usr/bin/env otter\nlet x = 1\n";
+        let tokens = tokenize(source).expect("lexing should succeed");
+        assert_eq!(tokens[0].kind(), &TokenKind::Let);
+        assert_eq!(tokens[0].span(), Span::new(21, 24));
+        assert_eq!(token_kinds(source), token_kinds("let x = 1\n"));
+
+        // Only on the first line; elsewhere `#!` starts an ordinary comment
+        let source = "let x = 1\n#!/usr/bin/env otter\n";
+        assert_eq!(token_kinds(source), token_kinds("let x = 1\n# comment\n"));
+    }
+
+    #[test]
+    fn hex_and_binary_literals_need_digits_of_their_base() {
+        assert_eq!(
+            token_kinds("0xFF_FF 0b1010\n")[..2],
+            [
+                TokenKind::Number("0xFF_FF".to_string()),
+                TokenKind::Number("0b1010".to_string())
+            ]
+        );
+
+        for source in ["0x\n", "0b102\n", "0x_\n", "0xfg\n"] {
+            let Err(errors) = tokenize(source) else {
+                panic!("`{source}` should not lex");
+            };
+            assert!(
+                matches!(errors[..], [LexerError::InvalidRadixLiteral { span, .. }] if span == Span::new(0, source.len() - 1)),
+                "{source}: {errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn float_literals_take_exponents_and_trailing_dots() {
+        let number = |value: &str| TokenKind::Number(value.to_string());
+        assert_eq!(
+            token_kinds("1.5e10 3.0E-4 2e+3 5. 5.e3\n")[..5],
+            [
+                number("1.5e10"),
+                number("3.0E-4"),
+                number("2e+3"),
+                number("5."),
+                number("5.e3")
+            ]
+        );
+        // The dot stays a separate token before `..`, a method or a bare `e`
+        assert_eq!(
+            token_kinds("0..5 5.abs()\n")[..8],
+            [
+                number("0"),
+                TokenKind::DoubleDot,
+                number("5"),
+                number("5"),
+                TokenKind::Dot,
+                TokenKind::Identifier("abs".to_string()),
+                TokenKind::LParen,
+                TokenKind::RParen,
+            ]
+        );
+        assert_eq!(
+            token_kinds("1.e\n")[..3],
+            [
+                number("1"),
+                TokenKind::Dot,
+                TokenKind::Identifier("e".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn lexer_yields_comments_unless_asked_not_to() {
+        let source = "# header\nlet x = 1  # trailing\n    # indented\n";
+        let comments: Vec<(TokenKind, Span)> = Lexer::new(source)
+            .map(|item| item.expect("lexing should succeed"))
+            .filter(Token::is_comment)
+            .map(|token| (token.kind().clone(), token.span()))
+            .collect();
+        assert_eq!(
+            comments,
+            [
+                (TokenKind::Comment("# header".to_string()), Span::new(0, 8)),
+                (
+                    TokenKind::Comment("# trailing".to_string()),
+                    Span::new(20, 30)
+                ),
+                (
+                    TokenKind::Comment("# indented".to_string()),
+                    Span::new(35, 45)
+                ),
+            ]
+        );
+
+        let without: Vec<Token> = Lexer::without_comments(source)
+            .collect::<Result<_, _>>()
+            .expect("lexing should succeed");
+        assert_eq!(without, tokenize(source).expect("lexing should succeed"));
+    }
+
+    #[test]
+    fn lexer_spans_start_at_the_base_offset() {
+        let tokens: Vec<Token> = Lexer::new_at("ab cd", 100)
+            .collect::<Result<_, _>>()
+            .expect("lexing should succeed");
+        assert_eq!(tokens[1].kind(), &TokenKind::Identifier("cd".to_string()));
+        assert_eq!(tokens[1].span(), Span::new(103, 105));
+
+        let tokens: Vec<Token> = Lexer::new_at("f\"{x}\"", 10)
+            .collect::<Result<_, _>>()
+            .expect("lexing should succeed");
+        let TokenKind::FString(segments) = tokens[0].kind() else {
+            panic!("expected an f-string, got {:?}", tokens[0]);
+        };
+        let FStringSegment::Expr { tokens, span } = &segments[0] else {
+            panic!("expected an interpolation, got {segments:?}");
+        };
+        assert_eq!(*span, Span::new(12, 15));
+        assert_eq!(tokens[0].span(), Span::new(13, 14));
+
+        let Some(Err(err)) = Lexer::new_at("0x", 7).next() else {
+            panic!("`0x` should not lex");
+        };
+        assert_eq!(err.span(), Span::new(7, 9));
+    }
+
+    #[test]
+    fn dropping_several_levels_closes_each_block() {
+        let source = "if a:\n    if b:\n        if c:\n            pass\nx\n";
+        let kinds = token_kinds(source);
+        let x = kinds
+            .iter()
+            .position(|kind| kind == &TokenKind::Identifier("x".to_string()))
+            .expect("x is lexed");
+        assert_eq!(
+            kinds[x - 3..x],
+            [TokenKind::Dedent, TokenKind::Dedent, TokenKind::Dedent]
+        );
+        let indents = kinds.iter().filter(|kind| **kind == TokenKind::Indent);
+        let dedents = kinds.iter().filter(|kind| **kind == TokenKind::Dedent);
+        assert_eq!(indents.count(), dedents.count());
+    }
+
+    #[test]
+    fn lexer_stays_exhausted_after_eof() {
+        fn assert_fused<I: std::iter::FusedIterator>(_: &I) {}
+
+        let mut lexer = Lexer::new("let x = 1\n");
+        assert_fused(&lexer);
+        let last = lexer.by_ref().last().expect("tokens");
+        assert!(matches!(last, Ok(token) if *token.kind() == TokenKind::Eof));
+        for _ in 0..3 {
+            assert!(lexer.next().is_none());
+        }
+    }
+
+    #[test]
+    fn unterminated_string_ends_at_the_line_and_lexing_continues() {
+        let items: Vec<_> = Lexer::new("let x = \"oops\nlet y = 2").collect();
+        assert!(matches!(
+            &items[3],
+            Err(LexerError::UnterminatedString { span, .. }) if *span == Span::new(8, 13)
+        ));
+        let tokens: Vec<_> = items.into_iter().filter_map(Result::ok).collect();
+        assert_eq!(
+            tokens[3],
+            Token::new(
+                TokenKind::StringLiteral("oops".to_string()),
+                Span::new(8, 13)
+            )
+        );
+        let kinds: Vec<_> = tokens[4..9]
+            .iter()
+            .map(|token| token.kind().clone())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Newline,
+                TokenKind::Let,
+                TokenKind::Identifier("y".to_string()),
+                TokenKind::Equals,
+                TokenKind::Number("2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn string_escapes_are_decoded() {
+        let string = |source: &str| match tokenize(source)
+            .expect("lexing should succeed")
+            .remove(0)
+            .kind()
+        {
+            TokenKind::StringLiteral(value) => value.clone(),
+            other => panic!("expected a string, got {other:?}"),
+        };
+        assert_eq!(string(r#""a\tb""#), "a\tb");
+        assert_eq!(string(r#""quote\"inside""#), "quote\"inside");
+        assert_eq!(string(r#""\u{1F600}""#), "\u{1F600}");
+        assert_eq!(string(r#""π \\ \0""#), "π \\ \0");
+        assert_eq!(string("\"\"\"line\\n\"\"\""), "line\n");
+    }
+
+    #[test]
+    fn unknown_escapes_are_reported_at_their_span() {
+        for (source, escape) in [
+            (r#"let s = "a\qb""#, r"\q"),
+            (r#"let s = "\u{110000}""#, r"\u{110000}"),
+            (r#"let s = "\u{}""#, r"\u{}"),
+            (r#"let s = "\u41""#, r"\u"),
+            (r#"let s = f"\é{x}""#, r"\é"),
+        ] {
+            let Err(errors) = tokenize(source) else {
+                panic!("`{source}` should not lex");
+            };
+            let [LexerError::InvalidEscape { span, .. }] = &errors[..] else {
+                panic!("{source}: {errors:?}");
+            };
+            assert_eq!(&source[Range::from(*span)], escape);
+        }
+    }
+
+    #[test]
+    fn peeking_does_not_advance_the_lexer() {
+        let mut lexer = Lexer::new("let x = 1\n");
+        let peek_token = |lexer: &mut Lexer| lexer.peek().cloned().and_then(Result::ok);
+        let first = peek_token(&mut lexer).expect("a token to peek");
+        assert_eq!(first.kind(), &TokenKind::Let);
+        assert_eq!(peek_token(&mut lexer), Some(first.clone()));
+        assert_eq!(lexer.next().and_then(Result::ok), Some(first));
+        assert_eq!(
+            peek_token(&mut lexer).map(|token| token.kind().clone()),
+            Some(TokenKind::Identifier("x".to_string()))
+        );
+
+        let mut lexer = Lexer::new("");
+        while lexer.next().is_some() {}
+        assert!(lexer.peek().is_none());
+    }
+
+    #[test]
+    fn lexer_tracks_indentation_after_each_token() {
+        let mut lexer = Lexer::new("if a:\n  if b:\n    pass\nx\n");
+        let mut depths = Vec::new();
+        while let Some(item) = lexer.next() {
+            let token = item.expect("lexing should succeed");
+            if matches!(
+                token.kind(),
+                TokenKind::Colon | TokenKind::Pass | TokenKind::Identifier(_)
+            ) {
+                depths.push((lexer.current_indent(), lexer.current_indent_width()));
+            }
+        }
+        // `a`, `:`, `b`, `:`, `pass`, `x`
+        assert_eq!(depths, [(0, 0), (0, 0), (1, 2), (1, 2), (2, 4), (0, 0)]);
+    }
+
+    #[test]
+    fn lexer_yields_tokenize_output_and_summarizes_it() {
+        let source = "fn f(x):\n    if x:\n        return \"a\"\n    return 1\n";
+        let streamed: Vec<Token> = Lexer::without_comments(source)
+            .collect::<Result<_, _>>()
+            .expect("lexing should succeed");
+        assert_eq!(streamed, tokenize(source).expect("lexing should succeed"));
+
+        let stats = Lexer::new(source).stats();
+        assert_eq!(stats.tokens, streamed.len());
+        assert_eq!(stats.keywords, 4);
+        assert_eq!(stats.identifiers, 3);
+        assert_eq!(stats.strings, 1);
+        assert_eq!(stats.numbers, 1);
+        assert_eq!(stats.lines, 4);
+        assert_eq!(stats.max_indent_depth, 2);
+        assert_eq!(stats.errors, 0);
+    }
 }
@@ -12,6 +12,7 @@ pub use memory_profiler::MemoryProfiler;
 pub use sampler::Sampler;
 
 use parking_lot::RwLock;
+use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -65,6 +66,19 @@ impl FunctionMetrics {
     }
 }
 
+impl fmt::Display for FunctionMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:20} calls: {:6} avg: {:>8.3}µs total: {:>8.3}ms",
+            self.name,
+            self.call_count,
+            self.avg_time.as_secs_f64() * 1_000_000.0,
+            self.total_time.as_secs_f64() * 1000.0
+        )
+    }
+}
+
 /// Global profiler instance
 pub struct GlobalProfiler {
     metrics: Arc<RwLock<std::collections::HashMap<String, FunctionMetrics>>>,
@@ -2,10 +2,14 @@
 //!
 //! Handles module resolution, loading, and dependency tracking for .ot files
 
+pub mod linker;
 pub mod loader;
 pub mod processor;
 pub mod resolver;
+pub mod visibility;
 
+pub use linker::{UnsupportedItem, link_local_modules};
 pub use loader::{Module, ModuleExports, ModuleLoader};
 pub use processor::ModuleProcessor;
 pub use resolver::{DependencyGraph, ModulePath, ModuleResolver};
+pub use visibility::{VisibilityError, check_visibility};
@@ -0,0 +1,617 @@
+//! Linking of local `.ot` modules into the program that imports them.
+//!
+//! Every function of a local module is appended to the root program under
+//! the name `module.function`, where `module` is the file stem (or the
+//! directory name for a `mod.ot`). Calls are rewritten to match: `add(..)`
+//! inside `helpers.ot` and `h.add(..)` after `use ./helpers as h` both
+//! become calls to `helpers.add`. Only `pub` functions are reachable through
+//! an import; private ones stay callable from inside their own module.
+//! Structs and type aliases are linked the same way, so `Point` in
+//! `helpers.ot` becomes `helpers.Point` and `h.Point(x=1, y=2)` builds one.
+//! Module-level `let` bindings and enums cannot be linked and are reported
+//! as [`UnsupportedItem`]s.
+//! Spans in linked functions point into their module's file, so the linker
+//! reports which file each one came from.
+
+use anyhow::{Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use otterc_ast::nodes::{Expr, Function, Node, Pattern, Program, Statement, Type};
+use otterc_ast::visit::{MutVisitor, walk_expr_mut, walk_function_mut, walk_statement_mut};
+use otterc_span::Span;
+use otterc_utils::errors::Diagnostic;
+
+use crate::{Module, ModuleProcessor};
+
+/// A module-level item the linker cannot bring into the program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedItem {
+    /// File of the module that defines the item
+    pub path: PathBuf,
+    /// The item as declared, such as `let LIMIT` or `enum Color`
+    pub item: String,
+    pub span: Span,
+}
+
+impl UnsupportedItem {
+    pub fn to_diagnostic(&self, source_id: &str) -> Diagnostic {
+        Diagnostic::error(source_id, self.span, self.to_string()).with_help(
+            "Only functions, structs and type aliases can be defined in an imported module.",
+        )
+    }
+}
+
+impl fmt::Display for UnsupportedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unsupported item in module `{}`: `{}`",
+            module_name(&self.path),
+            self.item
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedItem {}
+
+/// Appends the functions and types of every local module loaded by
+/// `processor` to `program` and points references through imports at them.
+/// Returns the file each linked function was defined in, keyed by its linked
+/// name.
+pub fn link_local_modules(
+    program: &mut Program,
+    processor: &ModuleProcessor,
+) -> Result<HashMap<String, PathBuf>> {
+    let mut modules: Vec<&Module> = processor.local_modules().collect();
+    modules.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut names: HashMap<&Path, String> = HashMap::new();
+    let mut taken: HashMap<String, &Path> = HashMap::new();
+    for module in &modules {
+        let name = module_name(&module.path);
+        if let Some(other) = taken.insert(name.clone(), &module.path) {
+            bail!(
+                "modules {} and {} are both named `{}`",
+                other.display(),
+                module.path.display(),
+                name
+            );
+        }
+        names.insert(&module.path, name);
+    }
+
+    let mut root_rewriter = NameRewriter {
+        imports: local_imports(program, processor, processor.source_dir()),
+        own: None,
+    };
+    root_rewriter.visit_program_mut(program);
+
+    let mut linked = Vec::new();
+    let mut files = HashMap::new();
+    for module in modules {
+        let name = names[module.path.as_path()].clone();
+        let importer_dir = module.path.parent().unwrap_or(Path::new("."));
+        let mut module_program = module.program.clone();
+        let functions = module_program
+            .functions()
+            .map(|function| function.as_ref().name.clone())
+            .collect();
+
+        let mut rewriter = NameRewriter {
+            imports: local_imports(&module_program, processor, importer_dir),
+            own: Some(OwnModule {
+                name: name.clone(),
+                functions,
+                types: type_names(&module_program),
+            }),
+        };
+        rewriter.visit_program_mut(&mut module_program);
+
+        for stmt in module_program.statements {
+            let (stmt, span) = stmt.into_parts();
+            match stmt {
+                Statement::Function(function) => {
+                    let function = function.map(|mut function| {
+                        function.name = format!("{}.{}", name, function.name);
+                        function
+                    });
+                    files.insert(function.as_ref().name.clone(), module.path.clone());
+                    linked.push(Node::new(Statement::Function(function), span));
+                }
+                Statement::Struct {
+                    name: struct_name,
+                    fields,
+                    methods,
+                    public,
+                    generics,
+                } => {
+                    let struct_name = format!("{}.{}", name, struct_name);
+                    // Methods are compiled as `Struct_method`
+                    for method in &methods {
+                        files.insert(
+                            format!("{}_{}", struct_name, method.as_ref().name),
+                            module.path.clone(),
+                        );
+                    }
+                    let stmt = Statement::Struct {
+                        name: struct_name,
+                        fields,
+                        methods,
+                        public,
+                        generics,
+                    };
+                    linked.push(Node::new(stmt, span));
+                }
+                Statement::TypeAlias {
+                    name: alias,
+                    target,
+                    public,
+                    generics,
+                } => {
+                    let stmt = Statement::TypeAlias {
+                        name: format!("{}.{}", name, alias),
+                        target,
+                        public,
+                        generics,
+                    };
+                    linked.push(Node::new(stmt, span));
+                }
+                Statement::Let { name: binding, .. } => {
+                    return Err(UnsupportedItem {
+                        path: module.path.clone(),
+                        item: format!("let {}", binding.as_ref()),
+                        span,
+                    }
+                    .into());
+                }
+                Statement::Enum {
+                    name: enum_name, ..
+                } => {
+                    return Err(UnsupportedItem {
+                        path: module.path.clone(),
+                        item: format!("enum {}", enum_name),
+                        span,
+                    }
+                    .into());
+                }
+                // Keep the stdlib and FFI imports the module's functions use
+                Statement::Use { imports } => {
+                    let imports: Vec<_> = imports
+                        .into_iter()
+                        .filter(|import| {
+                            processor
+                                .resolve_local_import(&import.as_ref().module, importer_dir)
+                                .is_none()
+                        })
+                        .collect();
+                    if !imports.is_empty() {
+                        linked.push(Node::new(Statement::Use { imports }, span));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    program.statements.extend(linked);
+
+    Ok(files)
+}
+
+/// Name a module's functions are linked under
+pub(crate) fn module_name(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|stem| stem.to_str());
+    let name = if stem == Some("mod") {
+        path.parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str())
+    } else {
+        stem
+    };
+    name.unwrap_or("module").to_string()
+}
+
+/// Structs and type aliases `program` defines
+fn type_names(program: &Program) -> HashSet<String> {
+    program
+        .statements
+        .iter()
+        .filter_map(|stmt| match stmt.as_ref() {
+            Statement::Struct { name, .. } | Statement::TypeAlias { name, .. } => {
+                Some(name.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// A local module as seen through one `use`
+pub(crate) struct LinkedImport {
+    pub(crate) module: String,
+    pub(crate) public: HashSet<String>,
+    /// Functions the module defines without `pub`
+    pub(crate) private: HashSet<String>,
+    /// `pub` structs, which are built through the import like calls
+    pub(crate) structs: HashSet<String>,
+}
+
+/// Local modules imported by `program`, keyed by the name they are bound to
+pub(crate) fn local_imports(
+    program: &Program,
+    processor: &ModuleProcessor,
+    importer_dir: &Path,
+) -> HashMap<String, LinkedImport> {
+    let mut imports = HashMap::new();
+    for stmt in &program.statements {
+        let Statement::Use { imports: uses } = stmt.as_ref() else {
+            continue;
+        };
+        for import in uses {
+            let import = import.as_ref();
+            let Some(module) = processor.resolve_local_import(&import.module, importer_dir) else {
+                continue;
+            };
+            let name = module_name(&module.path);
+            let public: HashSet<String> = module.exports.functions.iter().cloned().collect();
+            let private = module
+                .program
+                .functions()
+                .map(|function| function.as_ref().name.clone())
+                .filter(|function| !public.contains(function))
+                .collect();
+            let structs = module
+                .program
+                .statements
+                .iter()
+                .filter_map(|stmt| match stmt.as_ref() {
+                    Statement::Struct {
+                        name, public: true, ..
+                    } => Some(name.clone()),
+                    _ => None,
+                })
+                .collect();
+            let binding = import.alias.clone().unwrap_or_else(|| name.clone());
+            imports.insert(
+                binding,
+                LinkedImport {
+                    module: name,
+                    public,
+                    private,
+                    structs,
+                },
+            );
+        }
+    }
+    imports
+}
+
+/// The module whose definitions are being renamed
+struct OwnModule {
+    name: String,
+    functions: HashSet<String>,
+    /// Structs and type aliases
+    types: HashSet<String>,
+}
+
+/// Renames references to linked functions and types
+struct NameRewriter {
+    imports: HashMap<String, LinkedImport>,
+    /// The module being rewritten, if not the root
+    own: Option<OwnModule>,
+}
+
+impl NameRewriter {
+    fn linked_name(&self, callee: &Expr) -> Option<String> {
+        match callee {
+            Expr::Identifier(name) => {
+                let own = self.own.as_ref()?;
+                own.functions
+                    .contains(name)
+                    .then(|| format!("{}.{}", own.name, name))
+            }
+            Expr::Member { object, field } => {
+                let Expr::Identifier(binding) = object.as_ref().as_ref() else {
+                    return None;
+                };
+                let import = self.imports.get(binding)?;
+                import
+                    .public
+                    .contains(field)
+                    .then(|| format!("{}.{}", import.module, field))
+            }
+            _ => None,
+        }
+    }
+
+    /// Linked name of the struct `h.Point` refers to after `use ./helpers as h`
+    fn imported_struct(&self, callee: &Expr) -> Option<String> {
+        let Expr::Member { object, field } = callee else {
+            return None;
+        };
+        let Expr::Identifier(binding) = object.as_ref().as_ref() else {
+            return None;
+        };
+        let import = self.imports.get(binding)?;
+        import
+            .structs
+            .contains(field)
+            .then(|| format!("{}.{}", import.module, field))
+    }
+
+    /// Linked name of a struct or type alias of the module being rewritten
+    fn own_type(&self, name: &str) -> Option<String> {
+        let own = self.own.as_ref()?;
+        own.types
+            .contains(name)
+            .then(|| format!("{}.{}", own.name, name))
+    }
+
+    fn rename_type(&self, ty: &mut Node<Type>) {
+        match ty.as_mut() {
+            Type::Simple(name) => {
+                if let Some(linked) = self.own_type(name) {
+                    *name = linked;
+                }
+            }
+            Type::Generic { base, args } => {
+                if let Some(linked) = self.own_type(base) {
+                    *base = linked;
+                }
+                for arg in args {
+                    self.rename_type(arg);
+                }
+            }
+        }
+    }
+}
+
+impl MutVisitor for NameRewriter {
+    fn visit_statement_mut(&mut self, stmt: &mut Node<Statement>) {
+        match stmt.as_mut() {
+            Statement::Let { ty: Some(ty), .. } => self.rename_type(ty),
+            Statement::Struct { fields, .. } => {
+                for (_, ty) in fields {
+                    self.rename_type(ty);
+                }
+            }
+            Statement::TypeAlias { target, .. } => self.rename_type(target),
+            _ => {}
+        }
+        walk_statement_mut(self, stmt);
+    }
+
+    fn visit_function_mut(&mut self, func: &mut Node<Function>) {
+        let function = func.as_mut();
+        for param in &mut function.params {
+            if let Some(ty) = &mut param.as_mut().ty {
+                self.rename_type(ty);
+            }
+        }
+        if let Some(ty) = &mut function.ret_ty {
+            self.rename_type(ty);
+        }
+        walk_function_mut(self, func);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Node<Expr>) {
+        walk_expr_mut(self, expr);
+
+        match expr.as_mut() {
+            Expr::Call { func, args, kwargs } => {
+                if args.is_empty()
+                    && let Some(name) = self.imported_struct(func.as_ref().as_ref())
+                {
+                    let fields = std::mem::take(kwargs);
+                    *expr.as_mut() = Expr::Struct { name, fields };
+                } else if let Some(name) = self.linked_name(func.as_ref().as_ref()) {
+                    let span = *func.span();
+                    **func = Node::new(Expr::Identifier(name), span);
+                }
+            }
+            // `f(x=1)` parses as a struct literal, so it may name a function
+            Expr::Struct { name, .. } => {
+                let callee = Expr::Identifier(name.clone());
+                if let Some(linked) = self.own_type(name).or_else(|| self.linked_name(&callee)) {
+                    *name = linked;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_pattern_mut(&mut self, pattern: &mut Node<Pattern>) {
+        match pattern.as_mut() {
+            Pattern::Struct { name, fields } => {
+                if let Some(linked) = self.own_type(name) {
+                    *name = linked;
+                }
+                for (_, field) in fields {
+                    if let Some(field) = field {
+                        self.visit_pattern_mut(field);
+                    }
+                }
+            }
+            Pattern::EnumVariant {
+                fields: patterns, ..
+            }
+            | Pattern::Array { patterns, .. }
+            | Pattern::Or(patterns) => {
+                for pattern in patterns {
+                    self.visit_pattern_mut(pattern);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::panic, reason = "Panicking on test failures is acceptable")]
+
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn parse_file(path: &Path) -> Program {
+        let source = fs::read_to_string(path).expect("read source");
+        let tokens = otterc_lexer::tokenize(&source).expect("tokenize");
+        otterc_parser::parse(&tokens).expect("parse")
+    }
+
+    fn first_call_target(statements: &[Node<Statement>]) -> String {
+        for stmt in statements {
+            if let Statement::Function(function) = stmt.as_ref() {
+                for stmt in &function.as_ref().body.as_ref().statements {
+                    if let Statement::Return(Some(expr)) | Statement::Let { expr, .. } =
+                        stmt.as_ref()
+                        && let Expr::Call { func, .. } = expr.as_ref()
+                        && let Expr::Identifier(name) = func.as_ref().as_ref()
+                    {
+                        return name.clone();
+                    }
+                }
+            }
+        }
+        panic!("no call found");
+    }
+
+    #[test]
+    fn links_functions_of_imported_files() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let source_dir = temp_dir.path();
+        let main_file = source_dir.join("main.ot");
+        fs::write(
+            &main_file,
+            "use ./helpers as h\n\nfn main():\n    let total = h.add(1, 2)\n    println(f\"{total}\")\n",
+        )
+        .expect("write main");
+        fs::write(
+            source_dir.join("helpers.ot"),
+            "pub fn add(a: int, b: int) -> int:\n    let doubled = double(a)\n    return doubled + b\n\nfn double(x: int) -> int:\n    return x * 2\n",
+        )
+        .expect("write helpers");
+
+        let mut program = parse_file(&main_file);
+        let mut processor = ModuleProcessor::new(source_dir.to_path_buf(), None);
+        processor
+            .process_imports(&program)
+            .expect("process imports");
+        let files = link_local_modules(&mut program, &processor).expect("link");
+
+        let function_names: Vec<_> = program
+            .functions()
+            .map(|function| function.as_ref().name.clone())
+            .collect();
+        assert_eq!(function_names, ["main", "helpers.add", "helpers.double"]);
+        assert_eq!(first_call_target(&program.statements[1..2]), "helpers.add");
+        assert_eq!(
+            first_call_target(&program.statements[2..]),
+            "helpers.double"
+        );
+        let helpers = processor
+            .local_modules()
+            .next()
+            .expect("helpers")
+            .path
+            .clone();
+        assert_eq!(files.get("helpers.double"), Some(&helpers));
+        assert_eq!(files.get("main"), None);
+    }
+
+    fn link(main: &str, helpers: &str) -> Result<Program> {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let source_dir = temp_dir.path();
+        let main_file = source_dir.join("main.ot");
+        fs::write(&main_file, main).expect("write main");
+        fs::write(source_dir.join("helpers.ot"), helpers).expect("write helpers");
+
+        let mut program = parse_file(&main_file);
+        let mut processor = ModuleProcessor::new(source_dir.to_path_buf(), None);
+        processor
+            .process_imports(&program)
+            .expect("process imports");
+        link_local_modules(&mut program, &processor)?;
+        Ok(program)
+    }
+
+    #[test]
+    fn links_structs_of_imported_files() {
+        let program = link(
+            "use ./helpers as h\n\nfn main():\n    let p = h.Point(x=1, y=2)\n    println(f\"{p.x}\")\n",
+            "pub struct Point:\n    x: int\n    y: int\n\npub fn origin() -> Point:\n    return Point(x=0, y=0)\n",
+        )
+        .expect("link");
+
+        let Statement::Struct { name, .. } = program.statements[2].as_ref() else {
+            panic!("struct not linked: {:?}", program.statements[2]);
+        };
+        assert_eq!(name, "helpers.Point");
+
+        let main = program.functions().next().expect("main");
+        let Statement::Let { expr, .. } = main.as_ref().body.as_ref().statements[0].as_ref() else {
+            panic!("expected let");
+        };
+        let Expr::Struct { name, fields } = expr.as_ref() else {
+            panic!("expected struct literal: {expr:?}");
+        };
+        assert_eq!(name, "helpers.Point");
+        assert_eq!(fields.len(), 2);
+
+        let origin = program.functions().nth(1).expect("origin");
+        let origin = origin.as_ref();
+        assert_eq!(origin.name, "helpers.origin");
+        let Some(Type::Simple(ret_ty)) = origin.ret_ty.as_ref().map(|ty| ty.as_ref()) else {
+            panic!("expected return type");
+        };
+        assert_eq!(ret_ty, "helpers.Point");
+        let Statement::Return(Some(expr)) = origin.body.as_ref().statements[0].as_ref() else {
+            panic!("expected return");
+        };
+        let Expr::Struct { name, .. } = expr.as_ref() else {
+            panic!("expected struct literal: {expr:?}");
+        };
+        assert_eq!(name, "helpers.Point");
+    }
+
+    #[test]
+    fn rejects_module_level_constants() {
+        let helpers = "pub fn limit() -> int:\n    return LIMIT\n\npub let LIMIT = 10\n";
+        let Err(err) = link(
+            "use ./helpers\n\nfn main():\n    println(f\"{helpers.limit()}\")\n",
+            helpers,
+        ) else {
+            panic!("module constant should be rejected");
+        };
+        let item = err
+            .downcast_ref::<UnsupportedItem>()
+            .expect("unsupported item error");
+        assert_eq!(
+            item.to_string(),
+            "unsupported item in module `helpers`: `let LIMIT`"
+        );
+        assert!(item.path.ends_with("helpers.ot"), "{}", item.path.display());
+        assert_eq!(
+            &helpers[item.span.start()..item.span.end()],
+            "pub let LIMIT = 10"
+        );
+    }
+
+    #[test]
+    fn rejects_circular_imports() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let source_dir = temp_dir.path();
+        let main_file = source_dir.join("main.ot");
+        fs::write(&main_file, "use ./a\n").expect("write main");
+        fs::write(source_dir.join("a.ot"), "use ./b\n").expect("write a");
+        fs::write(source_dir.join("b.ot"), "use ./a\n").expect("write b");
+
+        let program = parse_file(&main_file);
+        let mut processor = ModuleProcessor::new(source_dir.to_path_buf(), None);
+        let Err(err) = processor.process_imports(&program) else {
+            panic!("circular import should be rejected");
+        };
+        assert!(err.to_string().starts_with("circular import:"), "{err}");
+    }
+}
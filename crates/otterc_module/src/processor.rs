@@ -189,6 +189,33 @@ impl ModuleProcessor {
         self.loaded_modules.values()
     }
 
+    /// Iterate over the loaded modules that come from local files rather
+    /// than the stdlib
+    pub fn local_modules(&self) -> impl Iterator<Item = &Module> {
+        self.loaded_modules
+            .values()
+            .filter(|module| !self.is_stdlib_path(&module.path))
+    }
+
+    /// The local module a `use` of `module` in a file under `importer_dir`
+    /// refers to, if it was loaded
+    pub fn resolve_local_import(&self, module: &str, importer_dir: &Path) -> Option<&Module> {
+        if Self::is_virtual_module(module) || module.starts_with("rust:") {
+            return None;
+        }
+        let resolver = ModuleResolver::new(importer_dir.to_path_buf(), self.stdlib_dir.clone());
+        let resolved = resolver.resolve(module).ok()?;
+        if self.is_stdlib_path(&resolved) {
+            return None;
+        }
+        self.loaded_modules.get(&resolved)
+    }
+
+    /// Directory imports of the root program are resolved against
+    pub fn source_dir(&self) -> &Path {
+        &self.source_dir
+    }
+
     /// Set stdlib directory
     pub fn set_stdlib_dir(&mut self, dir: PathBuf) {
         let normalized = dir.canonicalize().unwrap_or(dir);
@@ -255,13 +282,15 @@ impl ModuleProcessor {
         resolved: PathBuf,
         dependencies: &mut Vec<PathBuf>,
     ) -> Result<()> {
-        if !self.loaded_modules.contains_key(&resolved) {
-            let owner_path = owner.to_path_buf();
-            self.loader
-                .resolver_mut()
-                .add_dependency(owner_path.clone(), resolved.clone());
-            self.loader.resolver_mut().check_circular(&owner_path)?;
+        // Record the edge even when the module is already loaded, since
+        // that is exactly the case when an import closes a cycle
+        let owner_path = owner.to_path_buf();
+        self.loader
+            .resolver_mut()
+            .add_dependency(owner_path.clone(), resolved.clone());
+        self.loader.resolver_mut().check_circular(&owner_path)?;
 
+        if !self.loaded_modules.contains_key(&resolved) {
             let module = self.loader.load_file(&resolved)?;
             dependencies.push(resolved.clone());
             self.loaded_modules.insert(resolved.clone(), module);
@@ -280,6 +309,10 @@ impl ModuleProcessor {
     }
 
     fn is_virtual_module(module: &str) -> bool {
+        // Paths always name a file, even one called `math.ot`
+        if module.starts_with('.') || module.starts_with('/') {
+            return false;
+        }
         let namespace_split = module.rsplit(':').next().unwrap_or(module);
         let candidate = namespace_split
             .rsplit(['/', '.'])
@@ -147,7 +147,8 @@ impl ModulePath {
 #[derive(Debug, Default)]
 pub struct DependencyGraph {
     nodes: HashMap<PathBuf, HashSet<PathBuf>>,
-    visiting: HashSet<PathBuf>,
+    /// Path from the root to the node being checked
+    visiting: Vec<PathBuf>,
 }
 
 impl DependencyGraph {
@@ -167,17 +168,23 @@ impl DependencyGraph {
     }
 
     fn dfs_check(&mut self, node: &PathBuf) -> Result<()> {
-        if self.visiting.contains(node) {
-            bail!("circular dependency detected involving {}", node.display())
+        if let Some(start) = self.visiting.iter().position(|visited| visited == node) {
+            let cycle = self.visiting[start..]
+                .iter()
+                .chain([node])
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            bail!("circular import: {}", cycle.join(" -> "))
         }
 
         if let Some(deps) = self.nodes.get(node) {
-            let deps_clone: Vec<PathBuf> = deps.iter().cloned().collect();
-            self.visiting.insert(node.clone());
+            let mut deps_clone: Vec<PathBuf> = deps.iter().cloned().collect();
+            deps_clone.sort();
+            self.visiting.push(node.clone());
             for dep in &deps_clone {
                 self.dfs_check(dep)?;
             }
-            self.visiting.remove(node);
+            self.visiting.pop();
         }
 
         Ok(())
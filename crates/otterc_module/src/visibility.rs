@@ -0,0 +1,147 @@
+//! Visibility of functions across local modules.
+//!
+//! Within a file every function is visible. Through a `use` of another
+//! local file, only the functions it marks `pub` are.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use otterc_ast::nodes::{Expr, Node, Program};
+use otterc_ast::visit::{Visitor, walk_expr};
+use otterc_span::Span;
+use otterc_utils::errors::Diagnostic;
+
+use crate::ModuleProcessor;
+use crate::linker::{LinkedImport, local_imports};
+
+/// A call through an import to a function the module does not export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibilityError {
+    pub module: String,
+    pub function: String,
+    /// Whether the module defines the function without `pub`, rather than
+    /// not defining it at all
+    pub private: bool,
+    pub span: Span,
+}
+
+impl VisibilityError {
+    pub fn to_diagnostic(&self, source_id: &str) -> Diagnostic {
+        let diagnostic = Diagnostic::error(source_id, self.span, self.to_string());
+        if self.private {
+            diagnostic
+                .with_suggestion(format!("mark it `pub fn {}`", self.function))
+                .with_help("Only `pub` functions can be called from other modules.")
+        } else {
+            diagnostic
+        }
+    }
+}
+
+impl fmt::Display for VisibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.private {
+            write!(
+                f,
+                "function `{}` is private to module `{}`",
+                self.function, self.module
+            )
+        } else {
+            write!(
+                f,
+                "module `{}` has no function `{}`",
+                self.module, self.function
+            )
+        }
+    }
+}
+
+impl std::error::Error for VisibilityError {}
+
+/// Finds calls in `program`, a file in `importer_dir`, to functions its
+/// local imports do not export.
+pub fn check_visibility(
+    program: &Program,
+    importer_dir: &Path,
+    processor: &ModuleProcessor,
+) -> Vec<VisibilityError> {
+    let mut checker = VisibilityChecker {
+        imports: local_imports(program, processor, importer_dir),
+        errors: Vec::new(),
+    };
+    if !checker.imports.is_empty() {
+        checker.visit_program(program);
+    }
+    checker.errors
+}
+
+struct VisibilityChecker {
+    imports: HashMap<String, LinkedImport>,
+    errors: Vec<VisibilityError>,
+}
+
+impl Visitor for VisibilityChecker {
+    fn visit_expr(&mut self, expr: &Node<Expr>) {
+        if let Expr::Call { func, .. } = expr.as_ref()
+            && let Expr::Member { object, field } = func.as_ref().as_ref()
+            && let Expr::Identifier(binding) = object.as_ref().as_ref()
+            && let Some(import) = self.imports.get(binding)
+            && !import.public.contains(field)
+            && !import.structs.contains(field)
+        {
+            self.errors.push(VisibilityError {
+                module: import.module.clone(),
+                function: field.clone(),
+                private: import.private.contains(field),
+                span: *func.span(),
+            });
+        }
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::ops::Range;
+    use tempfile::TempDir;
+
+    fn visibility_errors(main: &str) -> Vec<VisibilityError> {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let source_dir = temp_dir.path();
+        fs::write(
+            source_dir.join("helpers.ot"),
+            "pub fn add(a: int, b: int) -> int:\n    return double(a) + b\n\nfn double(x: int) -> int:\n    return x * 2\n",
+        )
+        .expect("write helpers");
+
+        let tokens = otterc_lexer::tokenize(main).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut processor = ModuleProcessor::new(source_dir.to_path_buf(), None);
+        processor
+            .process_imports(&program)
+            .expect("process imports");
+        check_visibility(&program, source_dir, &processor)
+    }
+
+    #[test]
+    fn only_pub_functions_are_callable_through_an_import() {
+        let errors = visibility_errors("use ./helpers\n\nfn main():\n    helpers.add(1, 2)\n");
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let source = "use ./helpers as h\n\nfn main():\n    h.double(1)\n    h.triple(1)\n";
+        let errors = visibility_errors(source);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            "function `double` is private to module `helpers`"
+        );
+        assert_eq!(&source[Range::from(errors[0].span)], "h.double");
+        assert_eq!(
+            errors[1].to_string(),
+            "module `helpers` has no function `triple`"
+        );
+    }
+}
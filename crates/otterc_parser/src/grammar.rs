@@ -1,14 +1,19 @@
 use chumsky::Stream;
+use chumsky::combinator::DelimitedBy;
+use chumsky::error::SimpleReason;
 use chumsky::prelude::*;
+use chumsky::primitive::Just;
 
+use otterc_ast::desugar::desugar_program;
 use otterc_ast::nodes::{
     BinaryOp, Block, EnumVariant, Expr, FStringPart, Function, Literal, MatchArm, Node,
     NumberLiteral, Param, Pattern, Program, Statement, Type, UnaryOp, UseImport,
 };
 
-use otterc_lexer::token::{Token, TokenKind};
+use otterc_lexer::token::{FStringSegment, Token, TokenKind};
 use otterc_span::Span;
 use otterc_utils::errors::{Diagnostic, DiagnosticSeverity};
+use std::num::IntErrorKind;
 use std::ops::Range;
 
 #[derive(Debug, Clone)]
@@ -34,6 +39,10 @@ impl ParserError {
             diag = diag
                 .with_suggestion("Check for missing closing brackets, parentheses, or quotes")
                 .with_help("The parser reached the end of the file while expecting more tokens.");
+        } else if self.message.contains("too large for i64") {
+            diag = diag
+                .with_suggestion("Add a `.0` suffix to write it as a float")
+                .with_help("Integer literals must fit in a signed 64-bit integer.");
         }
 
         diag
@@ -44,7 +53,9 @@ impl From<Simple<TokenKind>> for ParserError {
     fn from(value: Simple<TokenKind>) -> Self {
         let span_range = value.span();
         let span = Span::new(span_range.start, span_range.end);
-        let message = if let Some(found) = value.found() {
+        let message = if let SimpleReason::Custom(message) = value.reason() {
+            message.clone()
+        } else if let Some(found) = value.found() {
             format!("unexpected token: {:?}", found)
         } else {
             "unexpected end of input".to_string()
@@ -53,7 +64,15 @@ impl From<Simple<TokenKind>> for ParserError {
     }
 }
 
+/// Parses `tokens` and desugars the result, ready for type checking.
 pub fn parse(tokens: &[Token]) -> Result<Program, Vec<ParserError>> {
+    let mut program = parse_surface(tokens)?;
+    desugar_program(&mut program);
+    Ok(program)
+}
+
+/// Parses `tokens` without desugaring, keeping the program as written.
+pub fn parse_surface(tokens: &[Token]) -> Result<Program, Vec<ParserError>> {
     let parser = program_parser();
     let eof_span = tokens
         .last()
@@ -73,6 +92,34 @@ pub fn parse(tokens: &[Token]) -> Result<Program, Vec<ParserError>> {
         .map_err(|errors| errors.into_iter().map(ParserError::from).collect())
 }
 
+type Delimiter = Just<TokenKind, TokenKind, Simple<TokenKind>>;
+type Delimited<P> = DelimitedBy<P, Delimiter, Delimiter, TokenKind, TokenKind>;
+
+/// Shorthands for wrapping a parser in a matching pair of delimiter tokens.
+trait DelimitedExt<O>: Parser<TokenKind, O, Error = Simple<TokenKind>> + Sized {
+    /// `( inner )`
+    fn parenthesized(self) -> Delimited<Self> {
+        self.delimited_by(just(TokenKind::LParen), just(TokenKind::RParen))
+    }
+
+    /// `[ inner ]`
+    fn bracketed(self) -> Delimited<Self> {
+        self.delimited_by(just(TokenKind::LBracket), just(TokenKind::RBracket))
+    }
+
+    /// `{ inner }`
+    fn braced(self) -> Delimited<Self> {
+        self.delimited_by(just(TokenKind::LBrace), just(TokenKind::RBrace))
+    }
+
+    /// An indented block: `Indent inner Dedent`
+    fn indented(self) -> Delimited<Self> {
+        self.delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+    }
+}
+
+impl<O, P> DelimitedExt<O> for P where P: Parser<TokenKind, O, Error = Simple<TokenKind>> {}
+
 fn identifier_parser() -> impl Parser<TokenKind, String, Error = Simple<TokenKind>> {
     select! { TokenKind::Identifier(name) => name }
 }
@@ -127,128 +174,120 @@ fn type_parser() -> impl Parser<TokenKind, Node<Type>, Error = Simple<TokenKind>
     })
 }
 
-fn parse_fstring(content: String, span: impl Into<Span>) -> Node<Expr> {
-    use chumsky::Parser;
-
-    // Parse f-string by splitting on braces and parsing expressions
+fn parse_fstring(
+    segments: Vec<FStringSegment>,
+    range: Range<usize>,
+) -> Result<Node<Expr>, Simple<TokenKind>> {
+    let span: Span = range.clone().into();
     let mut parts = Vec::new();
-    let mut current_text = String::new();
-    let mut chars = content.chars().enumerate().peekable();
-    let span: Span = span.into();
-    let span_start = span.start();
-
-    while let Some((i, ch)) = chars.next() {
-        let span_start = span_start + i;
-        match ch {
-            '{' => {
-                if let Some((_, '{')) = chars.peek() {
-                    // Escaped {{
-                    chars.next();
-                    current_text.push('{');
-                } else {
-                    // Expression start
-                    if !current_text.is_empty() {
-                        let s = Span::new(span_start, span_start + current_text.len());
-                        parts.push(Node::new(FStringPart::Text(current_text), s));
-                        current_text = String::new();
-                    }
-
-                    // Parse expression until }
-                    let mut expr_content = String::new();
-
-                    for (_, ch) in chars.by_ref() {
-                        if ch == '}' {
-                            break;
-                        }
-
-                        expr_content.push(ch);
-                    }
 
-                    if !expr_content.is_empty() {
-                        // Parse the expression content using the full expression parser
-                        let trimmed = expr_content.trim();
-                        if !trimmed.is_empty() {
-                            match otterc_lexer::tokenize(trimmed) {
-                                Ok(tokens) => {
-                                    // Create a stream from tokens for the parser
-                                    use chumsky::Stream;
-                                    let end_span =
-                                        tokens.last().map(|t| t.span().end()).unwrap_or(0);
-                                    let stream = Stream::from_iter(
-                                        end_span..end_span + 1,
-                                        tokens.iter().map(|token| {
-                                            (token.kind().clone(), token.span().into())
-                                        }),
-                                    );
-                                    match expr_parser().parse(stream) {
-                                        Ok(expr) => {
-                                            let s = Span::new(
-                                                span_start,
-                                                span_start + expr.span().end(),
-                                            );
-                                            parts.push(Node::new(FStringPart::Expr(expr), s));
-                                        }
-                                        Err(_) => {
-                                            // Fallback to simple identifier if parsing fails
-                                            let s =
-                                                Span::new(span_start, span_start + trimmed.len());
-                                            parts.push(Node::new(
-                                                FStringPart::Expr(Node::new(
-                                                    Expr::Identifier(trimmed.to_string()),
-                                                    s,
-                                                )),
-                                                s,
-                                            ));
-                                        }
-                                    }
-                                }
-                                Err(_) => {
-                                    // Fallback to simple identifier if parsing fails
-                                    let s = Span::new(span_start, span_start + trimmed.len());
-                                    parts.push(Node::new(
-                                        FStringPart::Expr(Node::new(
-                                            Expr::Identifier(trimmed.to_string()),
-                                            s,
-                                        )),
-                                        s,
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                }
+    for segment in segments {
+        match segment {
+            FStringSegment::Text { value, span } => {
+                parts.push(Node::new(FStringPart::Text(value), span));
             }
-            '}' => {
-                if let Some((_, '}')) = chars.peek() {
-                    // Escaped }}
-                    chars.next();
-                    current_text.push('}');
-                } else {
-                    current_text.push('}');
+            FStringSegment::Expr { tokens, span } => {
+                if tokens.is_empty() {
+                    return Err(Simple::custom(range, "empty f-string interpolation"));
                 }
+                // The lexer already tokenized the interpolation against the
+                // enclosing source, so spans carry over unchanged.
+                let stream = Stream::from_iter(
+                    span.end()..span.end() + 1,
+                    tokens
+                        .into_iter()
+                        .map(|token| (token.kind().clone(), token.span().into())),
+                );
+                let expr = expr_parser()
+                    .then_ignore(end())
+                    .parse(stream)
+                    .map_err(|errors| {
+                        let detail = errors.first().and_then(|err| err.found()).map_or_else(
+                            || "unexpected end of expression".to_string(),
+                            |found| format!("unexpected `{}`", found.name()),
+                        );
+                        Simple::custom(
+                            range.clone(),
+                            format!("malformed f-string interpolation: {detail}"),
+                        )
+                    })?;
+                parts.push(Node::new(FStringPart::Expr(expr), span));
             }
-            _ => current_text.push(ch),
         }
     }
 
-    // Add remaining text
-    if !current_text.is_empty() {
-        parts.push(Node::new(FStringPart::Text(current_text), span));
-    }
-
     // If no expressions found, treat as regular string
     if parts
         .iter()
         .all(|part| matches!(part.as_ref(), FStringPart::Text(_)))
-        && let Some(FStringPart::Text(text)) = parts.first().map(|p| p.as_ref())
     {
-        return Node::new(
-            Expr::Literal(Node::new(Literal::String(text.clone()), span)),
+        let text = parts
+            .iter()
+            .filter_map(|part| match part.as_ref() {
+                FStringPart::Text(text) => Some(text.as_str()),
+                FStringPart::Expr(_) => None,
+            })
+            .collect::<String>();
+        return Ok(Node::new(
+            Expr::Literal(Node::new(Literal::String(text), span)),
             span,
-        );
+        ));
     }
 
-    Node::new(Expr::FString { parts }, span)
+    Ok(Node::new(Expr::FString { parts }, span))
+}
+
+/// Parses the text of a number token. Integers that do not fit in `i64` are
+/// rejected rather than silently wrapping or becoming zero.
+fn parse_number_literal(value: &str) -> Result<NumberLiteral, String> {
+    // Remove underscores from the number
+    let clean_value = value.replace('_', "");
+    let radix_digits = |prefixes: [&str; 2]| {
+        prefixes
+            .iter()
+            .find_map(|prefix| clean_value.strip_prefix(prefix))
+    };
+    let radix = if let Some(digits) = radix_digits(["0x", "0X"]) {
+        Some((digits, 16))
+    } else {
+        radix_digits(["0b", "0B"]).map(|digits| (digits, 2))
+    };
+    let is_float_literal =
+        radix.is_none() && (value.contains('.') || value.contains('e') || value.contains('E'));
+    // Check if it contains a decimal point or is an integer
+    if let Some((digits, radix)) = radix {
+        i64::from_str_radix(digits, radix)
+            .map(|int_val| NumberLiteral::new(int_val as f64, false))
+            .map_err(|err| match err.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                    format!("integer literal `{value}` is too large for i64")
+                }
+                _ => format!("invalid integer literal `{value}`: {err}"),
+            })
+    } else if is_float_literal {
+        clean_value
+            .parse()
+            .map(|float_val| NumberLiteral::new(float_val, true))
+            .map_err(|err| format!("invalid float literal `{value}`: {err}"))
+    } else {
+        // Parse as integer
+        clean_value
+            .parse::<i64>()
+            .map(|int_val| NumberLiteral::new(int_val as f64, is_float_literal))
+            .map_err(|err| match err.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                    format!("integer literal `{value}` is too large for i64")
+                }
+                _ => format!("invalid integer literal `{value}`: {err}"),
+            })
+    }
+}
+
+/// Parses a number token, reporting out-of-range integers at its span.
+fn number_literal() -> impl Parser<TokenKind, NumberLiteral, Error = Simple<TokenKind>> + Clone {
+    select! { TokenKind::Number(value) => value }.try_map(|value, span| {
+        parse_number_literal(&value).map_err(|msg| Simple::custom(span, msg))
+    })
 }
 
 fn literal_expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>> {
@@ -258,32 +297,15 @@ fn literal_expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<To
             Node::new(Expr::Literal(Node::new(lit, span)), span)
         })
         .boxed();
-    let number_lit = select! { TokenKind::Number(value) => {
-        // Remove underscores from the number
-        let clean_value = value.replace('_', "");
-        let is_float_literal = value.contains('.') || value.contains('e') || value.contains('E');
-        // Check if it contains a decimal point or is an integer
-        if clean_value.contains('.') {
-            NumberLiteral::new(
-                clean_value.parse().unwrap_or_default(),
-                true,
+    let number_lit = number_literal()
+        .map_with_span(|num_lit, span: Range<usize>| {
+            let span: Span = span.into();
+            Node::new(
+                Expr::Literal(Node::new(Literal::Number(num_lit), span)),
+                span,
             )
-        } else {
-            // Parse as integer
-            match clean_value.parse::<i64>() {
-                Ok(int_val) => NumberLiteral::new(int_val as f64, is_float_literal),
-                Err(_) => NumberLiteral::new(0.0, is_float_literal),
-            }
-        }
-    }}
-    .map_with_span(|num_lit, span: Range<usize>| {
-        let span: Span = span.into();
-        Node::new(
-            Expr::Literal(Node::new(Literal::Number(num_lit), span)),
-            span,
-        )
-    })
-    .boxed();
+        })
+        .boxed();
     let bool_lit = select! {
         TokenKind::True => Literal::Bool(true),
         TokenKind::False => Literal::Bool(false),
@@ -300,8 +322,9 @@ fn literal_expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<To
             Node::new(Expr::Literal(Node::new(lit, span)), span)
         })
         .boxed();
-    let fstring_lit =
-        select! { |span| TokenKind::FString(content) => parse_fstring(content, span) }.boxed();
+    let fstring_lit = select! { TokenKind::FString(segments) => segments }
+        .try_map(parse_fstring)
+        .boxed();
     let unit_lit = just(TokenKind::LParen)
         .then(just(TokenKind::RParen))
         .map_with_span(|_, span: Range<usize>| {
@@ -319,6 +342,12 @@ fn literal_expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<To
     ))
 }
 
+/// A suffix applied to an atom: `.field` or `[index]`
+enum Postfix {
+    Field(String),
+    Index(Node<Expr>),
+}
+
 fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>> {
     recursive(|expr| {
         // Lambda expressions removed - use anonymous fn syntax instead
@@ -333,7 +362,7 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     .separated_by(just(TokenKind::Comma))
                     .at_least(1)
                     .allow_trailing()
-                    .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen)),
+                    .parenthesized(),
             )
             .map_with_span(|(name, fields), span| {
                 Node::new(
@@ -364,7 +393,7 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     span,
                 )
             })
-            .delimited_by(just(TokenKind::LBracket), just(TokenKind::RBracket))
+            .bracketed()
             .boxed();
 
         let dict_comprehension = expr
@@ -388,21 +417,24 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     span,
                 )
             })
-            .delimited_by(just(TokenKind::LBrace), just(TokenKind::RBrace))
+            .braced()
             .boxed();
 
         let atom = choice((
             literal_expr_parser(),
             struct_init_pythonic,
             identifier_parser().map_with_span(|name, span| Node::new(Expr::Identifier(name), span)),
-            expr.clone()
-                .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen)),
+            // `print` is a keyword but is called like any other function
+            just(TokenKind::Print)
+                .to("print".to_string())
+                .map_with_span(|name, span| Node::new(Expr::Identifier(name), span)),
+            expr.clone().parenthesized(),
             list_comprehension,
             // Array literal [expr, expr, ...]
             expr.clone()
                 .separated_by(just(TokenKind::Comma))
                 .allow_trailing()
-                .delimited_by(just(TokenKind::LBracket), just(TokenKind::RBracket))
+                .bracketed()
                 .map_with_span(|array, span| Node::new(Expr::Array(array), span)),
             dict_comprehension,
             // Dictionary literal {key: value, ...}
@@ -411,46 +443,75 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                 .then(expr.clone())
                 .separated_by(just(TokenKind::Comma))
                 .allow_trailing()
-                .delimited_by(just(TokenKind::LBrace), just(TokenKind::RBrace))
+                .braced()
                 .map_with_span(|dict, span| Node::new(Expr::Dict(dict), span)),
         ))
         .boxed();
 
+        // `.field` and `[index]` suffixes, in any order: `a.b[0].c`
         let member_access = atom
             .clone()
             .then(
                 just(TokenKind::Dot)
                     .ignore_then(identifier_or_keyword_parser())
+                    .map(Postfix::Field)
+                    .or(expr.clone().bracketed().map(Postfix::Index))
                     .map_with_span(Node::new)
                     .repeated(),
             )
-            .foldl(|object, field| {
-                let span = object.span().merge(field.span());
-                Node::new(
-                    Expr::Member {
+            .foldl(|object, suffix| {
+                let span = object.span().merge(suffix.span());
+                let expr = match suffix.into_inner() {
+                    Postfix::Field(field) => Expr::Member {
                         object: Box::new(object),
-                        field: field.into_inner(),
+                        field,
                     },
-                    span,
-                )
+                    Postfix::Index(index) => Expr::Index {
+                        base: Box::new(object),
+                        index: Box::new(index),
+                    },
+                };
+                Node::new(expr, span)
             })
             .boxed();
 
+        // Keyword argument `name = value` or a positional expression
+        let call_argument = identifier_parser()
+            .then_ignore(just(TokenKind::Equals))
+            .map(Some)
+            .then(expr.clone())
+            .or(expr.clone().map(|value| (None, value)));
+
         let call_suffix = just(TokenKind::LParen)
             .ignore_then(
-                expr.clone()
+                call_argument
                     .separated_by(just(TokenKind::Comma))
-                    .allow_trailing()
-                    .or_not()
-                    .map(|args| args.unwrap_or_default()),
+                    .allow_trailing(),
             )
             .then_ignore(just(TokenKind::RParen))
+            .try_map(|arguments, _| {
+                let mut args = Vec::new();
+                let mut kwargs = Vec::new();
+                for (name, value) in arguments {
+                    match name {
+                        Some(name) => kwargs.push((name, value)),
+                        None if kwargs.is_empty() => args.push(value),
+                        None => {
+                            return Err(Simple::custom(
+                                Range::from(*value.span()),
+                                "positional argument cannot follow keyword arguments",
+                            ));
+                        }
+                    }
+                }
+                Ok((args, kwargs))
+            })
             .boxed();
 
         let call = member_access
             .clone()
             .then(call_suffix.repeated())
-            .foldl(|func, args| {
+            .foldl(|func, (args, kwargs)| {
                 let span = func
                     .span()
                     .merge(args.last().map(|_| func.span()).unwrap_or(func.span()));
@@ -458,6 +519,7 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     Expr::Call {
                         func: Box::new(func),
                         args,
+                        kwargs,
                     },
                     span,
                 )
@@ -545,15 +607,22 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
             })
             .boxed();
 
+        // `..=` is `..` followed by `=`; the lexer has no dedicated token.
         let range = sum
             .clone()
-            .then(just(TokenKind::DoubleDot).ignore_then(sum.clone()).or_not())
+            .then(
+                just(TokenKind::DoubleDot)
+                    .ignore_then(just(TokenKind::Equals).or_not())
+                    .then(sum.clone())
+                    .or_not(),
+            )
             .map_with_span(|(start, end), span| {
-                if let Some(end) = end {
+                if let Some((inclusive, end)) = end {
                     Node::new(
                         Expr::Range {
                             start: Box::new(start),
                             end: Box::new(end),
+                            inclusive: inclusive.is_some(),
                         },
                         span,
                     )
@@ -630,29 +699,6 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
         // This duplicates some logic from program_parser but is necessary because expr_parser
         // cannot easily access the recursive statement parser from program_parser.
         let match_stmt = recursive(|_stmt| {
-            let print_stmt = just(TokenKind::Print)
-                .ignore_then(
-                    expr.clone()
-                        .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen)),
-                )
-                .map_with_span(|arg, span| {
-                    let span: Span = span.into();
-                    Node::new(
-                        Statement::Expr(Node::new(
-                            Expr::Call {
-                                func: Box::new(Node::new(
-                                    Expr::Identifier("print".to_string()),
-                                    span,
-                                )),
-                                args: vec![arg],
-                            },
-                            span,
-                        )),
-                        span,
-                    )
-                })
-                .boxed();
-
             let return_stmt = just(TokenKind::Return)
                 .ignore_then(expr.clone().or_not())
                 .map_with_span(|expr, span| Node::new(Statement::Return(expr), span))
@@ -688,19 +734,11 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     just(TokenKind::SlashEq).to(BinaryOp::Div),
                 )))
                 .then(expr.clone())
-                .map_with_span(|(((name, name_span), op), rhs), span| {
-                    let span: Span = span.into();
-                    let expr = Node::new(
-                        Expr::Binary {
-                            op,
-                            left: Box::new(Node::new(Expr::Identifier(name.clone()), name_span)),
-                            right: Box::new(rhs),
-                        },
-                        span,
-                    );
+                .map_with_span(|(((name, name_span), op), expr), span| {
                     Node::new(
-                        Statement::Assignment {
+                        Statement::CompoundAssignment {
                             name: Node::new(name, name_span),
+                            op,
                             expr,
                         },
                         span,
@@ -723,15 +761,16 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                 .boxed();
 
             let break_stmt = just(TokenKind::Break)
-                .map_with_span(|_, span| Node::new(Statement::Break, span))
+                .ignore_then(identifier_parser().map_with_span(Node::new).or_not())
+                .map_with_span(|label, span| Node::new(Statement::Break(label), span))
                 .boxed();
 
             let continue_stmt = just(TokenKind::Continue)
-                .map_with_span(|_, span| Node::new(Statement::Continue, span))
+                .ignore_then(identifier_parser().map_with_span(Node::new).or_not())
+                .map_with_span(|label, span| Node::new(Statement::Continue(label), span))
                 .boxed();
 
             choice((
-                print_stmt,
                 return_stmt,
                 let_stmt,
                 assignment_stmt,
@@ -748,6 +787,7 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
 
         let match_case = just(TokenKind::Case)
             .ignore_then(pattern_parser())
+            .then(just(TokenKind::If).ignore_then(logical.clone()).or_not())
             .then_ignore(just(TokenKind::Colon))
             .then_ignore(newline.clone())
             .then(
@@ -755,14 +795,14 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     .clone()
                     .repeated()
                     .at_least(1)
-                    .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+                    .indented()
                     .map_with_span(|block, span| Node::new(Block::new(block), span)),
             )
-            .map_with_span(|(pattern, body), span| {
+            .map_with_span(|((pattern, guard), body), span| {
                 Node::new(
                     MatchArm {
                         pattern,
-                        guard: None,
+                        guard,
                         body,
                     },
                     span,
@@ -771,6 +811,26 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
             .then_ignore(newline.clone().or_not())
             .boxed();
 
+        // `if cond: a else: b` as a value
+        let if_expr = just(TokenKind::If)
+            .ignore_then(logical.clone())
+            .then_ignore(just(TokenKind::Colon))
+            .then(expr.clone())
+            .then_ignore(just(TokenKind::Else))
+            .then_ignore(just(TokenKind::Colon))
+            .then(expr.clone())
+            .map_with_span(|((cond, then_branch), else_branch), span| {
+                Node::new(
+                    Expr::If {
+                        cond: Box::new(cond),
+                        then_branch: Box::new(then_branch),
+                        else_branch: Some(Box::new(else_branch)),
+                    },
+                    span,
+                )
+            })
+            .boxed();
+
         just(TokenKind::Match)
             .ignore_then(logical.clone())
             .then(
@@ -780,15 +840,28 @@ fn expr_parser() -> impl Parser<TokenKind, Node<Expr>, Error = Simple<TokenKind>
                     .ignore_then(match_case.repeated().at_least(1))
                     .then_ignore(just(TokenKind::Dedent)),
             )
-            .map_with_span(|(value, arms), span| {
-                Node::new(
+            .try_map(|(value, arms): (Node<Expr>, Vec<Node<MatchArm>>), span| {
+                // `case _ if cond:` as the final arm looks like a catch-all but
+                // lets values fall through when the guard is false.
+                if let Some(last) = arms.last()
+                    && last.as_ref().guard.is_some()
+                    && matches!(last.as_ref().pattern.as_ref(), Pattern::Wildcard)
+                {
+                    return Err(Simple::custom(
+                        Range::from(*last.span()),
+                        "guarded wildcard arm leaves the match non-exhaustive; add an unguarded `case _:` arm after it",
+                    ));
+                }
+
+                Ok(Node::new(
                     Expr::Match {
                         value: Box::new(value),
                         arms,
                     },
                     span,
-                )
+                ))
             })
+            .or(if_expr)
             .or(logical)
     })
 }
@@ -812,6 +885,64 @@ fn pattern_parser() -> impl Parser<TokenKind, Node<Pattern>, Error = Simple<Toke
             })
             .boxed();
 
+        // `-1` in pattern position folds into a single negative literal whose span
+        // includes the minus sign.
+        let negative_number_pattern = just(TokenKind::Minus)
+            .ignore_then(number_literal())
+            .map_with_span(|number, span: Range<usize>| {
+                let span: Span = span.into();
+                let negated = NumberLiteral::new(-number.value, number.is_float_literal);
+                Node::new(
+                    Pattern::Literal(Node::new(Literal::Number(negated), span)),
+                    span,
+                )
+            })
+            .boxed();
+
+        let range_bound = negative_number_pattern
+            .clone()
+            .or(literal_pattern.clone())
+            .try_map(|pattern, span| match pattern.into_inner() {
+                Pattern::Literal(lit) => Ok(lit),
+                _ => Err(Simple::custom(span, "expected a literal range bound")),
+            })
+            .boxed();
+
+        // `..` or `..=`; the lexer has no dedicated `..=` token.
+        let range_op = just(TokenKind::DoubleDot)
+            .ignore_then(just(TokenKind::Equals).or_not())
+            .map(|eq| eq.is_some())
+            .boxed();
+
+        // `start..end`, `start..=end`, `start..`, `..end`, `..=end`.
+        let range_pattern = choice((
+            range_bound
+                .clone()
+                .then(range_op.clone())
+                .then(range_bound.clone().or_not())
+                .map(|((start, inclusive), end)| (Some(start), inclusive, end)),
+            range_op
+                .then(range_bound)
+                .map(|(inclusive, end)| (None, inclusive, Some(end))),
+        ))
+        .try_map(|(start, inclusive, end), span: Range<usize>| {
+            if inclusive && end.is_none() {
+                return Err(Simple::custom(
+                    span,
+                    "inclusive range pattern requires an end bound",
+                ));
+            }
+            Ok(Node::new(
+                Pattern::Range {
+                    start,
+                    end,
+                    inclusive,
+                },
+                span,
+            ))
+        })
+        .boxed();
+
         let identifier_pattern = identifier_parser()
             .map_with_span(|ident, span| Node::new(Pattern::Identifier(ident), span))
             .boxed();
@@ -874,7 +1005,7 @@ fn pattern_parser() -> impl Parser<TokenKind, Node<Pattern>, Error = Simple<Toke
             .clone()
             .separated_by(just(TokenKind::Comma))
             .allow_trailing()
-            .delimited_by(just(TokenKind::LBracket), just(TokenKind::RBracket))
+            .bracketed()
             .then(
                 just(TokenKind::DoubleDot)
                     .ignore_then(identifier_parser())
@@ -885,41 +1016,74 @@ fn pattern_parser() -> impl Parser<TokenKind, Node<Pattern>, Error = Simple<Toke
             })
             .boxed();
 
-        choice((
+        let single_pattern = choice((
             wildcard,
+            range_pattern,
+            negative_number_pattern,
             literal_pattern,
             enum_variant_pattern,
             struct_pattern,
             array_pattern,
             identifier_pattern,
-        ))
+        ));
+
+        // `a | b | c`; a lone pattern is returned as-is rather than wrapped.
+        single_pattern
+            .separated_by(just(TokenKind::Pipe))
+            .at_least(1)
+            .map_with_span(|mut alternatives, span| {
+                if alternatives.len() == 1 {
+                    alternatives.remove(0)
+                } else {
+                    Node::new(Pattern::Or(alternatives), span)
+                }
+            })
     })
 }
 
+type ParamParts = (
+    ((Option<TokenKind>, Node<String>), Option<Node<Type>>),
+    Option<Node<Expr>>,
+);
+
+fn variadic_param(
+    (((star, name), ty), default): ParamParts,
+    span: Range<usize>,
+) -> Result<Node<Param>, Simple<TokenKind>> {
+    if star.is_none() {
+        return Ok(Node::new(Param::new(name, ty, default), span));
+    }
+    if default.is_some() {
+        return Err(Simple::custom(
+            span,
+            "variadic parameter cannot have a default value",
+        ));
+    }
+    Ok(Node::new(Param::new_variadic(name, ty), span))
+}
+
+fn check_variadic_last(
+    params: Vec<Node<Param>>,
+    _span: Range<usize>,
+) -> Result<Vec<Node<Param>>, Simple<TokenKind>> {
+    let last = params.len().saturating_sub(1);
+    match params
+        .iter()
+        .enumerate()
+        .find(|(index, param)| param.as_ref().variadic && *index != last)
+    {
+        Some((_, param)) => Err(Simple::custom(
+            Range::from(*param.span()),
+            "variadic parameter must be the last parameter",
+        )),
+        None => Ok(params),
+    }
+}
+
 fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>> {
     let newline = just(TokenKind::Newline).repeated().at_least(1);
     let expr = expr_parser().boxed();
 
-    let print_stmt = just(TokenKind::Print)
-        .ignore_then(
-            expr.clone()
-                .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen)),
-        )
-        .map_with_span(|arg, span| {
-            let span: Span = span.into();
-            Node::new(
-                Statement::Expr(Node::new(
-                    Expr::Call {
-                        func: Box::new(Node::new(Expr::Identifier("print".to_string()), span)),
-                        args: vec![arg],
-                    },
-                    span,
-                )),
-                span,
-            )
-        })
-        .boxed();
-
     let return_stmt = just(TokenKind::Return)
         .ignore_then(expr.clone().or_not())
         .map_with_span(|expr, span| Node::new(Statement::Return(expr), span))
@@ -964,20 +1128,12 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
             just(TokenKind::SlashEq).to(BinaryOp::Div),
         )))
         .then(expr.clone())
-        .map_with_span(|(((name, name_span), op), rhs), span| {
-            let span: Span = span.into();
-            // Desugar: x += y becomes x = x + y
-            let expr = Node::new(
-                Expr::Binary {
-                    op,
-                    left: Box::new(Node::new(Expr::Identifier(name.clone()), name_span)),
-                    right: Box::new(rhs),
-                },
-                span,
-            );
+        .map_with_span(|(((name, name_span), op), expr), span| {
+            // Kept as written; `desugar_program` lowers it after parsing
             Node::new(
-                Statement::Assignment {
+                Statement::CompoundAssignment {
                     name: Node::new(name, name_span),
+                    op,
                     expr,
                 },
                 span,
@@ -1061,10 +1217,12 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
         .boxed();
 
     let break_stmt = just(TokenKind::Break)
-        .map_with_span(|_, span| Node::new(Statement::Break, span))
+        .ignore_then(identifier_parser().map_with_span(Node::new).or_not())
+        .map_with_span(|label, span| Node::new(Statement::Break(label), span))
         .boxed();
     let continue_stmt = just(TokenKind::Continue)
-        .map_with_span(|_, span| Node::new(Statement::Continue, span))
+        .ignore_then(identifier_parser().map_with_span(Node::new).or_not())
+        .map_with_span(|label, span| Node::new(Statement::Continue(label), span))
         .boxed();
     let pass_stmt = just(TokenKind::Pass)
         .map_with_span(|_, span| Node::new(Statement::Pass, span))
@@ -1080,7 +1238,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
                 stmt.clone()
                     .repeated()
                     .at_least(1)
-                    .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+                    .indented()
                     .map_with_span(|block, span| Node::new(Block::new(block), span)),
             )
             .map(|(cond, block)| (cond, block))
@@ -1094,7 +1252,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
                 stmt.clone()
                     .repeated()
                     .at_least(1)
-                    .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+                    .indented()
                     .map_with_span(|block, span| Node::new(Block::new(block), span)),
             )
             .then(elif_block.repeated())
@@ -1106,7 +1264,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
                         stmt.clone()
                             .repeated()
                             .at_least(1)
-                            .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+                            .indented()
                             .map_with_span(|block, span| Node::new(Block::new(block), span)),
                     )
                     .or_not(),
@@ -1134,7 +1292,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
                 stmt.clone()
                     .repeated()
                     .at_least(1)
-                    .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+                    .indented()
                     .map_with_span(|block, span| Node::new(Block::new(block), span)),
             )
             .map_with_span(|((var, iterable), body), span| {
@@ -1157,7 +1315,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
                 stmt.clone()
                     .repeated()
                     .at_least(1)
-                    .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+                    .indented()
                     .map_with_span(|block, span| Node::new(Block::new(block), span)),
             )
             .map_with_span(|(cond, body), span| Node::new(Statement::While { cond, body }, span))
@@ -1166,7 +1324,6 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
         // Exception handling (try/except/finally/raise) removed - use Result<T, E> pattern matching instead
 
         choice((
-            print_stmt,
             return_stmt,
             let_stmt,
             compound_assignment_stmt,
@@ -1190,12 +1347,13 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
         .clone()
         .repeated()
         .at_least(1)
-        .delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent))
+        .indented()
         .map_with_span(|block, span| Node::new(Block::new(block), span))
         .boxed();
 
-    let function_param = identifier_parser()
-        .map_with_span(Node::new)
+    let function_param = just(TokenKind::Star)
+        .or_not()
+        .then(identifier_parser().map_with_span(Node::new))
         .then(choice((
             just(TokenKind::Colon).ignore_then(type_parser()).map(Some),
             empty().to(None),
@@ -1204,13 +1362,14 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
             just(TokenKind::Equals).ignore_then(expr.clone()).map(Some),
             empty().to(None),
         )))
-        .map_with_span(|((name, ty), default), span| Node::new(Param::new(name, ty, default), span))
+        .try_map(variadic_param)
         .boxed();
 
     let function_params = function_param
         .separated_by(just(TokenKind::Comma))
         .allow_trailing()
-        .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen))
+        .try_map(check_variadic_last)
+        .parenthesized()
         .or_not()
         .map(|params| params.unwrap_or_default());
 
@@ -1265,7 +1424,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
                     type_parser()
                         .separated_by(just(TokenKind::Comma))
                         .allow_trailing()
-                        .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen)),
+                        .parenthesized(),
                 )
                 .or_not(),
         )
@@ -1300,8 +1459,9 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
 
     // Method definition (fn method(self, ...) -> ReturnType: ...)
     // Recreate parsers for method definition
-    let method_function_param = identifier_parser()
-        .map_with_span(Node::new)
+    let method_function_param = just(TokenKind::Star)
+        .or_not()
+        .then(identifier_parser().map_with_span(Node::new))
         .then(choice((
             just(TokenKind::Colon).ignore_then(type_parser()).map(Some),
             empty().to(None),
@@ -1310,13 +1470,14 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
             just(TokenKind::Equals).ignore_then(expr.clone()).map(Some),
             empty().to(None),
         )))
-        .map_with_span(|((name, ty), default), span| Node::new(Param::new(name, ty, default), span))
+        .try_map(variadic_param)
         .boxed();
 
     let method_function_params = method_function_param
         .separated_by(just(TokenKind::Comma))
         .allow_trailing()
-        .delimited_by(just(TokenKind::LParen), just(TokenKind::RParen))
+        .try_map(check_variadic_last)
+        .parenthesized()
         .or_not()
         .map(|params| params.unwrap_or_default())
         .boxed();
@@ -1380,7 +1541,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
         .then(struct_generics())
         .then_ignore(just(TokenKind::Colon))
         .then_ignore(newline.clone())
-        .then(struct_body.delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent)))
+        .then(struct_body.indented())
         .then_ignore(newline.clone().or_not())
         .map_with_span(
             |((((pub_kw, _), name), generics), (fields, methods)), span| {
@@ -1405,7 +1566,7 @@ fn program_parser() -> impl Parser<TokenKind, Program, Error = Simple<TokenKind>
         .then(struct_generics())
         .then_ignore(just(TokenKind::Colon))
         .then_ignore(newline.clone())
-        .then(enum_body.delimited_by(just(TokenKind::Indent), just(TokenKind::Dedent)))
+        .then(enum_body.indented())
         .then_ignore(newline.clone().or_not())
         .map_with_span(|((((pub_kw, _), name), generics), variants), span| {
             Node::new(
@@ -1500,6 +1661,531 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_negative_number_pattern() {
+        let source = "match n:\n    case -1:\n        pass\n    case _:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize match");
+        let program = parse(&tokens).expect("parse match");
+
+        let Statement::Expr(expr) = program.statements[0].as_ref() else {
+            panic!("expected expression statement");
+        };
+        let Expr::Match { arms, .. } = expr.as_ref() else {
+            panic!("expected match expression");
+        };
+        let pattern = &arms[0].as_ref().pattern;
+        match pattern.as_ref() {
+            Pattern::Literal(lit) => {
+                assert_eq!(
+                    lit.as_ref(),
+                    &Literal::Number(NumberLiteral::new(-1.0, false))
+                );
+            }
+            other => panic!("expected literal pattern, got {:?}", other),
+        }
+        // The span starts at the minus sign.
+        assert_eq!(&source[Range::from(*pattern.span())], "-1");
+    }
+
+    #[test]
+    fn out_of_range_integer_literal_is_reported_at_its_span() {
+        let source = "let big = 99999999999999999999\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize literal");
+        let Err(errors) = parse(&tokens) else {
+            panic!("expected an out-of-range integer to be rejected");
+        };
+        let error = errors
+            .iter()
+            .find(|error| error.message.contains("too large for i64"))
+            .unwrap_or_else(|| panic!("no overflow diagnostic in {errors:?}"));
+        assert_eq!(&source[Range::from(error.span)], "99999999999999999999");
+
+        let tokens = otterc_lexer::tokenize("let max = 9_223_372_036_854_775_807\n")
+            .expect("tokenize literal");
+        assert!(parse(&tokens).is_ok());
+    }
+
+    fn let_number_values(source: &str) -> Vec<(f64, bool)> {
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize literals");
+        let program = parse(&tokens).expect("parse literals");
+        program
+            .statements
+            .iter()
+            .map(|stmt| match stmt.as_ref() {
+                Statement::Let { expr, .. } => match expr.as_ref() {
+                    Expr::Literal(lit) => match lit.as_ref() {
+                        Literal::Number(number) => (number.value, number.is_float_literal),
+                        other => panic!("expected number, got {other:?}"),
+                    },
+                    other => panic!("expected literal, got {other:?}"),
+                },
+                other => panic!("expected let, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hex_and_binary_literals_parse_to_their_integer_values() {
+        assert_eq!(
+            let_number_values("let a = 0xFF_FF\nlet b = 0b1_010\n"),
+            [(65535.0, false), (10.0, false)]
+        );
+    }
+
+    #[test]
+    fn float_literals_with_exponents_are_floats() {
+        assert_eq!(
+            let_number_values("let a = 1.5e3\nlet b = 3.0E-4\nlet c = 2e2\nlet d = 5.\n"),
+            [(1500.0, true), (0.0003, true), (200.0, true), (5.0, true)]
+        );
+    }
+
+    #[test]
+    fn compound_assignment_is_desugared_after_parsing() {
+        let source = "fn main():\n    total -= step * 2\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize compound assignment");
+        let body = |program: Program| {
+            let Statement::Function(func) = program.statements[0].as_ref() else {
+                panic!("expected function");
+            };
+            func.as_ref().body.as_ref().statements[0].as_ref().clone()
+        };
+
+        let surface = body(parse_surface(&tokens).expect("parse compound assignment"));
+        let Statement::CompoundAssignment { name, op, .. } = surface else {
+            panic!("expected compound assignment, got {surface:?}");
+        };
+        assert_eq!(name.as_ref(), "total");
+        assert_eq!(op, BinaryOp::Sub);
+
+        let lowered = body(parse(&tokens).expect("parse compound assignment"));
+        let Statement::Assignment { name, expr } = lowered else {
+            panic!("expected assignment, got {lowered:?}");
+        };
+        assert_eq!(name.as_ref(), "total");
+        let Expr::Binary { op, left, right } = expr.as_ref() else {
+            panic!("expected binary expression, got {expr:?}");
+        };
+        assert_eq!(*op, BinaryOp::Sub);
+        assert!(matches!(left.as_ref().as_ref(), Expr::Identifier(target) if target == "total"));
+        assert_eq!(&source[Range::from(*right.span())], "step * 2");
+    }
+
+    #[test]
+    fn parses_range_patterns() {
+        let source = "match n:\n    case 1..10:\n        pass\n    case -5..=0:\n        pass\n    case ..-10:\n        pass\n    case 100..:\n        pass\n    case 42:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize match");
+        let program = parse(&tokens).expect("parse match");
+
+        let Statement::Expr(expr) = program.statements[0].as_ref() else {
+            panic!("expected expression statement");
+        };
+        let Expr::Match { arms, .. } = expr.as_ref() else {
+            panic!("expected match expression");
+        };
+
+        let bound = |lit: &Option<Node<Literal>>| lit.as_ref().map(|lit| lit.as_ref().clone());
+        let number = |value: f64| Some(Literal::Number(NumberLiteral::new(value, false)));
+        let expected = [
+            (number(1.0), number(10.0), false, "1..10"),
+            (number(-5.0), number(0.0), true, "-5..=0"),
+            (None, number(-10.0), false, "..-10"),
+            (number(100.0), None, false, "100.."),
+        ];
+        for (arm, (start, end, inclusive, text)) in arms.iter().zip(expected) {
+            let pattern = &arm.as_ref().pattern;
+            match pattern.as_ref() {
+                Pattern::Range {
+                    start: actual_start,
+                    end: actual_end,
+                    inclusive: actual_inclusive,
+                } => {
+                    assert_eq!(bound(actual_start), start);
+                    assert_eq!(bound(actual_end), end);
+                    assert_eq!(*actual_inclusive, inclusive);
+                }
+                other => panic!("expected range pattern, got {:?}", other),
+            }
+            assert_eq!(&source[Range::from(*pattern.span())], text);
+        }
+        assert!(matches!(
+            arms[4].as_ref().pattern.as_ref(),
+            Pattern::Literal(_)
+        ));
+    }
+
+    #[test]
+    fn parses_or_patterns() {
+        let source = "match n:\n    case 1 | 2 | 3:\n        pass\n    case x:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize match");
+        let program = parse(&tokens).expect("parse match");
+
+        let Statement::Expr(expr) = program.statements[0].as_ref() else {
+            panic!("expected expression statement");
+        };
+        let Expr::Match { arms, .. } = expr.as_ref() else {
+            panic!("expected match expression");
+        };
+        let pattern = &arms[0].as_ref().pattern;
+        match pattern.as_ref() {
+            Pattern::Or(alternatives) => {
+                assert_eq!(alternatives.len(), 3);
+                assert!(
+                    alternatives
+                        .iter()
+                        .all(|alt| matches!(alt.as_ref(), Pattern::Literal(_)))
+                );
+            }
+            other => panic!("expected or-pattern, got {:?}", other),
+        }
+        assert_eq!(&source[Range::from(*pattern.span())], "1 | 2 | 3");
+        // A single alternative stays a plain pattern.
+        assert!(matches!(
+            arms[1].as_ref().pattern.as_ref(),
+            Pattern::Identifier(_)
+        ));
+    }
+
+    #[test]
+    fn parses_match_guard() {
+        let source = "match n:\n    case x if x > 0:\n        pass\n    case _:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize match");
+        let program = parse(&tokens).expect("parse match");
+
+        let Statement::Expr(expr) = program.statements[0].as_ref() else {
+            panic!("expected expression statement");
+        };
+        let Expr::Match { arms, .. } = expr.as_ref() else {
+            panic!("expected match expression");
+        };
+        let guard = arms[0].as_ref().guard.as_ref().expect("guard on first arm");
+        assert!(matches!(
+            guard.as_ref(),
+            Expr::Binary {
+                op: BinaryOp::Gt,
+                ..
+            }
+        ));
+        assert!(arms[1].as_ref().guard.is_none());
+    }
+
+    #[test]
+    fn rejects_guarded_wildcard_as_last_arm() {
+        let source = "match n:\n    case 1:\n        pass\n    case _ if n > 0:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize match");
+        let errors = parse(&tokens).expect_err("guarded catch-all should be rejected");
+        assert!(
+            errors
+                .iter()
+                .any(|err| err.message.contains("guarded wildcard")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn parses_keyword_arguments() {
+        let source = "connect(host, port = 8080, secure = true)\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize call");
+        let program = parse(&tokens).expect("parse call");
+
+        let Statement::Expr(expr) = program.statements[0].as_ref() else {
+            panic!("expected expression statement");
+        };
+        let Expr::Call { args, kwargs, .. } = expr.as_ref() else {
+            panic!("expected call expression");
+        };
+        assert_eq!(args.len(), 1);
+        let names: Vec<_> = kwargs.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["port", "secure"]);
+    }
+
+    #[test]
+    fn rejects_positional_argument_after_keyword() {
+        let source = "connect(port = 8080, host)\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize call");
+        let errors = parse(&tokens).expect_err("positional after keyword should be rejected");
+        assert!(
+            errors
+                .iter()
+                .any(|err| err.message.contains("positional argument cannot follow")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn parses_variadic_parameter() {
+        let source = "fn log(level: str, *args: str):\n    pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize function");
+        let program = parse(&tokens).expect("parse function");
+
+        let Statement::Function(function) = program.statements[0].as_ref() else {
+            panic!("expected function");
+        };
+        let params = &function.as_ref().params;
+        assert!(!params[0].as_ref().variadic);
+        assert!(params[1].as_ref().variadic);
+        assert_eq!(params[1].as_ref().name.as_ref(), "args");
+    }
+
+    #[test]
+    fn rejects_variadic_parameter_before_others() {
+        let source = "fn log(*args, level: str):\n    pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize function");
+        let errors = parse(&tokens).expect_err("variadic must be last");
+        assert!(
+            errors
+                .iter()
+                .any(|err| err.message.contains("must be the last parameter")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn parses_labeled_break() {
+        let source = "for row in rows:\n    for cell in row:\n        break row\n    continue\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize loops");
+        let program = parse(&tokens).expect("parse loops");
+
+        let Statement::For { body: outer, .. } = program.statements[0].as_ref() else {
+            panic!("expected for loop");
+        };
+        let Statement::For { body: inner, .. } = outer.as_ref().statements[0].as_ref() else {
+            panic!("expected nested for loop");
+        };
+        let Statement::Break(Some(label)) = inner.as_ref().statements[0].as_ref() else {
+            panic!("expected labeled break");
+        };
+        assert_eq!(label.as_ref(), "row");
+        assert!(matches!(
+            outer.as_ref().statements[1].as_ref(),
+            Statement::Continue(None)
+        ));
+    }
+
+    #[test]
+    fn parses_index_between_member_accesses() {
+        let source = "let x = a.b[i + 1].c\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize index");
+        let program = parse(&tokens).expect("parse index");
+
+        let Statement::Let { expr, .. } = program.statements[0].as_ref() else {
+            panic!("expected let");
+        };
+        let Expr::Member { object, field } = expr.as_ref() else {
+            panic!("expected member access");
+        };
+        assert_eq!(field, "c");
+        let Expr::Index { base, index } = object.as_ref().as_ref() else {
+            panic!("expected index");
+        };
+        assert_eq!(&source[Range::from(*base.span())], "a.b");
+        assert_eq!(&source[Range::from(*index.span())], "i + 1");
+        assert_eq!(&source[Range::from(*object.span())], "a.b[i + 1]");
+    }
+
+    #[test]
+    fn print_takes_any_number_of_arguments() {
+        let source = "print()\nprint(x, y)\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize print");
+        let program = parse(&tokens).expect("parse print");
+
+        let arg_counts: Vec<usize> = program
+            .statements
+            .iter()
+            .map(|stmt| match stmt.as_ref() {
+                Statement::Expr(expr) => match expr.as_ref() {
+                    Expr::Call { func, args, .. } => {
+                        assert!(matches!(func.as_ref().as_ref(), Expr::Identifier(name) if name == "print"));
+                        args.len()
+                    }
+                    other => panic!("expected call, got {other:?}"),
+                },
+                other => panic!("expected expression statement, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(arg_counts, [0, 2]);
+    }
+
+    #[test]
+    fn parses_if_expression() {
+        let source = "let x = if n > 3: n * 2 else: 0\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize if expression");
+        let program = parse(&tokens).expect("parse if expression");
+
+        let Statement::Let { expr, .. } = program.statements[0].as_ref() else {
+            panic!("expected let");
+        };
+        let Expr::If {
+            cond,
+            then_branch,
+            else_branch: Some(else_branch),
+        } = expr.as_ref()
+        else {
+            panic!("expected if expression with else");
+        };
+        assert_eq!(&source[Range::from(*cond.span())], "n > 3");
+        assert_eq!(&source[Range::from(*then_branch.span())], "n * 2");
+        assert_eq!(&source[Range::from(*else_branch.span())], "0");
+    }
+
+    #[test]
+    fn parses_list_literals() {
+        let source = "let empty = []\nlet xs = [1, 2, 3,]\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize lists");
+        let program = parse(&tokens).expect("parse lists");
+
+        let lengths: Vec<usize> = program
+            .statements
+            .iter()
+            .map(|stmt| match stmt.as_ref() {
+                Statement::Let { expr, .. } => match expr.as_ref() {
+                    Expr::Array(elements) => elements.len(),
+                    other => panic!("expected list literal, got {other:?}"),
+                },
+                other => panic!("expected let, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(lengths, [0, 3]);
+    }
+
+    #[test]
+    fn parses_dict_literals() {
+        let source = "let empty = {}\nlet ages = {\"a\": 1, \"b\": 2,}\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize dicts");
+        let program = parse(&tokens).expect("parse dicts");
+
+        let Statement::Let { expr, .. } = program.statements[0].as_ref() else {
+            panic!("expected let");
+        };
+        assert!(matches!(expr.as_ref(), Expr::Dict(pairs) if pairs.is_empty()));
+
+        let Statement::Let { expr, .. } = program.statements[1].as_ref() else {
+            panic!("expected let");
+        };
+        let Expr::Dict(pairs) = expr.as_ref() else {
+            panic!("expected dict literal");
+        };
+        let entries: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(key, value)| {
+                (
+                    &source[Range::from(*key.span())],
+                    &source[Range::from(*value.span())],
+                )
+            })
+            .collect();
+        assert_eq!(entries, [("\"a\"", "1"), ("\"b\"", "2")]);
+    }
+
+    #[test]
+    fn parses_while_loop() {
+        let source = "while x < 10:\n    x = x + 1\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize while");
+        let program = parse(&tokens).expect("parse while");
+
+        let Statement::While { cond, body } = program.statements[0].as_ref() else {
+            panic!("expected while loop");
+        };
+        assert_eq!(&source[Range::from(*cond.span())], "x < 10");
+        assert!(matches!(
+            body.as_ref().statements.as_slice(),
+            [stmt] if matches!(stmt.as_ref(), Statement::Assignment { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_elif_chain_in_order() {
+        let source =
+            "if x < 0:\n    a()\nelif x == 0:\n    b()\nelif x < 10:\n    c()\nelse:\n    d()\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize if");
+        let program = parse(&tokens).expect("parse if");
+
+        let Statement::If {
+            elif_blocks,
+            else_block,
+            ..
+        } = program.statements[0].as_ref()
+        else {
+            panic!("expected if statement");
+        };
+        let conditions: Vec<&str> = elif_blocks
+            .iter()
+            .map(|(cond, _)| &source[Range::from(*cond.span())])
+            .collect();
+        assert_eq!(conditions, ["x == 0", "x < 10"]);
+        assert!(else_block.is_some());
+    }
+
+    #[test]
+    fn parses_inclusive_range_expression() {
+        let source = "let r = 1..=10\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize range");
+        let program = parse(&tokens).expect("parse range");
+
+        let Statement::Let { expr, .. } = program.statements[0].as_ref() else {
+            panic!("expected let");
+        };
+        assert!(matches!(
+            expr.as_ref(),
+            Expr::Range {
+                inclusive: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn fstring_interpolations_parse_into_spanned_expressions() {
+        let source = "let s = f\"{{a}} {x + 1}!\"\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize f-string");
+        let program = parse(&tokens).expect("parse f-string");
+        let Statement::Let { expr, .. } = program.statements[0].as_ref() else {
+            panic!("expected let");
+        };
+        let Expr::FString { parts } = expr.as_ref() else {
+            panic!("expected an f-string, got {expr:?}");
+        };
+        let [text, interpolation, tail] = &parts[..] else {
+            panic!("expected three parts, got {parts:?}");
+        };
+        assert!(matches!(text.as_ref(), FStringPart::Text(text) if text == "{a} "));
+        assert!(matches!(tail.as_ref(), FStringPart::Text(text) if text == "!"));
+        let FStringPart::Expr(inner) = interpolation.as_ref() else {
+            panic!("expected an interpolation, got {interpolation:?}");
+        };
+        assert!(matches!(
+            inner.as_ref(),
+            Expr::Binary {
+                op: BinaryOp::Add,
+                ..
+            }
+        ));
+        assert_eq!(&source[Range::from(*interpolation.span())], "{x + 1}");
+        assert_eq!(&source[Range::from(*inner.span())], "x + 1");
+    }
+
+    #[test]
+    fn rejects_malformed_fstring_interpolation() {
+        let source = "print(f\"total: {a + }\")\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize f-string");
+        let errors = parse(&tokens).expect_err("malformed interpolation should be rejected");
+        let error = errors
+            .iter()
+            .find(|err| err.message.contains("malformed f-string interpolation"))
+            .unwrap_or_else(|| panic!("unexpected errors: {:?}", errors));
+        assert_eq!(&source[Range::from(error.span)], "f\"total: {a + }\"");
+    }
+
+    #[test]
+    fn rejects_open_inclusive_range_pattern() {
+        let source = "match n:\n    case 0..=:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize match");
+        assert!(parse(&tokens).is_err());
+    }
+
     #[test]
     fn parses_core_stdlib_module() {
         let source = include_str!("../../../stdlib/otter/core.ot");
@@ -1,3 +1,3 @@
 pub mod grammar;
 
-pub use grammar::{ParserError, parse};
+pub use grammar::{ParserError, parse, parse_surface};
@@ -8,6 +8,8 @@ use parking_lot::RwLock;
 
 use otterc_symbol::registry::{FfiFunction, FfiSignature, FfiType, SymbolRegistry};
 
+use crate::strings::format_float;
+
 // ============================================================================
 // Built-in Collections Registry
 // For lists and maps, we'll use opaque handles
@@ -158,13 +160,7 @@ fn value_to_string(value: &Value) -> String {
         Value::Unit => "None".to_string(),
         Value::Bool(b) => b.to_string(),
         Value::I64(i) => i.to_string(),
-        Value::F64(f) => {
-            if f.fract() == 0.0 {
-                (*f as i64).to_string()
-            } else {
-                f.to_string()
-            }
-        }
+        Value::F64(f) => format_float(*f),
         Value::String(s) => s.clone(),
         Value::List(handle) => stringify_list_handle(*handle),
         Value::Map(handle) => stringify_map_handle(*handle),
@@ -186,6 +182,18 @@ pub extern "C" fn otter_runtime_list_length(handle: u64) -> i64 {
     otter_builtin_len_list(handle)
 }
 
+/// Target of the bounds checks codegen emits before indexing; reports the
+/// offending index and aborts.
+#[expect(
+    clippy::print_stderr,
+    reason = "Out-of-bounds indexing is fatal and reported on stderr"
+)]
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_panic_index_out_of_bounds(index: i64, len: i64) -> ! {
+    eprintln!("panic: index out of bounds: the len is {len} but the index is {index}");
+    std::process::abort()
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn otter_runtime_list_get(handle: u64, index: i64) -> u64 {
     list_value(handle, index)
@@ -200,13 +208,14 @@ fn map_value(handle: HandleId, key: &str) -> Option<Value> {
 }
 
 fn stringify_list_handle(handle: HandleId) -> String {
-    let lists = LISTS.read();
-    if let Some(list) = lists.get(&handle) {
-        let items = list.items.iter().map(value_to_string).collect::<Vec<_>>();
-        format!("[{}]", items.join(", "))
-    } else {
-        "[]".to_string()
-    }
+    // Copy the items out first: nested lists take the lock again.
+    let items = LISTS
+        .read()
+        .get(&handle)
+        .map(|list| list.items.clone())
+        .unwrap_or_default();
+    let items = items.iter().map(value_to_string).collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
 }
 
 fn stringify_map_handle(handle: HandleId) -> String {
@@ -1325,7 +1334,7 @@ pub extern "C" fn otter_builtin_stringify_int(value: i64) -> *mut c_char {
 
 #[unsafe(no_mangle)]
 pub extern "C" fn otter_builtin_stringify_float(value: f64) -> *mut c_char {
-    CString::new(value.to_string())
+    CString::new(format_float(value))
         .ok()
         .map(CString::into_raw)
         .unwrap_or(std::ptr::null_mut())
@@ -1363,20 +1372,17 @@ pub unsafe extern "C" fn otter_builtin_stringify_string(s: *const c_char) -> *mu
 
 #[unsafe(no_mangle)]
 pub extern "C" fn otter_builtin_stringify_list(handle: u64) -> *mut c_char {
-    let lists = LISTS.read();
-    if let Some(list) = lists.get(&handle) {
-        let items: Vec<String> = list.items.iter().map(value_to_string).collect();
-        let json = format!("[{}]", items.join(", "));
-        CString::new(json)
-            .ok()
-            .map(CString::into_raw)
-            .unwrap_or(std::ptr::null_mut())
-    } else {
-        CString::new("[]")
-            .ok()
-            .map(CString::into_raw)
-            .unwrap_or(std::ptr::null_mut())
-    }
+    otter_std_fmt_list(handle)
+}
+
+/// Formats a list as `[a, b, c]`, rendering each element the way an
+/// f-string would. The caller frees the result with `otter_runtime_free_string`.
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_std_fmt_list(handle: u64) -> *mut c_char {
+    CString::new(stringify_list_handle(handle))
+        .ok()
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
 }
 
 #[unsafe(no_mangle)]
@@ -1626,6 +1632,12 @@ fn register_builtin_symbols(registry: &SymbolRegistry) {
         signature: FfiSignature::new(vec![FfiType::List, FfiType::I64], FfiType::I64),
     });
 
+    registry.register(FfiFunction {
+        name: "runtime.panic_index_out_of_bounds".into(),
+        symbol: "otter_panic_index_out_of_bounds".into(),
+        signature: FfiSignature::new(vec![FfiType::I64, FfiType::I64], FfiType::Unit),
+    });
+
     registry.register(FfiFunction {
         name: "map.new".into(),
         symbol: "otter_builtin_map_new".into(),
@@ -1876,41 +1888,55 @@ fn register_builtin_symbols(registry: &SymbolRegistry) {
         signature: FfiSignature::new(vec![FfiType::Opaque], FfiType::Str),
     });
 
-    // stringify() functions
+    // stringify() functions, behind `str(x)`. Each result is a fresh string
+    // the caller frees.
     registry.register(FfiFunction {
         name: "stringify<int>".into(),
         symbol: "otter_builtin_stringify_int".into(),
-        signature: FfiSignature::new(vec![FfiType::I64], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::I64], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
     });
 
     registry.register(FfiFunction {
         name: "stringify<float>".into(),
         symbol: "otter_builtin_stringify_float".into(),
-        signature: FfiSignature::new(vec![FfiType::F64], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::F64], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
     });
 
     registry.register(FfiFunction {
         name: "stringify<bool>".into(),
         symbol: "otter_builtin_stringify_bool".into(),
-        signature: FfiSignature::new(vec![FfiType::Bool], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::Bool], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
     });
 
     registry.register(FfiFunction {
         name: "stringify<string>".into(),
         symbol: "otter_builtin_stringify_string".into(),
-        signature: FfiSignature::new(vec![FfiType::Str], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::Str], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
     });
 
     registry.register(FfiFunction {
         name: "stringify<list>".into(),
         symbol: "otter_builtin_stringify_list".into(),
-        signature: FfiSignature::new(vec![FfiType::List], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::List], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
+    });
+
+    registry.register(FfiFunction {
+        name: "fmt.list".into(),
+        symbol: "otter_std_fmt_list".into(),
+        signature: FfiSignature::new(vec![FfiType::List], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
     });
 
     registry.register(FfiFunction {
         name: "stringify<map>".into(),
         symbol: "otter_builtin_stringify_map".into(),
-        signature: FfiSignature::new(vec![FfiType::Map], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::Map], FfiType::Str)
+            .with_release("otter_runtime_free_string"),
     });
 
     // Async/await functions
@@ -1935,28 +1961,28 @@ fn register_builtin_symbols(registry: &SymbolRegistry) {
     registry.register(FfiFunction {
         name: "gc.alloc".into(),
         symbol: "otter_alloc".into(),
-        signature: FfiSignature {
-            params: vec![FfiType::I64], // size
-            result: FfiType::Opaque,    // ptr
-        },
+        signature: FfiSignature::new(
+            vec![FfiType::I64], // size
+            FfiType::Opaque,    // ptr
+        ),
     });
 
     registry.register(FfiFunction {
         name: "gc.add_root".into(),
         symbol: "otter_gc_add_root".into(),
-        signature: FfiSignature {
-            params: vec![FfiType::Opaque], // ptr
-            result: FfiType::Unit,
-        },
+        signature: FfiSignature::new(
+            vec![FfiType::Opaque], // ptr
+            FfiType::Unit,
+        ),
     });
 
     registry.register(FfiFunction {
         name: "gc.remove_root".into(),
         symbol: "otter_gc_remove_root".into(),
-        signature: FfiSignature {
-            params: vec![FfiType::Opaque], // ptr
-            result: FfiType::Unit,
-        },
+        signature: FfiSignature::new(
+            vec![FfiType::Opaque], // ptr
+            FfiType::Unit,
+        ),
     });
 
     registry.register(FfiFunction {
@@ -2234,3 +2260,48 @@ inventory::submit! {
         register: register_builtin_symbols,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iterate_string(source: &str) -> Vec<String> {
+        let source = CString::new(source).unwrap();
+        let iter = unsafe { otter_builtin_iter_string(source.as_ptr()) };
+        let mut items = Vec::new();
+        while otter_builtin_iter_has_next_string(iter) {
+            let encoded = otter_builtin_iter_next_string(iter);
+            assert!(matches!(decode_value_kind(encoded), ValueKind::String));
+            let ptr = decode_value_handle(encoded) as *mut c_char;
+            items.push(unsafe { CString::from_raw(ptr) }.into_string().unwrap());
+        }
+        otter_builtin_iter_free_string(iter);
+        items
+    }
+
+    fn format_list(handle: u64) -> String {
+        let ptr = otter_std_fmt_list(handle);
+        unsafe { CString::from_raw(ptr) }.into_string().unwrap()
+    }
+
+    #[test]
+    fn test_fmt_list_formats_nested_and_empty_lists() {
+        let empty = otter_builtin_list_new();
+        assert_eq!(format_list(empty), "[]");
+
+        let inner = otter_builtin_list_new();
+        otter_builtin_append_list_int(inner, 2);
+        otter_builtin_append_list_int(inner, 3);
+        let outer = otter_builtin_list_new();
+        otter_builtin_append_list_int(outer, 1);
+        otter_builtin_append_list_list(outer, inner);
+        otter_builtin_append_list_list(outer, empty);
+        assert_eq!(format_list(outer), "[1, [2, 3], []]");
+    }
+
+    #[test]
+    fn test_string_iteration_yields_characters() {
+        assert_eq!(iterate_string("aé€"), vec!["a", "é", "€"]);
+        assert!(iterate_string("").is_empty());
+    }
+}
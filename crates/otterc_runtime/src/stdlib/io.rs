@@ -9,6 +9,8 @@ use parking_lot::RwLock;
 
 use otterc_symbol::registry::{FfiFunction, FfiSignature, FfiType, SymbolRegistry};
 
+use crate::memory::profiler::get_profiler;
+
 // ============================================================================
 // Buffer Management
 // ============================================================================
@@ -110,13 +112,31 @@ pub extern "C" fn otter_std_io_read_line() -> *mut c_char {
         Ok(0) | Err(_) => std::ptr::null_mut(),
         Ok(_) => {
             let trimmed = line.trim_end_matches(['\n', '\r']).to_string();
-            CString::new(trimmed)
-                .map(CString::into_raw)
-                .unwrap_or_else(|_| std::ptr::null_mut())
+            hand_over_string(trimmed, "std.io.read_line")
         }
     }
 }
 
+/// Hands `text` to the caller, who frees it with `otter_std_io_free_string`.
+/// While the memory profiler runs, the string counts as a live allocation
+/// until then.
+fn hand_over_string(text: String, function: &str) -> *mut c_char {
+    let Ok(text) = CString::new(text) else {
+        return std::ptr::null_mut();
+    };
+    let size = text.as_bytes_with_nul().len();
+    let ptr = text.into_raw();
+    get_profiler().record_allocation(
+        ptr as usize,
+        size,
+        Some(function.to_string()),
+        None,
+        None,
+        Some("str".to_string()),
+    );
+    ptr
+}
+
 /// attempts to free the string pointed to by `ptr`
 ///
 /// # Safety
@@ -127,6 +147,7 @@ pub unsafe extern "C" fn otter_std_io_free_string(ptr: *mut c_char) {
     if ptr.is_null() {
         return;
     }
+    get_profiler().record_deallocation(ptr as usize);
     unsafe {
         drop(CString::from_raw(ptr));
     }
@@ -146,10 +167,7 @@ pub unsafe extern "C" fn otter_std_io_read(path: *const c_char) -> *mut c_char {
     let path_str = unsafe { CStr::from_ptr(path).to_str().unwrap_or("").to_string() };
 
     match fs::read_to_string(&path_str) {
-        Ok(content) => CString::new(content)
-            .ok()
-            .map(CString::into_raw)
-            .unwrap_or(std::ptr::null_mut()),
+        Ok(content) => hand_over_string(content, "io.read"),
         Err(_) => std::ptr::null_mut(),
     }
 }
@@ -569,7 +587,7 @@ fn register_std_io_symbols(registry: &SymbolRegistry) {
     registry.register(FfiFunction {
         name: "std.io.read_line".into(),
         symbol: "otter_std_io_read_line".into(),
-        signature: FfiSignature::new(vec![], FfiType::Str),
+        signature: FfiSignature::new(vec![], FfiType::Str).with_release("otter_std_io_free_string"),
     });
 
     registry.register(FfiFunction {
@@ -581,7 +599,8 @@ fn register_std_io_symbols(registry: &SymbolRegistry) {
     registry.register(FfiFunction {
         name: "io.read".into(),
         symbol: "otter_std_io_read".into(),
-        signature: FfiSignature::new(vec![FfiType::Str], FfiType::Str),
+        signature: FfiSignature::new(vec![FfiType::Str], FfiType::Str)
+            .with_release("otter_std_io_free_string"),
     });
 
     registry.register(FfiFunction {
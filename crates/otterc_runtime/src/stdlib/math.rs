@@ -91,6 +91,31 @@ pub extern "C" fn otter_std_math_lerp(a: f64, b: f64, t: f64) -> f64 {
     a + (b - a) * t
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_std_math_inf() -> f64 {
+    f64::INFINITY
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_std_math_nan() -> f64 {
+    f64::NAN
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_std_math_is_nan(value: f64) -> bool {
+    value.is_nan()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_std_math_is_inf(value: f64) -> bool {
+    value.is_infinite()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn otter_std_math_is_finite(value: f64) -> bool {
+    value.is_finite()
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn otter_std_math_randf() -> f64 {
     // Use simple pseudo-random (for deterministic, use rand module)
@@ -272,6 +297,36 @@ fn register_std_math_symbols(registry: &SymbolRegistry) {
         signature: FfiSignature::new(vec![FfiType::F64, FfiType::F64, FfiType::F64], FfiType::F64),
     });
 
+    registry.register(FfiFunction {
+        name: "math.inf".into(),
+        symbol: "otter_std_math_inf".into(),
+        signature: FfiSignature::new(vec![], FfiType::F64),
+    });
+
+    registry.register(FfiFunction {
+        name: "math.nan".into(),
+        symbol: "otter_std_math_nan".into(),
+        signature: FfiSignature::new(vec![], FfiType::F64),
+    });
+
+    registry.register(FfiFunction {
+        name: "math.is_nan".into(),
+        symbol: "otter_std_math_is_nan".into(),
+        signature: FfiSignature::new(vec![FfiType::F64], FfiType::Bool),
+    });
+
+    registry.register(FfiFunction {
+        name: "math.is_inf".into(),
+        symbol: "otter_std_math_is_inf".into(),
+        signature: FfiSignature::new(vec![FfiType::F64], FfiType::Bool),
+    });
+
+    registry.register(FfiFunction {
+        name: "math.is_finite".into(),
+        symbol: "otter_std_math_is_finite".into(),
+        signature: FfiSignature::new(vec![FfiType::F64], FfiType::Bool),
+    });
+
     registry.register(FfiFunction {
         name: "math.randf".into(),
         symbol: "otter_std_math_randf".into(),
@@ -310,3 +365,17 @@ inventory::submit! {
         register: register_std_math_symbols,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infinities_compare_ordered() {
+        let inf = otter_std_math_inf();
+        assert!(otter_std_math_is_inf(inf) && otter_std_math_is_inf(-inf));
+        assert!(inf == inf && -inf < inf && inf > f64::MAX);
+        assert!(otter_std_math_is_nan(inf - inf));
+        assert!(otter_std_math_is_finite(1.0));
+    }
+}
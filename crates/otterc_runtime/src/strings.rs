@@ -4,13 +4,19 @@ use std::os::raw::c_char;
 use crate::memory::gc::{ObjectKind, get_gc};
 use otterc_symbol::registry::{FfiFunction, FfiSignature, FfiType, SymbolRegistry};
 
+/// Formatting policy for floats everywhere the runtime prints them: the
+/// shortest string that parses back to the same `f64` (Rust's `{}`), so
+/// `3.0` prints as `3`, `0.1` as `0.1`, and `0.1 + 0.2` as
+/// `0.30000000000000004`. The C runtimes linked into builds without this
+/// library (`otterc_codegen`'s `runtimes/*.c`) print floats the same way.
+pub fn format_float(value: f64) -> String {
+    value.to_string()
+}
+
 /// Format a float value to string
 #[unsafe(no_mangle)]
 pub extern "C" fn otter_format_float(value: f64) -> *mut c_char {
-    let formatted = format!("{:.9}", value)
-        .trim_end_matches('0')
-        .trim_end_matches('.')
-        .to_string();
+    let formatted = format_float(value);
     let s = CString::new(formatted)
         .map(CString::into_raw)
         .unwrap_or_else(|_| std::ptr::null_mut());
@@ -252,6 +258,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_float_round_trips() {
+        let cases = [
+            (3.0, "3"),
+            (-3.0, "-3"),
+            (0.5, "0.5"),
+            (0.1, "0.1"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (1.0 / 3.0, "0.3333333333333333"),
+            (1e-7, "0.0000001"),
+            (123456789.125, "123456789.125"),
+        ];
+        for (value, expected) in cases {
+            let formatted = format_float(value);
+            assert_eq!(formatted, expected);
+            assert_eq!(formatted.parse::<f64>().unwrap(), value);
+        }
+    }
+
     #[test]
     fn test_format_int() {
         let result = otter_format_int(42);
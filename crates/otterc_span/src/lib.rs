@@ -4,9 +4,12 @@
 //!
 //! This API is completely unstable and subject to change.
 
+use core::cmp::Ordering;
 use core::ops::Range;
 
 /// A range typically used to define a slice of source-text.
+///
+/// Spans are ordered by their start, then by their end, which is source order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Span {
     /// The end of the span.
@@ -31,6 +34,13 @@ impl Span {
     pub const fn new(start: usize, end: usize) -> Self {
         Self { end, start }
     }
+
+    /// Creates the same span moved `offset` bytes later in the source
+    #[inline]
+    #[must_use]
+    pub const fn shifted(&self, offset: usize) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
 }
 
 // methods
@@ -72,6 +82,22 @@ impl Span {
     }
 }
 
+impl Ord for Span {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then_with(|| self.end.cmp(&other.end))
+    }
+}
+
+impl PartialOrd for Span {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl From<Span> for Range<usize> {
     #[inline]
     fn from(span: Span) -> Self {
@@ -85,3 +111,36 @@ impl From<Range<usize>> for Span {
         Self::new(range.start, range.end)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_by_start_then_end() {
+        let mut spans = vec![
+            Span::new(4, 6),
+            Span::new(0, 9),
+            Span::new(4, 5),
+            Span::new(0, 2),
+        ];
+        spans.sort();
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(0, 2),
+                Span::new(0, 9),
+                Span::new(4, 5),
+                Span::new(4, 6)
+            ]
+        );
+        // The field order (`end` first) does not leak into the ordering
+        assert!(Span::new(1, 10) < Span::new(2, 3));
+    }
+
+    #[test]
+    fn layout_is_two_words() {
+        assert_eq!(size_of::<Span>(), 2 * size_of::<usize>());
+        assert_eq!(align_of::<Span>(), align_of::<usize>());
+    }
+}
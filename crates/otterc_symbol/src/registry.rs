@@ -60,15 +60,28 @@ impl fmt::Display for FfiType {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FfiSignature {
     pub params: Vec<FfiType>,
     pub result: FfiType,
+    /// Symbol that frees a `str` result handed over to the caller. `None`
+    /// means the callee keeps ownership and the caller must not free it.
+    pub release: Option<String>,
 }
 
 impl FfiSignature {
     pub fn new(params: Vec<FfiType>, result: FfiType) -> Self {
-        Self { params, result }
+        Self {
+            params,
+            result,
+            release: None,
+        }
+    }
+
+    /// Marks the `str` result as caller-owned, to be freed with `symbol`.
+    pub fn with_release(mut self, symbol: impl Into<String>) -> Self {
+        self.release = Some(symbol.into());
+        self
     }
 }
 
@@ -94,8 +107,153 @@ pub struct FfiFunction {
 
 type ModuleRegistrar = fn(&SymbolRegistry);
 
+/// Why a call could not be matched to one overload of a registry function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OverloadError {
+    /// No function is registered under the name.
+    Unknown { name: String },
+    /// No overload accepts the call's arguments.
+    NoMatch { name: String, arity: usize },
+    /// Several overloads accept the call's arguments equally well.
+    Ambiguous {
+        name: String,
+        candidates: Vec<FfiSignature>,
+    },
+}
+
+impl fmt::Display for OverloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverloadError::Unknown { name } => write!(f, "unknown function `{name}`"),
+            OverloadError::NoMatch { name, arity } => write!(
+                f,
+                "no overload of `{name}` accepts {arity} argument(s) of these types"
+            ),
+            OverloadError::Ambiguous { name, candidates } => {
+                let candidates = candidates
+                    .iter()
+                    .map(|sig| sig.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "call to `{name}` is ambiguous between {candidates}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OverloadError {}
+
+/// How well an argument of type `arg` fits a parameter of type `param`, or
+/// `None` if it cannot be passed. Unknown argument types fit anything.
+fn argument_score(param: &FfiType, arg: Option<&FfiType>) -> Option<u32> {
+    let Some(arg) = arg else {
+        return Some(0);
+    };
+    match (param, arg) {
+        _ if param == arg => Some(2),
+        (FfiType::Opaque, _) | (FfiType::I32, FfiType::I64) | (FfiType::I64, FfiType::I32) => {
+            Some(1)
+        }
+        _ => None,
+    }
+}
+
+/// Module aliases introduced by a program's `use` imports, mapping the name
+/// the program refers to a module by to the module's full path, so that
+/// `use std.io as io` lets `io.println` resolve to `std.io.println`.
+#[derive(Clone, Debug, Default)]
+pub struct AliasMap {
+    aliases: AHashMap<String, String>,
+}
+
+/// An alias that would hide a top-level function of the same name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AliasError {
+    pub alias: String,
+}
+
+impl fmt::Display for AliasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "module alias `{}` shadows the top-level function `{}`",
+            self.alias, self.alias
+        )
+    }
+}
+
+impl std::error::Error for AliasError {}
+
+impl AliasMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the alias introduced by `use <module> [as <alias>]`. Without
+    /// an explicit alias the module is known by its last path segment.
+    /// Imports that are not registry modules (`rust:` crates, Otter source
+    /// modules) are ignored.
+    pub fn insert_import(
+        &mut self,
+        module: &str,
+        alias: Option<&str>,
+        registry: &SymbolRegistry,
+    ) -> Result<(), AliasError> {
+        let module = module.trim();
+        if module.is_empty()
+            || module.starts_with("rust:")
+            || module.starts_with('.')
+            || module.starts_with('/')
+            || module.contains('\\')
+        {
+            return Ok(());
+        }
+        // `std/io` and `std:io` both name the registry path `std.io`
+        let path = module.replace(['/', ':'], ".");
+        let last = path.rsplit('.').next().unwrap_or(&path);
+        if !registry.has_module(last) {
+            return Ok(());
+        }
+        self.insert(alias.unwrap_or(last), &path, registry)
+    }
+
+    /// Makes `alias` refer to the module at `path`, unless it would shadow a
+    /// top-level function in `registry`.
+    pub fn insert(
+        &mut self,
+        alias: &str,
+        path: &str,
+        registry: &SymbolRegistry,
+    ) -> Result<(), AliasError> {
+        if alias == path {
+            return Ok(());
+        }
+        if registry.contains(alias) {
+            return Err(AliasError {
+                alias: alias.to_string(),
+            });
+        }
+        self.aliases.insert(alias.to_string(), path.to_string());
+        Ok(())
+    }
+
+    /// Rewrites a dotted name whose first segment is an alias to the full
+    /// module path, e.g. `io.println` to `std.io.println`.
+    pub fn rewrite(&self, name: &str) -> Option<String> {
+        let (head, rest) = name.split_once('.')?;
+        let path = self.aliases.get(head)?;
+        Some(format!("{path}.{rest}"))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+}
+
 pub struct SymbolRegistry {
-    functions: RwLock<AHashMap<String, FfiFunction>>,
+    /// Every signature registered under a name, in registration order. The
+    /// first one is what [`SymbolRegistry::resolve`] returns.
+    functions: RwLock<AHashMap<String, Vec<FfiFunction>>>,
     lazy_modules: RwLock<AHashMap<String, Vec<ModuleRegistrar>>>,
     active_modules: RwLock<HashSet<String>>,
 }
@@ -121,10 +279,26 @@ impl SymbolRegistry {
         &GLOBAL_SYMBOL_REGISTRY
     }
 
+    /// Registers `function`, replacing every signature previously
+    /// registered under its name.
     pub fn register(&self, function: FfiFunction) {
         self.functions
             .write()
-            .insert(function.name.clone(), function);
+            .insert(function.name.clone(), vec![function]);
+    }
+
+    /// Registers `function` as an additional overload of its name. An
+    /// existing overload with the same parameter types is replaced.
+    pub fn register_overload(&self, function: FfiFunction) {
+        let mut guard = self.functions.write();
+        let overloads = guard.entry(function.name.clone()).or_default();
+        match overloads
+            .iter_mut()
+            .find(|existing| existing.signature.params == function.signature.params)
+        {
+            Some(existing) => *existing = function,
+            None => overloads.push(function),
+        }
     }
 
     pub fn register_many<I>(&self, functions: I)
@@ -133,7 +307,7 @@ impl SymbolRegistry {
     {
         let mut guard = self.functions.write();
         for function in functions {
-            guard.insert(function.name.clone(), function);
+            guard.insert(function.name.clone(), vec![function]);
         }
     }
 
@@ -183,10 +357,203 @@ impl SymbolRegistry {
     }
 
     pub fn resolve(&self, name: &str) -> Option<FfiFunction> {
-        self.functions.read().get(name).cloned()
+        self.functions
+            .read()
+            .get(name)
+            .and_then(|overloads| overloads.first())
+            .cloned()
+    }
+
+    /// Every signature registered under `name`.
+    pub fn overloads(&self, name: &str) -> Vec<FfiFunction> {
+        self.functions.read().get(name).cloned().unwrap_or_default()
+    }
+
+    /// Whether more than one signature is registered under `name`.
+    pub fn is_overloaded(&self, name: &str) -> bool {
+        self.functions
+            .read()
+            .get(name)
+            .is_some_and(|overloads| overloads.len() > 1)
+    }
+
+    /// Picks the overload of `name` that best fits a call whose arguments
+    /// have types `args`, where `None` marks a type that is not known. An
+    /// exact type match beats passing to an `opaque` parameter or between
+    /// `i32` and `i64`. A name with a single signature resolves to it
+    /// whatever the arguments.
+    pub fn resolve_call(
+        &self,
+        name: &str,
+        args: &[Option<FfiType>],
+    ) -> Result<FfiFunction, OverloadError> {
+        let overloads = self.overloads(name);
+        if overloads.is_empty() {
+            return Err(OverloadError::Unknown {
+                name: name.to_string(),
+            });
+        }
+        if let [function] = overloads.as_slice() {
+            return Ok(function.clone());
+        }
+
+        let mut scored: Vec<(u32, FfiFunction)> = overloads
+            .into_iter()
+            .filter(|function| function.signature.params.len() == args.len())
+            .filter_map(|function| {
+                let score = function
+                    .signature
+                    .params
+                    .iter()
+                    .zip(args)
+                    .map(|(param, arg)| argument_score(param, arg.as_ref()))
+                    .sum::<Option<u32>>()?;
+                Some((score, function))
+            })
+            .collect();
+        let Some(best) = scored.iter().map(|(score, _)| *score).max() else {
+            return Err(OverloadError::NoMatch {
+                name: name.to_string(),
+                arity: args.len(),
+            });
+        };
+        scored.retain(|(score, _)| *score == best);
+        if scored.len() > 1 {
+            return Err(OverloadError::Ambiguous {
+                name: name.to_string(),
+                candidates: scored
+                    .into_iter()
+                    .map(|(_, function)| function.signature)
+                    .collect(),
+            });
+        }
+        Ok(scored.remove(0).1)
+    }
+
+    /// Resolves `name`, falling back to its alias-rewritten form when no
+    /// function is registered under `name` itself. The returned function
+    /// carries its full registered name.
+    pub fn resolve_with_aliases(&self, name: &str, aliases: &AliasMap) -> Option<FfiFunction> {
+        self.resolve(name)
+            .or_else(|| aliases.rewrite(name).and_then(|full| self.resolve(&full)))
     }
 
     pub fn all(&self) -> Vec<FfiFunction> {
-        self.functions.read().values().cloned().collect()
+        self.functions.read().values().flatten().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry_with(names: &[&str]) -> SymbolRegistry {
+        let registry = SymbolRegistry::new();
+        registry.mark_module_active("io");
+        for name in names {
+            registry.register(FfiFunction {
+                name: (*name).to_string(),
+                symbol: format!("otter_{}", name.replace('.', "_")),
+                signature: FfiSignature::new(vec![FfiType::Str], FfiType::Unit),
+            });
+        }
+        registry
+    }
+
+    #[test]
+    fn resolves_calls_through_module_aliases() {
+        let registry = registry_with(&["std.io.println", "io.read"]);
+        let mut aliases = AliasMap::new();
+        aliases
+            .insert_import("std/io", Some("out"), &registry)
+            .unwrap();
+        aliases.insert_import("std:io", None, &registry).unwrap();
+
+        let resolved = registry.resolve_with_aliases("out.println", &aliases);
+        assert_eq!(resolved.map(|f| f.name).as_deref(), Some("std.io.println"));
+        let resolved = registry.resolve_with_aliases("io.println", &aliases);
+        assert_eq!(resolved.map(|f| f.name).as_deref(), Some("std.io.println"));
+        // A name registered as written wins over the alias
+        let resolved = registry.resolve_with_aliases("io.read", &aliases);
+        assert_eq!(resolved.map(|f| f.name).as_deref(), Some("io.read"));
+    }
+
+    #[test]
+    fn rejects_aliases_shadowing_top_level_functions() {
+        let registry = registry_with(&["print", "std.io.println"]);
+        let mut aliases = AliasMap::new();
+        assert_eq!(
+            aliases.insert_import("std/io", Some("print"), &registry),
+            Err(AliasError {
+                alias: "print".to_string()
+            })
+        );
+        assert!(aliases.is_empty());
+    }
+
+    fn print_overload(params: Vec<FfiType>, symbol: &str) -> FfiFunction {
+        FfiFunction {
+            name: "print".to_string(),
+            symbol: symbol.to_string(),
+            signature: FfiSignature::new(params, FfiType::Unit),
+        }
+    }
+
+    #[test]
+    fn resolves_overloads_by_arity_and_argument_types() {
+        let registry = SymbolRegistry::new();
+        registry.register_overload(print_overload(vec![], "otter_print_newline"));
+        registry.register_overload(print_overload(vec![FfiType::Str], "otter_print_str"));
+        registry.register_overload(print_overload(vec![FfiType::I64], "otter_print_int"));
+        registry.register_overload(print_overload(
+            vec![FfiType::Str, FfiType::Str],
+            "otter_print_two",
+        ));
+
+        let symbol =
+            |args: &[Option<FfiType>]| registry.resolve_call("print", args).map(|f| f.symbol);
+        assert_eq!(symbol(&[]).as_deref(), Ok("otter_print_newline"));
+        assert_eq!(
+            symbol(&[Some(FfiType::Str)]).as_deref(),
+            Ok("otter_print_str")
+        );
+        assert_eq!(
+            symbol(&[Some(FfiType::I32)]).as_deref(),
+            Ok("otter_print_int")
+        );
+        assert_eq!(
+            symbol(&[None, Some(FfiType::Str)]).as_deref(),
+            Ok("otter_print_two")
+        );
+        assert_eq!(
+            symbol(&[Some(FfiType::F64)]),
+            Err(OverloadError::NoMatch {
+                name: "print".to_string(),
+                arity: 1
+            })
+        );
+        assert!(matches!(
+            symbol(&[None]),
+            Err(OverloadError::Ambiguous { candidates, .. }) if candidates.len() == 2
+        ));
+        assert_eq!(registry.all().len(), 4);
+        assert_eq!(
+            registry.resolve("print").map(|f| f.symbol).as_deref(),
+            Some("otter_print_newline")
+        );
+    }
+
+    #[test]
+    fn register_replaces_all_overloads() {
+        let registry = SymbolRegistry::new();
+        registry.register_overload(print_overload(vec![], "otter_print_newline"));
+        registry.register_overload(print_overload(vec![FfiType::Str], "otter_print_str"));
+        assert!(registry.is_overloaded("print"));
+
+        registry.register(print_overload(vec![FfiType::Str], "otter_print"));
+        assert!(!registry.is_overloaded("print"));
+        // A single signature is used whatever the arguments
+        let resolved = registry.resolve_call("print", &[Some(FfiType::F64), None]);
+        assert_eq!(resolved.map(|f| f.symbol).as_deref(), Ok("otter_print"));
     }
 }
@@ -5,18 +5,22 @@ use crate::types::{
     EnumDefinition, EnumLayout, StructDefinition, TypeContext, TypeError, TypeInfo,
 };
 use otterc_ast::nodes::{
-    BinaryOp, Block, Expr, FStringPart, Function, Literal, Node, Pattern, Program, Statement, Type,
-    UnaryOp, UseImport,
+    BinaryOp, Block, Expr, FStringPart, Function, Literal, MatchArm, Node, Pattern, Program,
+    Statement, Type, UnaryOp, UseImport,
 };
 use otterc_config::LanguageFeatureFlags;
 use otterc_span::Span;
-use otterc_symbol::registry::{FfiType, SymbolRegistry};
+use otterc_symbol::registry::{AliasMap, FfiFunction, FfiType, SymbolRegistry};
 
 /// Type checker that validates and infers types in OtterLang programs
 pub struct TypeChecker {
     errors: Vec<TypeError>,
+    /// Lint findings that do not fail type checking
+    warnings: Vec<TypeError>,
     context: TypeContext,
     registry: Option<&'static SymbolRegistry>,
+    /// Module aliases from `use` imports, for resolving registry functions
+    aliases: AliasMap,
     expr_types: HashMap<usize, TypeInfo>,
     expr_types_by_span: HashMap<Span, TypeInfo>,
     expr_spans: HashMap<usize, Span>,
@@ -26,6 +30,20 @@ pub struct TypeChecker {
     features: LanguageFeatureFlags,
     /// Current function's return type (if inside a function)
     current_function_return_type: Option<TypeInfo>,
+    /// Name of the function whose body is being checked
+    current_function_name: Option<String>,
+    /// Labels of the enclosing loops, innermost last (`None` for `while`)
+    loop_labels: Vec<Option<String>>,
+    /// Set while checking a `match` in statement position, whose value is discarded
+    match_value_discarded: bool,
+}
+
+/// Parameter view of a callee used to bind call arguments
+struct CallSignature<'a> {
+    params: &'a [TypeInfo],
+    defaults: &'a [bool],
+    names: &'a [String],
+    variadic: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -153,8 +171,10 @@ impl TypeChecker {
 
         Self {
             errors: Vec::new(),
+            warnings: Vec::new(),
             context,
             registry: None,
+            aliases: AliasMap::new(),
             expr_types: HashMap::new(),
             expr_types_by_span: HashMap::new(),
             expr_spans: HashMap::new(),
@@ -163,6 +183,9 @@ impl TypeChecker {
             method_expr_ids: HashMap::new(),
             features,
             current_function_return_type: None,
+            current_function_name: None,
+            loop_labels: Vec::new(),
+            match_value_discarded: false,
         }
     }
 
@@ -179,6 +202,8 @@ impl TypeChecker {
             TypeInfo::Function {
                 params: vec![TypeInfo::Str],
                 param_defaults: vec![false],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::Unit),
             },
         );
@@ -189,6 +214,8 @@ impl TypeChecker {
             TypeInfo::Function {
                 params: vec![TypeInfo::Str],
                 param_defaults: vec![false],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::Unit),
             },
         );
@@ -198,6 +225,8 @@ impl TypeChecker {
             TypeInfo::Function {
                 params: vec![TypeInfo::Unknown],
                 param_defaults: vec![false],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::Str),
             },
         );
@@ -208,6 +237,8 @@ impl TypeChecker {
             TypeInfo::Function {
                 params: vec![TypeInfo::Unknown],
                 param_defaults: vec![false],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::I64),
             },
         );
@@ -218,6 +249,8 @@ impl TypeChecker {
             TypeInfo::Function {
                 params: vec![TypeInfo::Str],
                 param_defaults: vec![false],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::I64),
             },
         );
@@ -228,6 +261,8 @@ impl TypeChecker {
             TypeInfo::Function {
                 params: vec![TypeInfo::Str],
                 param_defaults: vec![false],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::Unit),
             },
         );
@@ -342,13 +377,14 @@ impl TypeChecker {
         match stmt {
             Statement::Expr(expr)
             | Statement::Let { expr, .. }
-            | Statement::Assignment { expr, .. } => {
+            | Statement::Assignment { expr, .. }
+            | Statement::CompoundAssignment { expr, .. } => {
                 self.collect_metadata_in_expr(expr, spans, expr_ids);
             }
             Statement::Return(Some(expr)) => self.collect_metadata_in_expr(expr, spans, expr_ids),
             Statement::Return(None)
-            | Statement::Break
-            | Statement::Continue
+            | Statement::Break(_)
+            | Statement::Continue(_)
             | Statement::Pass
             | Statement::Use { .. }
             | Statement::PubUse { .. }
@@ -423,7 +459,11 @@ impl TypeChecker {
                     self.collect_metadata_in_expr(cond, spans, expr_ids);
                 }
             }
-            Expr::Binary { left, right, .. } => {
+            Expr::Binary { left, right, .. }
+            | Expr::Index {
+                base: left,
+                index: right,
+            } => {
                 self.collect_metadata_in_expr(left, spans, expr_ids);
                 self.collect_metadata_in_expr(right, spans, expr_ids);
             }
@@ -433,9 +473,9 @@ impl TypeChecker {
             | Expr::Member { object: expr, .. } => {
                 self.collect_metadata_in_expr(expr, spans, expr_ids);
             }
-            Expr::Call { func, args } => {
+            Expr::Call { func, args, kwargs } => {
                 self.collect_metadata_in_expr(func, spans, expr_ids);
-                for arg in args {
+                for arg in args.iter().chain(kwargs.iter().map(|(_, arg)| arg)) {
                     self.collect_metadata_in_expr(arg, spans, expr_ids);
                 }
             }
@@ -459,7 +499,7 @@ impl TypeChecker {
                     self.collect_metadata_in_block(arm.as_ref().body.as_ref(), spans, expr_ids);
                 }
             }
-            Expr::Range { start, end } => {
+            Expr::Range { start, end, .. } => {
                 self.collect_metadata_in_expr(start, spans, expr_ids);
                 self.collect_metadata_in_expr(end, spans, expr_ids);
             }
@@ -589,6 +629,13 @@ impl TypeChecker {
                 TypeInfo::Unknown
             };
 
+            if param.as_ref().variadic {
+                // `*args` collects the remaining arguments and may receive none
+                param_types.push(TypeInfo::List(Box::new(resolved_type)));
+                param_defaults.push(true);
+                continue;
+            }
+
             param_types.push(resolved_type.clone());
 
             if let Some(default_expr) = &param.as_ref().default {
@@ -636,10 +683,180 @@ impl TypeChecker {
         TypeInfo::Function {
             params: param_types,
             param_defaults,
+            param_names: function
+                .as_ref()
+                .params
+                .iter()
+                .map(|param| param.as_ref().name.as_ref().clone())
+                .collect(),
+            variadic: function
+                .as_ref()
+                .params
+                .last()
+                .is_some_and(|param| param.as_ref().variadic),
             return_type: Box::new(return_type),
         }
     }
 
+    /// Bind positional and keyword arguments to parameters and check their types.
+    /// Returns `false` when the arguments cannot be bound to the signature.
+    fn check_call_arguments(
+        &mut self,
+        signature: CallSignature<'_>,
+        args: &[Node<Expr>],
+        kwargs: &[(String, Node<Expr>)],
+        span: &Span,
+    ) -> Result<bool> {
+        let CallSignature {
+            params,
+            defaults,
+            names,
+            variadic,
+        } = signature;
+        let total_params = params.len();
+        let required_params = defaults.iter().filter(|flag| !**flag).count();
+        // Positional arguments past the fixed parameters are collected by `*args`
+        let (fixed_params, rest_type) = match params.last() {
+            Some(TypeInfo::List(element)) if variadic => (total_params - 1, Some(element.as_ref())),
+            _ => (total_params, None),
+        };
+        let (fixed_args, rest_args) = args.split_at(args.len().min(fixed_params));
+
+        if rest_type.is_none() && args.len() > total_params {
+            self.errors.push(
+                TypeError::new(format!(
+                    "function expects at most {} arguments, got {}",
+                    total_params,
+                    args.len()
+                ))
+                .with_hint("Remove extra arguments or verify the function signature".to_string())
+                .with_span(*span),
+            );
+            return Ok(false);
+        }
+
+        if kwargs.is_empty() && args.len() < required_params {
+            self.errors.push(
+                TypeError::new(format!(
+                    "function expects at least {} arguments, got {}",
+                    required_params,
+                    args.len()
+                ))
+                .with_hint("Provide values for all parameters without defaults".to_string())
+                .with_span(*span),
+            );
+            return Ok(false);
+        }
+
+        let mut bound: Vec<Option<&Node<Expr>>> = vec![None; fixed_params];
+        for (slot, arg) in bound.iter_mut().zip(fixed_args) {
+            *slot = Some(arg);
+        }
+
+        if !kwargs.is_empty() && names.len() != total_params {
+            self.errors.push(
+                TypeError::new("function does not accept keyword arguments".to_string())
+                    .with_hint("Pass the arguments positionally".to_string())
+                    .with_span(*span),
+            );
+            return Ok(false);
+        }
+
+        let mut bound_ok = true;
+        for (name, value) in kwargs {
+            match names.iter().position(|param| param == name) {
+                Some(index) if index >= fixed_params => {
+                    self.errors.push(
+                        TypeError::new(format!(
+                            "variadic parameter `{}` cannot be passed by keyword",
+                            name
+                        ))
+                        .with_span(*value.span()),
+                    );
+                    bound_ok = false;
+                }
+                Some(index) if bound[index].is_some() => {
+                    self.errors.push(
+                        TypeError::new(format!("argument `{}` given more than once", name))
+                            .with_span(*value.span()),
+                    );
+                    bound_ok = false;
+                }
+                Some(index) => bound[index] = Some(value),
+                None => {
+                    self.errors.push(
+                        TypeError::new(format!("unknown keyword argument `{}`", name))
+                            .with_hint(format!("Parameters are: {}", names.join(", ")))
+                            .with_span(*value.span()),
+                    );
+                    bound_ok = false;
+                }
+            }
+        }
+
+        for (index, slot) in bound.iter().enumerate() {
+            if slot.is_none() && !defaults.get(index).copied().unwrap_or(false) {
+                self.errors.push(
+                    TypeError::new(format!("missing argument for parameter `{}`", names[index]))
+                        .with_hint("Provide values for all parameters without defaults".to_string())
+                        .with_span(*span),
+                );
+                bound_ok = false;
+            }
+        }
+        if !bound_ok {
+            return Ok(false);
+        }
+
+        let bound_args = bound
+            .iter()
+            .zip(params)
+            .enumerate()
+            .filter_map(|(i, (slot, param_type))| slot.map(|arg| (i, arg, param_type)));
+        let rest_args = rest_args.iter().enumerate().filter_map(|(offset, arg)| {
+            rest_type.map(|element| (fixed_params + offset, arg, element))
+        });
+        for (i, arg, param_type) in bound_args.chain(rest_args) {
+            let label = if i < args.len() {
+                (i + 1).to_string()
+            } else {
+                format!("`{}`", names[i])
+            };
+            let arg_type = self.infer_expr_type(arg)?;
+            self.check_argument_type(&label, &arg_type, param_type, span);
+        }
+
+        Ok(true)
+    }
+
+    /// Reports an argument whose already inferred type does not fit its parameter
+    fn check_argument_type(
+        &mut self,
+        label: &str,
+        arg_type: &TypeInfo,
+        param_type: &TypeInfo,
+        span: &Span,
+    ) {
+        if matches!(arg_type, TypeInfo::Error) || arg_type.is_compatible_with(param_type) {
+            return;
+        }
+        self.errors.push(
+            TypeError::new(format!(
+                "argument {} type mismatch: expected {}, got {}",
+                label,
+                param_type.display_name(),
+                arg_type.display_name()
+            ))
+            .with_span(*span)
+            .with_hint(format!(
+                "Argument {} should be of type `{}`",
+                label,
+                param_type.display_name()
+            ))
+            .with_help("Check the function signature and ensure argument types match".to_string()),
+        );
+    }
+
     pub fn register_module_definitions(&mut self, program: &Program) {
         self.register_type_definitions(&program.statements);
     }
@@ -668,6 +885,18 @@ impl TypeChecker {
         }
 
         registry.activate_module(&module_name);
+        if let Err(err) = self.aliases.insert_import(
+            &import.as_ref().module,
+            import.as_ref().alias.as_deref(),
+            registry,
+        ) {
+            self.errors.push(
+                TypeError::new(err.to_string())
+                    .with_hint(format!("choose a different name than `{}`", err.alias))
+                    .with_span(*import.span()),
+            );
+            return;
+        }
         let alias = import
             .as_ref()
             .alias
@@ -683,6 +912,8 @@ impl TypeChecker {
         }
 
         let candidate = module.rsplit(':').next().unwrap_or(module);
+        // `std/io` names the registry module `io`
+        let candidate = candidate.strip_prefix("std/").unwrap_or(candidate);
         if candidate.starts_with('.') || candidate.starts_with('/') {
             return None;
         }
@@ -830,11 +1061,14 @@ impl TypeChecker {
 
         // Add function parameters to context, overriding any globals/imports
         for param in &function.as_ref().params {
-            let param_type = if let Some(ty) = &param.as_ref().ty {
+            let mut param_type = if let Some(ty) = &param.as_ref().ty {
                 self.context.type_from_annotation(ty)
             } else {
                 TypeInfo::Unknown
             };
+            if param.as_ref().variadic {
+                param_type = TypeInfo::List(Box::new(param_type));
+            }
             fn_context.insert_variable(param.as_ref().name.as_ref().clone(), param_type);
         }
 
@@ -850,9 +1084,23 @@ impl TypeChecker {
         // Type check function body with return type tracking
         let old_context = std::mem::replace(&mut self.context, fn_context);
         let old_return_type = self.current_function_return_type.replace(return_type);
-        let _ = self.check_block(&function.as_ref().body)?;
+        let old_name = self
+            .current_function_name
+            .replace(function.as_ref().name.clone());
+        let old_labels = std::mem::take(&mut self.loop_labels);
+        let (first_error, first_warning) = (self.errors.len(), self.warnings.len());
+        let body = self.check_block(&function.as_ref().body);
+        for error in self.errors[first_error..]
+            .iter_mut()
+            .chain(&mut self.warnings[first_warning..])
+        {
+            error.function_span.get_or_insert(*function.span());
+        }
+        let _ = body?;
         self.context = old_context;
         self.current_function_return_type = old_return_type;
+        self.current_function_name = old_name;
+        self.loop_labels = old_labels;
 
         Ok(())
     }
@@ -1277,7 +1525,14 @@ impl TypeChecker {
                     }
                 }
             }
-            Pattern::Wildcard | Pattern::Literal(_) => {
+            Pattern::Or(alternatives) => {
+                // Alternatives must bind the same names; bind each so every
+                // name is in scope for the arm body.
+                for alternative in alternatives {
+                    self.bind_pattern_variables(alternative, ty);
+                }
+            }
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range { .. } => {
                 // No variables to bind
             }
         }
@@ -1290,21 +1545,7 @@ impl TypeChecker {
             }
             Pattern::Literal(lit) => {
                 // Check literal type matches expected type
-                let lit_type = match lit.as_ref() {
-                    Literal::String(_) => TypeInfo::Str,
-                    Literal::Number(n) => {
-                        if n.value.fract() == 0.0
-                            && n.value >= i32::MIN as f64
-                            && n.value <= i32::MAX as f64
-                        {
-                            TypeInfo::I32
-                        } else {
-                            TypeInfo::F64
-                        }
-                    }
-                    Literal::Bool(_) => TypeInfo::Bool,
-                    Literal::None | Literal::Unit => TypeInfo::Unit,
-                };
+                let lit_type = Self::literal_pattern_type(lit.as_ref());
 
                 if !lit_type.is_compatible_with(ty) {
                     self.errors.push(
@@ -1473,6 +1714,184 @@ impl TypeChecker {
                     );
                 }
             }
+            Pattern::Range { start, end, .. } => {
+                for bound in start.iter().chain(end.iter()) {
+                    if !matches!(bound.as_ref(), Literal::Number(_)) {
+                        self.errors.push(
+                            TypeError::new(
+                                "range pattern bounds must be numeric literals".to_string(),
+                            )
+                            .with_span(*bound.span()),
+                        );
+                        continue;
+                    }
+
+                    let bound_type = Self::literal_pattern_type(bound.as_ref());
+                    if !bound_type.is_compatible_with(ty) {
+                        self.errors.push(
+                            TypeError::new(format!(
+                                "range pattern bound type {} does not match expected type {}",
+                                bound_type.display_name(),
+                                ty.display_name()
+                            ))
+                            .with_span(*bound.span()),
+                        );
+                    }
+                }
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    self.validate_pattern_against_type(alternative, ty);
+                }
+            }
+        }
+    }
+
+    /// Report arms that can never run because an earlier arm matches every
+    /// value, and enum variants / booleans that no arm covers.
+    fn check_match_exhaustiveness(
+        &mut self,
+        value_type: &TypeInfo,
+        arms: &[Node<MatchArm>],
+        span: Span,
+        produces_value: bool,
+    ) {
+        let mut covered = Vec::new();
+        let mut catch_all = false;
+
+        for arm in arms {
+            if catch_all {
+                self.errors.push(
+                    TypeError::new("unreachable match arm".to_string())
+                        .with_span(*arm.span())
+                        .with_hint("An earlier arm already matches every value".to_string()),
+                );
+                continue;
+            }
+
+            // A guarded arm may decline the value, so it covers nothing.
+            if arm.as_ref().guard.is_some() {
+                continue;
+            }
+
+            let pattern = &arm.as_ref().pattern;
+            if Self::pattern_is_irrefutable(pattern) {
+                catch_all = true;
+            } else {
+                Self::collect_covered_cases(pattern, &mut covered);
+            }
+        }
+
+        if catch_all {
+            return;
+        }
+
+        let missing: Vec<String> = match value_type {
+            TypeInfo::Bool => [true, false]
+                .into_iter()
+                .map(|b| b.to_string())
+                .filter(|case| !covered.contains(case))
+                .collect(),
+            TypeInfo::Enum { name, variants, .. } => {
+                // Prefer declaration order; fall back to sorted names.
+                let names: Vec<String> = match self.context.get_enum(name) {
+                    Some(definition) => definition
+                        .variants
+                        .iter()
+                        .map(|variant| variant.name.clone())
+                        .collect(),
+                    None => {
+                        let mut names: Vec<String> = variants.keys().cloned().collect();
+                        names.sort();
+                        names
+                    }
+                };
+                names
+                    .into_iter()
+                    .filter(|variant| !covered.contains(variant))
+                    .map(|variant| format!("{}.{}", name, variant))
+                    .collect()
+            }
+            // Open types cannot be enumerated, but a match that yields a
+            // value has no fallback result when nothing matches.
+            _ if produces_value => {
+                self.errors.push(
+                    TypeError::new(format!(
+                        "non-exhaustive match on {} used as a value",
+                        value_type.display_name()
+                    ))
+                    .with_span(span)
+                    .with_hint(
+                        "Add a `case _:` arm to produce a value for the remaining cases"
+                            .to_string(),
+                    ),
+                );
+                return;
+            }
+            _ => return,
+        };
+
+        if !missing.is_empty() {
+            self.errors.push(
+                TypeError::new(format!(
+                    "non-exhaustive match: {} not covered",
+                    missing.join(", ")
+                ))
+                .with_span(span)
+                .with_hint("Add arms for the missing cases or a `case _:` arm".to_string()),
+            );
+        }
+    }
+
+    fn pattern_is_irrefutable(pattern: &Node<Pattern>) -> bool {
+        match pattern.as_ref() {
+            Pattern::Wildcard | Pattern::Identifier(_) => true,
+            Pattern::Or(alternatives) => alternatives.iter().any(Self::pattern_is_irrefutable),
+            _ => false,
+        }
+    }
+
+    /// Record the enum variants / booleans a pattern matches in full. Variants
+    /// with refutable field patterns only match some of their values, so they
+    /// are not recorded.
+    fn collect_covered_cases(pattern: &Node<Pattern>, covered: &mut Vec<String>) {
+        match pattern.as_ref() {
+            Pattern::Literal(lit) => {
+                if let Literal::Bool(b) = lit.as_ref() {
+                    covered.push(b.to_string());
+                }
+            }
+            Pattern::EnumVariant {
+                variant, fields, ..
+            } => {
+                if fields.iter().all(Self::pattern_is_irrefutable) {
+                    covered.push(variant.clone());
+                }
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    Self::collect_covered_cases(alternative, covered);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn literal_pattern_type(lit: &Literal) -> TypeInfo {
+        match lit {
+            Literal::String(_) => TypeInfo::Str,
+            Literal::Number(n) => {
+                if n.value.fract() == 0.0
+                    && n.value >= i32::MIN as f64
+                    && n.value <= i32::MAX as f64
+                {
+                    TypeInfo::I32
+                } else {
+                    TypeInfo::F64
+                }
+            }
+            Literal::Bool(_) => TypeInfo::Bool,
+            Literal::None | Literal::Unit => TypeInfo::Unit,
         }
     }
 
@@ -1545,11 +1964,20 @@ impl TypeChecker {
                         var_type.display_name()
                     ))
                     .with_hint(format!("The variable `{}` is declared as `{}`, but you're trying to assign a value of type `{}`", name, var_type.display_name(), expr_type.display_name()))
-                    .with_help("Make sure the types match or are compatible (e.g., i32 can be promoted to i64 or f64)".to_string())
+                    .with_help("Make sure the types match or are compatible (e.g., i32 can be promoted to int or float)".to_string())
                     .with_span(*span));
                 }
                 Ok(TypeInfo::Unit)
             }
+            Statement::CompoundAssignment { name, .. } => Err(TypeError::new(format!(
+                "compound assignment to {} was not desugared before type checking",
+                name
+            ))
+            .with_help(
+                "Programs from `parse_surface` must go through `desugar_program`".to_string(),
+            )
+            .with_span(*span)
+            .into()),
             Statement::If {
                 cond,
                 then_block,
@@ -1566,9 +1994,11 @@ impl TypeChecker {
                         .with_span(*span),
                     );
                 }
+                self.lint_constant_condition("if", cond);
 
                 self.check_block(then_block)?;
-                for (_, block) in elif_blocks {
+                for (elif_cond, block) in elif_blocks {
+                    self.lint_constant_condition("elif", elif_cond);
                     self.check_block(block)?;
                 }
                 if let Some(block) = else_block {
@@ -1611,7 +2041,9 @@ impl TypeChecker {
                 let previous = self.context.remove_variable(var.as_ref());
                 self.context
                     .insert_variable(var.as_ref().clone(), element_type);
+                self.loop_labels.push(Some(var.as_ref().clone()));
                 self.check_block(body)?;
+                self.loop_labels.pop();
                 match previous {
                     Some(prev) => {
                         self.context.insert_variable(var.as_ref().clone(), prev);
@@ -1633,7 +2065,10 @@ impl TypeChecker {
                         .with_span(*span),
                     );
                 }
+                self.lint_constant_condition("while", cond);
+                self.loop_labels.push(None);
                 self.check_block(body)?;
+                self.loop_labels.pop();
                 Ok(TypeInfo::Unit)
             }
             Statement::Return(expr) => {
@@ -1642,7 +2077,22 @@ impl TypeChecker {
 
                     // Check return type matches function signature
                     if let Some(expected_return_type) = &self.current_function_return_type {
-                        if !expr_type.is_compatible_with(expected_return_type) {
+                        if matches!(expected_return_type, TypeInfo::Unit)
+                            && !expr_type.is_compatible_with(expected_return_type)
+                        {
+                            self.errors.push(
+                                TypeError::new(format!(
+                                    "function `{}` has no return type but returns a value of type {}",
+                                    self.current_function_name.as_deref().unwrap_or("<anonymous>"),
+                                    expr_type.display_name()
+                                ))
+                                .with_hint(
+                                    "Declare the return type with `-> type` or use a bare `return`"
+                                        .to_string(),
+                                )
+                                .with_span(*span),
+                            );
+                        } else if !expr_type.is_compatible_with(expected_return_type) {
                             self.errors.push(
                                 TypeError::new(format!(
                                     "return type mismatch: expected {}, got {}",
@@ -1665,9 +2115,13 @@ impl TypeChecker {
                         if !expected_return_type.is_compatible_with(&TypeInfo::Unit) {
                             self.errors.push(
                                 TypeError::new(format!(
-                                    "bare return in function that expects return type {}",
+                                    "bare return in function `{}` that expects return type {}",
+                                    self.current_function_name
+                                        .as_deref()
+                                        .unwrap_or("<anonymous>"),
                                     expected_return_type.display_name()
                                 ))
+                                .with_hint("Return a value of the declared type".to_string())
                                 .with_span(*span),
                             );
                         }
@@ -1686,12 +2140,33 @@ impl TypeChecker {
                 Ok(TypeInfo::Unit)
             }
             Statement::Expr(expr) => {
+                self.match_value_discarded = matches!(expr.as_ref(), Expr::Match { .. });
                 let expr_type = self.infer_expr_type(expr)?;
+                if let Expr::Call { func, args, .. } = expr.as_ref()
+                    && matches!(func.as_ref().as_ref(), Expr::Identifier(name) if name == "assert")
+                    && let Some(cond) = args.first()
+                {
+                    self.lint_constant_condition("assert", cond);
+                }
                 // Expression statements are allowed (e.g., function calls)
                 Ok(expr_type)
             }
-            Statement::Break | Statement::Continue => {
-                // These are handled by loop context
+            Statement::Break(label) | Statement::Continue(label) => {
+                if let Some(label) = label
+                    && !self
+                        .loop_labels
+                        .iter()
+                        .any(|l| l.as_deref() == Some(label.as_ref().as_str()))
+                {
+                    self.errors.push(
+                        TypeError::new(format!("unknown loop label `{}`", label.as_ref()))
+                            .with_hint(
+                                "a label names the loop variable of an enclosing `for` loop"
+                                    .to_string(),
+                            )
+                            .with_span(*label.span()),
+                    );
+                }
                 Ok(TypeInfo::Unit)
             }
             Statement::Pass => {
@@ -1726,7 +2201,14 @@ impl TypeChecker {
             match expr.as_ref() {
                 Expr::Literal(lit) => Ok(match lit.as_ref() {
                     Literal::Number(num) => {
-                        if num.is_float_literal {
+                        if !num.value.is_finite() {
+                            self.errors.push(
+                                TypeError::new("float literal is out of range for f64".to_string())
+                                    .with_hint("use `math.inf()` for an infinite value".to_string())
+                                    .with_span(*span),
+                            );
+                            TypeInfo::Error
+                        } else if num.is_float_literal {
                             TypeInfo::F64
                         } else {
                             TypeInfo::I64
@@ -1760,6 +2242,7 @@ impl TypeChecker {
                         {
                             error = error
                                 .with_hint(format!("did you mean `{}`?", closest))
+                                .with_replacement(*span, closest.clone())
                                 .with_suggestion(closest);
                         } else {
                             error = error.with_hint(format!(
@@ -1935,20 +2418,67 @@ impl TypeChecker {
                         }
                     }
                 }
-                Expr::Call { func, args } => {
+                Expr::Call { func, args, kwargs } => {
                     if let Some(enum_type) = self.try_eval_enum_constructor(func.as_ref(), args)? {
                         return Ok(enum_type);
                     }
+                    // `print(xs)` prints a list in its `[a, b, c]` form, and
+                    // numbers, bools and dicts as `str()` would render them
+                    if let Expr::Identifier(name) = func.as_ref().as_ref()
+                        && matches!(name.as_str(), "print" | "println")
+                        && let [arg] = args.as_slice()
+                        && kwargs.is_empty()
+                        && let Some(TypeInfo::Function {
+                            params,
+                            return_type,
+                            ..
+                        }) = self.context.get_function(name).cloned()
+                        && let [param_type] = params.as_slice()
+                    {
+                        // Inferred once here so errors in the argument are reported once
+                        let arg_type = self.infer_expr_type(arg)?;
+                        if !matches!(
+                            arg_type,
+                            TypeInfo::List(_)
+                                | TypeInfo::Dict { .. }
+                                | TypeInfo::I32
+                                | TypeInfo::I64
+                                | TypeInfo::F64
+                                | TypeInfo::Bool
+                        ) {
+                            self.check_argument_type("1", &arg_type, param_type, func.span());
+                        }
+                        return Ok(*return_type);
+                    }
                     let span = func.span();
                     let func_type = match func.as_ref().as_ref() {
                         Expr::Identifier(name) => {
-                            if let Some(func) = self.context.get_function(name).cloned() {
+                            if let Some(registry) = self.registry
+                                && registry.is_overloaded(name)
+                            {
+                                let Some(symbol) =
+                                    self.select_overload(registry, name, args, *span)?
+                                else {
+                                    return Ok(TypeInfo::Error);
+                                };
+                                let return_type = ffi_type_to_typeinfo(&symbol.signature.result);
+                                ffi_function_type(&symbol, return_type)
+                            } else if let Some(func) = self.context.get_function(name).cloned() {
                                 func
                             } else {
-                                self.errors.push(
+                                let mut error =
                                     TypeError::new(format!("undefined function: {}", name))
-                                        .with_span(*span),
-                                );
+                                        .with_span(*span);
+                                let candidates = self.context.functions.keys().cloned();
+                                if let Some(closest) =
+                                    otterc_utils::suggest::find_best_match(name, candidates)
+                                {
+                                    error = error
+                                        .with_hint(format!("did you mean `{}`?", closest))
+                                        .with_replacement(*span, closest.clone())
+                                        .with_suggestion(closest);
+                                }
+                                self.errors.push(error);
                                 TypeInfo::Error
                             }
                         }
@@ -1957,14 +2487,21 @@ impl TypeChecker {
 
                             // First check registry for exact FFI signatures
                             if let Some(registry) = self.registry {
-                                if let Some(symbol) = registry.resolve(&full_name) {
-                                    let params: Vec<TypeInfo> = symbol
-                                        .signature
-                                        .params
-                                        .iter()
-                                        .map(ffi_type_to_typeinfo)
-                                        .collect();
-                                    let return_type = if full_name == "sys.getenv" {
+                                if let Some(mut symbol) =
+                                    registry.resolve_with_aliases(&full_name, &self.aliases)
+                                {
+                                    if registry.is_overloaded(&symbol.name) {
+                                        match self.select_overload(
+                                            registry,
+                                            &symbol.name,
+                                            args,
+                                            *span,
+                                        )? {
+                                            Some(overload) => symbol = overload,
+                                            None => return Ok(TypeInfo::Error),
+                                        }
+                                    }
+                                    let return_type = if symbol.name == "sys.getenv" {
                                         if let Some(option_enum) = self
                                             .context
                                             .build_enum_type("Option", vec![TypeInfo::Str])
@@ -1976,11 +2513,7 @@ impl TypeChecker {
                                     } else {
                                         ffi_type_to_typeinfo(&symbol.signature.result)
                                     };
-                                    TypeInfo::Function {
-                                        params,
-                                        param_defaults: vec![false; symbol.signature.params.len()],
-                                        return_type: Box::new(return_type),
-                                    }
+                                    ffi_function_type(&symbol, return_type)
                                 } else {
                                     self.context
                                         .get_function(&full_name)
@@ -2015,10 +2548,13 @@ impl TypeChecker {
                         TypeInfo::Function {
                             params,
                             param_defaults,
+                            param_names,
+                            variadic,
                             return_type,
                         } => {
                             let mut params_slice: &[TypeInfo] = &params;
                             let mut defaults_slice: &[bool] = &param_defaults;
+                            let mut names_slice: &[String] = &param_names;
                             let has_signature = !params.is_empty() || !param_defaults.is_empty();
 
                             if let Expr::Member { object, .. } = func.as_ref().as_ref()
@@ -2046,6 +2582,9 @@ impl TypeChecker {
                                 }
                                 params_slice = &params[1..];
                                 defaults_slice = &param_defaults[1..];
+                                if !param_names.is_empty() {
+                                    names_slice = &param_names[1..];
+                                }
 
                                 if let TypeInfo::Struct { name, fields } = object_type {
                                     let inferred =
@@ -2059,69 +2598,18 @@ impl TypeChecker {
                             }
 
                             if has_signature {
-                                let total_params = params_slice.len();
-                                let required_params =
-                                    defaults_slice.iter().filter(|flag| !**flag).count();
-
-                                if args.len() > total_params {
-                                    self.errors.push(
-                                        TypeError::new(format!(
-                                            "function expects at most {} arguments, got {}",
-                                            total_params,
-                                            args.len()
-                                        ))
-                                        .with_hint(
-                                            "Remove extra arguments or verify the function signature"
-                                                .to_string(),
-                                        )
-                                        .with_span(*span),
-                                    );
-                                    return Ok(TypeInfo::Error);
-                                }
-
-                                if args.len() < required_params {
-                                    self.errors.push(
-                                        TypeError::new(format!(
-                                            "function expects at least {} arguments, got {}",
-                                            required_params,
-                                            args.len()
-                                        ))
-                                        .with_hint(
-                                            "Provide values for all parameters without defaults"
-                                                .to_string(),
-                                        )
-                                        .with_span(*span),
-                                    );
+                                let signature = CallSignature {
+                                    params: params_slice,
+                                    defaults: defaults_slice,
+                                    names: names_slice,
+                                    variadic,
+                                };
+                                if !self.check_call_arguments(signature, args, kwargs, span)? {
                                     return Ok(TypeInfo::Error);
                                 }
-
-                                for (i, (arg, param_type)) in
-                                    args.iter().zip(params_slice.iter()).enumerate()
-                                {
-                                    let arg_type = self.infer_expr_type(arg)?;
-                                    if !matches!(arg_type, TypeInfo::Error)
-                                        && !arg_type.is_compatible_with(param_type)
-                                    {
-                                        self.errors.push(
-                                            TypeError::new(format!(
-                                                "argument {} type mismatch: expected {}, got {}",
-                                                i + 1,
-                                                param_type.display_name(),
-                                                arg_type.display_name()
-                                            ))
-                                            .with_span(*span)
-                                            .with_hint(format!(
-                                                "Argument {} should be of type `{}`",
-                                                i + 1,
-                                                param_type.display_name()
-                                            ))
-                                            .with_help("Check the function signature and ensure argument types match".to_string()),
-                                        );
-                                    }
-                                }
                             } else {
                                 // For unknown FFI functions, just ensure arguments are type-checked
-                                for arg in args {
+                                for arg in args.iter().chain(kwargs.iter().map(|(_, arg)| arg)) {
                                     let _ = self.infer_expr_type(arg)?;
                                 }
                             }
@@ -2166,7 +2654,7 @@ impl TypeChecker {
                         }
                     }
                 }
-                Expr::Range { start, end } => {
+                Expr::Range { start, end, .. } => {
                     let start_type = self.infer_expr_type(start)?;
                     let end_type = self.infer_expr_type(end)?;
 
@@ -2238,6 +2726,8 @@ impl TypeChecker {
                             return Ok(TypeInfo::Function {
                                 params: vec![],
                                 param_defaults: vec![],
+                                param_names: Vec::new(),
+                                variadic: false,
                                 return_type: Box::new(TypeInfo::Unknown),
                             });
                         } else {
@@ -2263,7 +2753,9 @@ impl TypeChecker {
                         {
                             return Ok(TypeInfo::Module(full_name));
                         }
-                        if let Some(symbol) = registry.resolve(&full_name) {
+                        if let Some(symbol) =
+                            registry.resolve_with_aliases(&full_name, &self.aliases)
+                        {
                             return Ok(TypeInfo::Function {
                                 params: symbol
                                     .signature
@@ -2272,6 +2764,8 @@ impl TypeChecker {
                                     .map(ffi_type_to_typeinfo)
                                     .collect(),
                                 param_defaults: vec![false; symbol.signature.params.len()],
+                                param_names: Vec::new(),
+                                variadic: false,
                                 return_type: Box::new(ffi_type_to_typeinfo(
                                     &symbol.signature.result,
                                 )),
@@ -2291,7 +2785,9 @@ impl TypeChecker {
                                 {
                                     return Ok(TypeInfo::Module(full_name));
                                 }
-                                if let Some(symbol) = registry.resolve(&full_name) {
+                                if let Some(symbol) =
+                                    registry.resolve_with_aliases(&full_name, &self.aliases)
+                                {
                                     return Ok(TypeInfo::Function {
                                         params: symbol
                                             .signature
@@ -2300,6 +2796,8 @@ impl TypeChecker {
                                             .map(ffi_type_to_typeinfo)
                                             .collect(),
                                         param_defaults: vec![false; symbol.signature.params.len()],
+                                        param_names: Vec::new(),
+                                        variadic: false,
                                         return_type: Box::new(ffi_type_to_typeinfo(
                                             &symbol.signature.result,
                                         )),
@@ -2354,6 +2852,37 @@ impl TypeChecker {
                         }
                     }
                 }
+                Expr::Index { base, index } => {
+                    let base_type = self.infer_expr_type(base)?;
+                    let index_type = self.infer_expr_type(index)?;
+                    let (expected_index, element) = match &base_type {
+                        TypeInfo::List(element) => (TypeInfo::I64, (**element).clone()),
+                        TypeInfo::Dict { key, value } => ((**key).clone(), (**value).clone()),
+                        TypeInfo::Unknown | TypeInfo::Error => return Ok(TypeInfo::Unknown),
+                        _ => {
+                            self.errors.push(
+                                TypeError::new(format!(
+                                    "cannot index into type {}",
+                                    base_type.display_name()
+                                ))
+                                .with_span(*span)
+                                .with_hint("Only lists and dicts can be indexed".to_string()),
+                            );
+                            return Ok(TypeInfo::Error);
+                        }
+                    };
+                    if !expected_index.is_compatible_with(&index_type) {
+                        self.errors.push(
+                            TypeError::new(format!(
+                                "index has type {}, expected {}",
+                                index_type.display_name(),
+                                expected_index.display_name()
+                            ))
+                            .with_span(*index.span()),
+                        );
+                    }
+                    Ok(element)
+                }
                 Expr::FString { parts } => {
                     // F-strings always evaluate to strings
                     // Type check all embedded expressions
@@ -2606,6 +3135,7 @@ impl TypeChecker {
                     })
                 }
                 Expr::Match { value, arms } => {
+                    let used_as_value = !std::mem::take(&mut self.match_value_discarded);
                     let mut value_type = self.infer_expr_type(value)?;
                     // Normalize generic types to enum types if applicable
                     value_type = self.context.normalize_type(value_type);
@@ -2670,6 +3200,10 @@ impl TypeChecker {
                         self.context.variables = old_vars;
                     }
 
+                    let produces_value =
+                        used_as_value && !matches!(arm_types[0], TypeInfo::Unit | TypeInfo::Error);
+                    self.check_match_exhaustiveness(&value_type, arms, *span, produces_value);
+
                     // All arms must return compatible types
                     let common_type = arm_types.remove(0);
                     for (i, arm_type) in arm_types.iter().enumerate() {
@@ -2696,6 +3230,27 @@ impl TypeChecker {
                     let struct_def = match self.context.get_struct(name) {
                         Some(def) => def.clone(),
                         None => {
+                            // `f(x=1, y=2)` parses like a struct literal; treat it as a
+                            // keyword-only call when `f` names a function.
+                            if let Some(TypeInfo::Function {
+                                params,
+                                param_defaults,
+                                param_names,
+                                variadic,
+                                return_type,
+                            }) = self.context.get_function(name).cloned()
+                            {
+                                let signature = CallSignature {
+                                    params: &params,
+                                    defaults: &param_defaults,
+                                    names: &param_names,
+                                    variadic,
+                                };
+                                if self.check_call_arguments(signature, &[], fields, span)? {
+                                    return Ok(*return_type);
+                                }
+                                return Ok(TypeInfo::Error);
+                            }
                             self.errors.push(
                                 TypeError::new(format!("unknown struct type: {}", name))
                                     .with_hint(
@@ -2834,8 +3389,11 @@ impl TypeChecker {
                     })
                 }
             }
-        })()?;
+        })();
 
+        // Keep a (placeholder) entry even when inference bails, so editor
+        // queries still find the expression in a program with errors.
+        let ty = ty.inspect_err(|_| self.record_expr_type(expr, &TypeInfo::Unknown))?;
         self.record_expr_type(expr, &ty);
         Ok(ty)
     }
@@ -2845,10 +3403,100 @@ impl TypeChecker {
         &self.errors
     }
 
+    /// Get collected lint warnings
+    pub fn warnings(&self) -> &[TypeError] {
+        &self.warnings
+    }
+
+    /// Warn when a branch or loop condition folds to a constant boolean.
+    fn lint_constant_condition(&mut self, construct: &str, cond: &Node<Expr>) {
+        let Some(value) = Self::fold_constant_condition(cond) else {
+            return;
+        };
+        let hint = match (construct, value) {
+            ("while", true) => "this loop only exits through `break` or `return`",
+            ("while", false) | ("if", false) | ("elif", false) => "this body never runs",
+            ("assert", true) => "this assertion can never fail",
+            ("assert", false) => "this assertion always fails",
+            _ => "the other branches never run",
+        };
+        self.warnings.push(
+            TypeError::new(format!("{} condition is always `{}`", construct, value))
+                .with_hint(hint.to_string())
+                .with_span(*cond.span()),
+        );
+    }
+
+    /// Fold literal-only boolean expressions (`true`, `not false`, `1 < 2`, ...).
+    fn fold_constant_condition(expr: &Node<Expr>) -> Option<bool> {
+        match expr.as_ref() {
+            Expr::Literal(lit) => match lit.as_ref() {
+                Literal::Bool(value) => Some(*value),
+                _ => None,
+            },
+            Expr::Unary {
+                op: UnaryOp::Not,
+                expr,
+            } => Self::fold_constant_condition(expr).map(|value| !value),
+            Expr::Binary { op, left, right } => match op {
+                BinaryOp::And => Some(
+                    Self::fold_constant_condition(left)? && Self::fold_constant_condition(right)?,
+                ),
+                BinaryOp::Or => Some(
+                    Self::fold_constant_condition(left)? || Self::fold_constant_condition(right)?,
+                ),
+                _ => {
+                    if let (Some(l), Some(r)) = (
+                        Self::fold_constant_condition(left),
+                        Self::fold_constant_condition(right),
+                    ) {
+                        return match op {
+                            BinaryOp::Eq => Some(l == r),
+                            BinaryOp::Ne => Some(l != r),
+                            _ => None,
+                        };
+                    }
+                    let (Expr::Literal(l), Expr::Literal(r)) =
+                        (left.as_ref().as_ref(), right.as_ref().as_ref())
+                    else {
+                        return None;
+                    };
+                    let (Literal::Number(l), Literal::Number(r)) = (l.as_ref(), r.as_ref()) else {
+                        return None;
+                    };
+                    let (l, r) = (l.value, r.value);
+                    match op {
+                        BinaryOp::Eq => Some(l == r),
+                        BinaryOp::Ne => Some(l != r),
+                        BinaryOp::Lt => Some(l < r),
+                        BinaryOp::Gt => Some(l > r),
+                        BinaryOp::LtEq => Some(l <= r),
+                        BinaryOp::GtEq => Some(l >= r),
+                        _ => None,
+                    }
+                }
+            },
+            _ => None,
+        }
+    }
+
     pub fn expr_type_map(&self) -> &HashMap<usize, TypeInfo> {
         &self.expr_types
     }
 
+    /// Inferred type of the expression spanning `span`, or else of the
+    /// innermost expression that encloses it. Filled in as checking proceeds,
+    /// so it is usable after `check_program` reports errors.
+    pub fn type_at(&self, span: Span) -> Option<&TypeInfo> {
+        self.expr_types_by_span.get(&span).or_else(|| {
+            self.expr_types_by_span
+                .iter()
+                .filter(|(outer, _)| outer.start() <= span.start() && span.end() <= outer.end())
+                .min_by_key(|(outer, _)| outer.len())
+                .map(|(_, ty)| ty)
+        })
+    }
+
     pub fn into_type_maps(
         self,
     ) -> (
@@ -2960,6 +3608,29 @@ impl TypeChecker {
         }
     }
 
+    /// The overload of the registry function `name` that accepts `args`.
+    /// When none does, the error is recorded at `span` and `None` returned.
+    fn select_overload(
+        &mut self,
+        registry: &SymbolRegistry,
+        name: &str,
+        args: &[Node<Expr>],
+        span: Span,
+    ) -> Result<Option<FfiFunction>> {
+        let mut arg_types = Vec::with_capacity(args.len());
+        for arg in args {
+            arg_types.push(self.infer_expr_type(arg)?.to_ffi_type());
+        }
+        match registry.resolve_call(name, &arg_types) {
+            Ok(overload) => Ok(Some(overload)),
+            Err(err) => {
+                self.errors
+                    .push(TypeError::new(err.to_string()).with_span(span));
+                Ok(None)
+            }
+        }
+    }
+
     fn resolve_member_function(
         &mut self,
         object: &Node<Expr>,
@@ -2983,6 +3654,8 @@ impl TypeChecker {
             _ => TypeInfo::Function {
                 params: vec![],
                 param_defaults: vec![],
+                param_names: Vec::new(),
+                variadic: false,
                 return_type: Box::new(TypeInfo::Unknown),
             },
         }
@@ -2995,6 +3668,22 @@ impl Default for TypeChecker {
     }
 }
 
+/// The type of calling the registry function `symbol`, returning `return_type`
+fn ffi_function_type(symbol: &FfiFunction, return_type: TypeInfo) -> TypeInfo {
+    TypeInfo::Function {
+        params: symbol
+            .signature
+            .params
+            .iter()
+            .map(ffi_type_to_typeinfo)
+            .collect(),
+        param_defaults: vec![false; symbol.signature.params.len()],
+        param_names: Vec::new(),
+        variadic: false,
+        return_type: Box::new(return_type),
+    }
+}
+
 fn ffi_type_to_typeinfo(ft: &FfiType) -> TypeInfo {
     match ft {
         FfiType::Unit => TypeInfo::Unit,
@@ -3072,4 +3761,347 @@ mod tests {
         let ty = checker.infer_expr_type(&expr).unwrap();
         assert_eq!(ty, TypeInfo::F64);
     }
+
+    fn check_source(source: &str) -> Vec<String> {
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut checker = TypeChecker::new();
+        let _ = checker.check_program(&program);
+        checker
+            .errors()
+            .iter()
+            .map(|error| error.message.clone())
+            .collect()
+    }
+
+    const COLOR_ENUM: &str = "enum Color:\n    Red\n    Green\n    Blue\n\n";
+
+    #[test]
+    fn test_unlocated_error_falls_back_to_function_span() {
+        let source = "fn main():\n    let x: i64 = \"text\"\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut checker = TypeChecker::new();
+        let _ = checker.check_program(&program);
+
+        let function_span = *program.statements[0].span();
+        let mut error = checker.errors()[0].clone();
+        assert_eq!(error.function_span, Some(function_span));
+
+        error.span = None;
+        error.message = "something went wrong".to_string();
+        let diagnostics = crate::diagnostics_from_type_errors(&[error], "main", source);
+        assert_eq!(diagnostics[0].span(), function_span);
+    }
+
+    #[test]
+    fn test_match_missing_enum_variants() {
+        let source = format!(
+            "{COLOR_ENUM}fn describe(c: Color) -> i64:\n    return match c:\n        case Color.Red:\n            1\n        case Color.Green if true:\n            2\n"
+        );
+        let errors = check_source(&source);
+        assert!(
+            errors
+                .iter()
+                .any(|message| message
+                    == "non-exhaustive match: Color.Green, Color.Blue not covered"),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_match_bool_and_wildcard_coverage() {
+        let source = format!(
+            "{COLOR_ENUM}fn a(flag: bool) -> i64:\n    return match flag:\n        case true:\n            1\n        case false:\n            0\n\nfn b(c: Color) -> i64:\n    return match c:\n        case Color.Red | Color.Blue:\n            1\n        case _:\n            0\n"
+        );
+        let errors = check_source(&source);
+        assert!(
+            !errors.iter().any(|message| message.contains("match")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_match_arm_after_wildcard_is_unreachable() {
+        let source = "fn f(n: i64) -> i64:\n    return match n:\n        case x:\n            x\n        case 1:\n            1\n";
+        let errors = check_source(source);
+        assert!(
+            errors
+                .iter()
+                .any(|message| message == "unreachable match arm"),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_constant_conditions_warn() {
+        let source = "fn f(n: i64):\n    if true:\n        pass\n    while not (1 < 2):\n        pass\n    if n > 0:\n        pass\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut checker = TypeChecker::new();
+        checker
+            .check_program(&program)
+            .expect("program type checks");
+
+        let warnings: Vec<_> = checker
+            .warnings()
+            .iter()
+            .map(|warning| {
+                let span = warning.span.expect("warning span");
+                (warning.message.as_str(), &source[span.start()..span.end()])
+            })
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                ("if condition is always `true`", "true"),
+                ("while condition is always `false`", "not (1 < 2)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keyword_arguments_bind_by_name() {
+        let header =
+            "fn area(width: float, height: float = 1.0) -> float:\n    return width * height\n\n";
+        let ok = format!(
+            "{header}fn main():\n    let a = area(height = 2.0, width = 3.0)\n    let b = area(3.0, height = 2.0)\n    let c = area(width = 3.0)\n"
+        );
+        assert_eq!(check_source(&ok), Vec::<String>::new());
+
+        let unknown = format!("{header}fn main():\n    let a = area(3.0, depth = 2.0)\n");
+        assert_eq!(
+            check_source(&unknown),
+            vec!["unknown keyword argument `depth`".to_string()]
+        );
+
+        let duplicate = format!("{header}fn main():\n    let a = area(3.0, width = 2.0)\n");
+        assert_eq!(
+            check_source(&duplicate),
+            vec!["argument `width` given more than once".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_variadic_arguments_collect_into_list() {
+        let header = "fn total(label: str, *values: int) -> int:\n    return len(values)\n\n";
+        let ok = format!(
+            "{header}fn main():\n    let a = total(\"none\")\n    let b = total(\"some\", 1, 2, 3)\n"
+        );
+        assert_eq!(check_source(&ok), Vec::<String>::new());
+
+        let mismatch = format!("{header}fn main():\n    let a = total(\"x\", 1, \"two\")\n");
+        let errors = check_source(&mismatch);
+        assert!(
+            errors
+                .iter()
+                .any(|message| message.starts_with("argument 3 type mismatch")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_return_value_must_match_unit_ness() {
+        let bare = "fn answer() -> int:\n    return\n";
+        assert_eq!(
+            check_source(bare),
+            vec!["bare return in function `answer` that expects return type int".to_string()]
+        );
+
+        let valued = "fn log():\n    return 1\n";
+        assert_eq!(
+            check_source(valued),
+            vec!["function `log` has no return type but returns a value of type int".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_loop_labels_must_name_enclosing_for() {
+        let known =
+            "fn main():\n    for i in [1, 2]:\n        for j in [3, 4]:\n            continue i\n";
+        assert!(check_source(known).is_empty());
+
+        let unknown =
+            "fn main():\n    for i in [1, 2]:\n        while true:\n            break j\n";
+        assert_eq!(
+            check_source(unknown),
+            vec!["unknown loop label `j`".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_match_used_as_value_needs_catch_all() {
+        let statement =
+            "fn main():\n    let n = 1\n    match n:\n        case 0:\n            n + 1\n";
+        assert!(check_source(statement).is_empty());
+
+        let covered = "fn main():\n    let n = 1\n    let sign = match n:\n        case 0:\n            \"zero\"\n        case _:\n            \"other\"\n";
+        assert!(check_source(covered).is_empty());
+
+        let open = "fn main():\n    let n = 1\n    let sign = match n:\n        case 0:\n            \"zero\"\n";
+        assert_eq!(
+            check_source(open),
+            vec!["non-exhaustive match on int used as a value".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_range_is_a_value() {
+        let source = "fn total(r: range) -> int:\n    let sum = 0\n    for i in r:\n        sum += i\n    return sum\n\nfn main():\n    let r = 0..=10\n    let t = total(r)\n";
+        assert_eq!(check_source(source), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_float_literals_must_be_finite() {
+        let huge = format!("1{}.0", "0".repeat(400));
+        let source = format!("fn main():\n    let x = {huge}\n    let y = 1.5\n");
+        assert_eq!(
+            check_source(&source),
+            vec!["float literal is out of range for f64".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_print_accepts_lists() {
+        let source =
+            "fn main():\n    let xs = [[1, 2], []]\n    print(xs)\n    println([1, 2, 3])\n";
+        assert_eq!(check_source(source), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_print_accepts_numbers_and_bools() {
+        let source = "fn main():\n    print(42)\n    println(2.5)\n    print(1 < 2)\n";
+        assert_eq!(check_source(source), Vec::<String>::new());
+        assert_eq!(check_source("fn main():\n    print(42, 1)\n").len(), 1);
+    }
+
+    #[test]
+    fn test_print_argument_errors_are_reported_once() {
+        assert_eq!(
+            check_source("fn main():\n    print(missing)\n"),
+            ["undefined variable: missing"]
+        );
+    }
+
+    #[test]
+    fn test_module_alias_resolves_registry_functions() {
+        use otterc_symbol::registry::{FfiFunction, FfiSignature};
+
+        let registry: &'static SymbolRegistry = Box::leak(Box::new(SymbolRegistry::new()));
+        registry.mark_module_active("io");
+        for name in ["shout", "std.io.println"] {
+            registry.register(FfiFunction {
+                name: name.to_string(),
+                symbol: format!("otter_{}", name.replace('.', "_")),
+                signature: FfiSignature::new(vec![FfiType::Str], FfiType::Unit),
+            });
+        }
+        let check = |source: &str| {
+            let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+            let program = otterc_parser::parse(&tokens).expect("parse");
+            let mut checker = TypeChecker::new().with_registry(registry);
+            let _ = checker.check_program(&program);
+            checker
+                .errors()
+                .iter()
+                .map(|error| error.message.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let source = "use std/io as out\n\nfn main():\n    out.println(\"hi\")\n";
+        assert_eq!(check(source), Vec::<String>::new());
+
+        let source = "use std/io as shout\n\nfn main():\n    pass\n";
+        assert_eq!(
+            check(source),
+            vec!["module alias `shout` shadows the top-level function `shout`".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_overloaded_registry_function_picks_signature_by_arguments() {
+        use otterc_symbol::registry::{FfiFunction, FfiSignature};
+
+        let registry: &'static SymbolRegistry = Box::leak(Box::new(SymbolRegistry::new()));
+        registry.mark_module_active("fmt");
+        for (params, result, symbol) in [
+            (vec![FfiType::Str], FfiType::Unit, "otter_fmt_show_str"),
+            (vec![FfiType::I64], FfiType::Str, "otter_fmt_show_int"),
+        ] {
+            registry.register_overload(FfiFunction {
+                name: "fmt.show".to_string(),
+                symbol: symbol.to_string(),
+                signature: FfiSignature::new(params, result),
+            });
+        }
+
+        let source = "use fmt\n\nfn main():\n    let s: str = fmt.show(1)\n    fmt.show(s)\n    fmt.show(1.5)\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut checker = TypeChecker::new().with_registry(registry);
+        let _ = checker.check_program(&program);
+        let errors = checker
+            .errors()
+            .iter()
+            .map(|error| error.message.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec!["no overload of `fmt.show` accepts 1 argument(s) of these types".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_overloaded_bare_function_picks_signature_by_arguments() {
+        use otterc_symbol::registry::{FfiFunction, FfiSignature};
+
+        let registry: &'static SymbolRegistry = Box::leak(Box::new(SymbolRegistry::new()));
+        for (params, result, symbol) in [
+            (vec![FfiType::Str], FfiType::Unit, "otter_show_str"),
+            (vec![FfiType::I64], FfiType::Str, "otter_show_int"),
+        ] {
+            registry.register_overload(FfiFunction {
+                name: "show".to_string(),
+                symbol: symbol.to_string(),
+                signature: FfiSignature::new(params, result),
+            });
+        }
+
+        let source = "fn main():\n    let s: str = show(1)\n    show(s)\n    show(1.5)\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut checker = TypeChecker::new().with_registry(registry);
+        let _ = checker.check_program(&program);
+        let errors = checker
+            .errors()
+            .iter()
+            .map(|error| error.message.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec!["no overload of `show` accepts 1 argument(s) of these types".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_type_at_is_available_despite_errors() {
+        let source = "fn main():\n    let x = 1 + 2\n    let y = missing\n";
+        let tokens = otterc_lexer::tokenize(source).expect("tokenize");
+        let program = otterc_parser::parse(&tokens).expect("parse");
+        let mut checker = TypeChecker::new();
+        assert!(checker.check_program(&program).is_err());
+
+        let start = source.find("1 + 2").unwrap();
+        let sum = Span::new(start, start + "1 + 2".len());
+        assert_eq!(checker.type_at(sum), Some(&TypeInfo::I64));
+        // A position inside the sum resolves to the innermost expression.
+        assert_eq!(
+            checker.type_at(Span::new(start + 4, start + 4)),
+            Some(&TypeInfo::I64)
+        );
+    }
 }
@@ -7,22 +7,38 @@ use super::TypeError;
 pub fn from_type_errors(errors: &[TypeError], source_id: &str, source: &str) -> Vec<Diagnostic> {
     errors
         .iter()
-        .map(|error| to_diagnostic(error, source_id, source))
+        .map(|error| to_diagnostic(error, DiagnosticSeverity::Error, source_id, source))
         .collect()
 }
 
-fn to_diagnostic(error: &TypeError, source_id: &str, source: &str) -> Diagnostic {
+/// Convert type checker lint warnings into warning-level diagnostics.
+pub fn from_type_warnings(
+    warnings: &[TypeError],
+    source_id: &str,
+    source: &str,
+) -> Vec<Diagnostic> {
+    warnings
+        .iter()
+        .map(|warning| to_diagnostic(warning, DiagnosticSeverity::Warning, source_id, source))
+        .collect()
+}
+
+fn to_diagnostic(
+    error: &TypeError,
+    severity: DiagnosticSeverity,
+    source_id: &str,
+    source: &str,
+) -> Diagnostic {
     let span = error.span.unwrap_or_else(|| guess_span(error, source));
-    let mut diagnostic = Diagnostic::new(
-        DiagnosticSeverity::Error,
-        source_id.to_string(),
-        span,
-        error.message.clone(),
-    );
+    let mut diagnostic =
+        Diagnostic::new(severity, source_id.to_string(), span, error.message.clone());
 
     if let Some(suggestion) = &error.suggestion {
         diagnostic = diagnostic.with_suggestion(suggestion.clone());
     }
+    if let Some(replacement) = &error.replacement {
+        diagnostic = diagnostic.with_replacement(replacement.clone());
+    }
 
     match (&error.hint, &error.help) {
         (Some(hint), Some(help)) => {
@@ -49,7 +65,7 @@ fn guess_span(error: &TypeError, source: &str) -> Span {
         }
     }
 
-    Span::new(0, 0)
+    error.function_span.unwrap_or_else(|| Span::new(0, 0))
 }
 
 fn extract_candidates(message: &str) -> Vec<&str> {
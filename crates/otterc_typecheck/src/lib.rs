@@ -9,5 +9,6 @@ pub mod workspace;
 
 pub use checker::{ModuleExports, TypeChecker};
 pub use diagnostics::from_type_errors as diagnostics_from_type_errors;
+pub use diagnostics::from_type_warnings as diagnostics_from_type_warnings;
 pub use types::{EnumLayout, TypeContext, TypeError, TypeInfo};
 pub use workspace::{ModuleDependency, ModuleRecord, TypecheckWorkspace};
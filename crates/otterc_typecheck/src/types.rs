@@ -2,8 +2,10 @@ use std::collections::HashMap;
 
 use otterc_ast::nodes::{EnumVariant, Node, Type};
 use otterc_span::Span;
+use otterc_utils::errors::Replacement;
 
 use otterc_config::LanguageFeatureFlags;
+use otterc_symbol::registry::FfiType;
 
 /// Represents a type in the type system
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,6 +35,10 @@ pub enum TypeInfo {
     Function {
         params: Vec<TypeInfo>,
         param_defaults: Vec<bool>,
+        /// Parameter names for keyword arguments; empty when unknown (FFI, builtins)
+        param_names: Vec<String>,
+        /// The last parameter is `*args` and has type `List(element)`
+        variadic: bool,
         return_type: Box<TypeInfo>,
     },
     /// Generic type (e.g., List<T>, Map<K, V>)
@@ -94,10 +100,14 @@ impl TypeInfo {
             TypeInfo::Function {
                 params,
                 param_defaults,
+                param_names,
+                variadic,
                 return_type,
             } => TypeInfo::Function {
                 params: params.iter().map(|p| p.substitute(substitutions)).collect(),
                 param_defaults: param_defaults.clone(),
+                param_names: param_names.clone(),
+                variadic: *variadic,
                 return_type: Box::new(return_type.substitute(substitutions)),
             },
             TypeInfo::List(element) => TypeInfo::List(Box::new(element.substitute(substitutions))),
@@ -207,6 +217,9 @@ impl TypeInfo {
             (TypeInfo::Dict { key: k1, value: v1 }, TypeInfo::Dict { key: k2, value: v2 }) => {
                 k1.is_compatible_with(k2) && v1.is_compatible_with(v2)
             }
+            (TypeInfo::Range(start1, end1), TypeInfo::Range(start2, end2)) => {
+                start1.is_compatible_with(start2) && end1.is_compatible_with(end2)
+            }
             (
                 TypeInfo::Enum {
                     name: name_a,
@@ -271,12 +284,16 @@ impl TypeInfo {
                 TypeInfo::Function {
                     params: p1,
                     param_defaults: d1,
+                    variadic: v1,
                     return_type: r1,
+                    ..
                 },
                 TypeInfo::Function {
                     params: p2,
                     param_defaults: d2,
+                    variadic: v2,
                     return_type: r2,
+                    ..
                 },
             ) => {
                 p1.len() == p2.len()
@@ -286,25 +303,27 @@ impl TypeInfo {
                         .zip(p2.iter())
                         .all(|(t1, t2)| t1.is_compatible_with(t2))
                     && d1.iter().zip(d2.iter()).all(|(a, b)| a == b)
+                    && v1 == v2
                     && r1.is_compatible_with(r2)
             }
             _ => false,
         }
     }
 
-    /// Get a display name for the type
+    /// Get a display name for the type, spelled as Otter source writes it
+    /// (`int`, `float`)
     pub fn display_name(&self) -> String {
         match self {
             TypeInfo::Unit => "None".to_string(),
             TypeInfo::Bool => "bool".to_string(),
             TypeInfo::I32 => "i32".to_string(),
-            TypeInfo::I64 => "i64".to_string(),
-            TypeInfo::F64 => "f64".to_string(),
+            TypeInfo::I64 => "int".to_string(),
+            TypeInfo::F64 => "float".to_string(),
             TypeInfo::Str => "str".to_string(),
             TypeInfo::Function {
                 params,
-                param_defaults: _,
                 return_type,
+                ..
             } => {
                 let params_str = params
                     .iter()
@@ -367,6 +386,22 @@ impl TypeInfo {
     pub fn is_integer(&self) -> bool {
         matches!(self, TypeInfo::I32 | TypeInfo::I64)
     }
+
+    /// The FFI type a value of this type is passed as, used to pick between
+    /// overloads of a registry function. `None` when it does not decide.
+    pub fn to_ffi_type(&self) -> Option<FfiType> {
+        match self {
+            TypeInfo::Unit => Some(FfiType::Unit),
+            TypeInfo::Bool => Some(FfiType::Bool),
+            TypeInfo::I32 => Some(FfiType::I32),
+            TypeInfo::I64 => Some(FfiType::I64),
+            TypeInfo::F64 => Some(FfiType::F64),
+            TypeInfo::Str => Some(FfiType::Str),
+            TypeInfo::List(_) => Some(FfiType::List),
+            TypeInfo::Dict { .. } => Some(FfiType::Map),
+            _ => None,
+        }
+    }
 }
 
 impl From<&Type> for TypeInfo {
@@ -384,6 +419,9 @@ impl From<&Type> for TypeInfo {
                     key: Box::new(TypeInfo::Unknown),
                     value: Box::new(TypeInfo::Unknown),
                 },
+                "range" | "Range" => {
+                    TypeInfo::Range(Box::new(TypeInfo::I64), Box::new(TypeInfo::I64))
+                }
                 "Error" => TypeInfo::Error,
                 _ => TypeInfo::Generic {
                     base: name.clone(),
@@ -453,7 +491,12 @@ pub struct TypeError {
     pub hint: Option<String>,
     pub help: Option<String>,
     pub suggestion: Option<String>,
+    /// Edit applying `suggestion`, offered by the LSP as a quick fix
+    pub replacement: Option<Replacement>,
     pub span: Option<Span>,
+    /// Span of the function the error was raised in, reported when `span`
+    /// is missing
+    pub function_span: Option<Span>,
 }
 
 impl TypeError {
@@ -463,7 +506,9 @@ impl TypeError {
             hint: None,
             help: None,
             suggestion: None,
+            replacement: None,
             span: None,
+            function_span: None,
         }
     }
 
@@ -482,6 +527,12 @@ impl TypeError {
         self
     }
 
+    /// Suggests replacing the source at `span` with `text`.
+    pub fn with_replacement(mut self, span: Span, text: String) -> Self {
+        self.replacement = Some(Replacement { span, text });
+        self
+    }
+
     pub fn with_span(mut self, span: Span) -> Self {
         self.span = Some(span);
         self
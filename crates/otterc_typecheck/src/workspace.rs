@@ -234,6 +234,7 @@ mod tests {
                     span(),
                 )),
                 args: vec![literal_int(41)],
+                kwargs: vec![],
             },
             span(),
         );
@@ -9,7 +9,15 @@ pub enum DiagnosticSeverity {
     Hint,
 }
 
-#[derive(Clone)]
+/// A concrete edit that fixes a diagnostic: the source text at `span` is
+/// replaced with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    pub span: Span,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct Diagnostic {
     severity: DiagnosticSeverity,
     source_id: String,
@@ -17,6 +25,7 @@ pub struct Diagnostic {
     message: String,
     label: Option<String>,
     suggestion: Option<String>,
+    replacement: Option<Replacement>,
     help: Option<String>,
 }
 
@@ -34,6 +43,7 @@ impl Diagnostic {
             message: message.into(),
             label: None,
             suggestion: None,
+            replacement: None,
             help: None,
         }
     }
@@ -48,6 +58,13 @@ impl Diagnostic {
         self
     }
 
+    /// Attaches an edit that applies the suggestion, for editors to offer
+    /// as a quick fix.
+    pub fn with_replacement(mut self, replacement: Replacement) -> Self {
+        self.replacement = Some(replacement);
+        self
+    }
+
     pub fn with_help(mut self, help: impl Into<String>) -> Self {
         self.help = Some(help.into());
         self
@@ -77,6 +94,10 @@ impl Diagnostic {
         self.suggestion.as_deref()
     }
 
+    pub fn replacement(&self) -> Option<&Replacement> {
+        self.replacement.as_ref()
+    }
+
     pub fn help(&self) -> Option<&str> {
         self.help.as_deref()
     }
@@ -111,6 +132,9 @@ impl Diagnostic {
 }
 
 pub fn emit_diagnostics(diagnostics: &[Diagnostic], source: &str) {
+    // Passes report in their own order; show diagnostics in source order
+    let mut diagnostics: Vec<&Diagnostic> = diagnostics.iter().collect();
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span());
     for diagnostic in diagnostics {
         let color = match diagnostic.severity {
             DiagnosticSeverity::Error => Color::Red,
@@ -0,0 +1,62 @@
+//! Feeds arbitrary input to the lexer, which must return tokens or errors
+//! but never panic or hang, and must agree with the streaming `Lexer`. Run with `cargo fuzz run tokenize` from the
+//! repository root; libFuzzer reports inputs that run past `-timeout` as
+//! hangs.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use otterc_lexer::{Lexer, Token, TokenKind, tokenize};
+use otterc_span::Span;
+
+fn assert_in_source(source: &str, span: Span) {
+    assert!(span.start() <= span.end() && span.end() <= source.len());
+    assert!(source.is_char_boundary(span.start()) && source.is_char_boundary(span.end()));
+}
+
+/// Drains `lexer`, splitting its items into tokens and an error count.
+fn drain(lexer: Lexer) -> (Vec<Token>, usize) {
+    let mut tokens = Vec::new();
+    let mut errors = 0;
+    for item in lexer {
+        match item {
+            Ok(token) => tokens.push(token),
+            Err(_) => errors += 1,
+        }
+    }
+    (tokens, errors)
+}
+
+fuzz_target!(|data: &[u8]| {
+    // The lexer takes `&str`; invalid UTF-8 reaches it as replacement characters
+    let source = String::from_utf8_lossy(data);
+    let (with_comments, comment_errors) = drain(Lexer::new(&source));
+    let (streamed, streamed_errors) = drain(Lexer::without_comments(&source));
+    assert_eq!(comment_errors, streamed_errors);
+    let uncommented: Vec<Token> = with_comments
+        .into_iter()
+        .filter(|token| !token.is_comment())
+        .collect();
+    assert_eq!(uncommented, streamed);
+
+    match tokenize(&source) {
+        Ok(tokens) => {
+            assert_eq!(streamed_errors, 0);
+            assert_eq!(streamed, tokens);
+            assert_eq!(
+                tokens.last().map(|token| token.kind()),
+                Some(&TokenKind::Eof)
+            );
+            for token in &tokens {
+                assert_in_source(&source, token.span());
+            }
+        }
+        Err(errors) => {
+            assert!(!errors.is_empty());
+            assert_eq!(streamed_errors, errors.len());
+            for error in &errors {
+                assert_in_source(&source, error.to_diagnostic("fuzz").span());
+            }
+        }
+    }
+});
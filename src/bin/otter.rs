@@ -11,7 +11,7 @@ fn main() -> anyhow::Result<()> {
         }
         #[expect(clippy::print_stderr, reason = "TODO: Use robust logging")]
         {
-            eprintln!("Error: {}", msg);
+            eprintln!("Error: {:#}", e);
         }
         std::process::exit(1);
     }
@@ -29,7 +29,19 @@ mod tests {
     fn run_command_parses_path_argument() {
         let cli = OtterCli::parse_from(["otter", "run", "tests/demo.ot"]); // no filesystem access
         match cli.command() {
-            Command::Run { path } => assert_eq!(path.to_string_lossy(), "tests/demo.ot"),
+            Command::Run { path, args } => {
+                assert_eq!(path.to_string_lossy(), "tests/demo.ot");
+                assert!(args.is_empty());
+            }
+            other => panic!("expected run command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn run_command_forwards_program_arguments() {
+        let cli = OtterCli::parse_from(["otter", "run", "tests/demo.ot", "--jit", "--", "-v", "x"]);
+        match cli.command() {
+            Command::Run { args, .. } => assert_eq!(args, &["-v", "x"]),
             other => panic!("expected run command, got {other:?}"),
         }
     }
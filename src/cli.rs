@@ -17,12 +17,14 @@ use tracing::{debug, info, warn};
 const TASK_RUNTIME_ENABLED: bool = cfg!(feature = "task-runtime");
 
 use otterc_cache::{CacheBuildOptions, CacheEntry, CacheManager, CacheMetadata, CompilationInputs};
-use otterc_codegen::{BuildArtifact, build_executable};
+use otterc_codegen::{BuildArtifact, CodegenError, build_executable};
 use otterc_config::{CodegenOptLevel, CodegenOptions, LanguageFeatureFlags, TargetTriple, VERSION};
 use otterc_ffi::{BridgeSymbolRegistry, FunctionSpec, TypeSpec};
 use otterc_jit::{ExecutorStats, JitExecutor};
 use otterc_lexer::{LexerError, tokenize};
-use otterc_module::ModuleProcessor;
+use otterc_module::{
+    ModuleProcessor, UnsupportedItem, VisibilityError, check_visibility, link_local_modules,
+};
 use otterc_parser::{ParserError, parse};
 use otterc_runtime::memory::config::GcStrategy;
 use otterc_symbol::registry::SymbolRegistry;
@@ -59,6 +61,14 @@ pub struct OtterCli {
     /// Enable release mode (O3 + LTO) when building binaries.
     release: bool,
 
+    #[arg(long, global = true)]
+    /// Skip runtime bounds checks on indexing.
+    no_bounds_checks: bool,
+
+    #[arg(long, global = true)]
+    /// Infer the types of unannotated function parameters from their uses.
+    infer: bool,
+
     #[arg(long, global = true)]
     /// Enable the experimental async task runtime when executing programs.
     tasks: bool,
@@ -121,7 +131,12 @@ impl OtterCli {
 pub enum Command {
     /// Lexes, parses, and executes the specified source file via the cached native pipeline.
     #[command(alias = "r")]
-    Run { path: PathBuf },
+    Run {
+        path: PathBuf,
+        #[arg(last = true)]
+        /// Arguments passed to the program, after `--`.
+        args: Vec<String>,
+    },
     /// Builds a native executable from the specified source file.
     #[command(alias = "b")]
     Build {
@@ -173,7 +188,7 @@ pub fn run() -> Result<()> {
     }
 
     match &cli.command {
-        Command::Run { path } => handle_run(&cli, path),
+        Command::Run { path, args } => handle_run(&cli, path, args),
         Command::Build { path, output } => handle_build(&cli, path, output.clone()),
         Command::Check { path } => handle_check(&cli, path),
         Command::Fmt { paths } => handle_fmt(paths),
@@ -272,7 +287,7 @@ fn maybe_auto_update() -> Result<()> {
     Ok(())
 }
 
-fn handle_run(cli: &OtterCli, path: &Path) -> Result<()> {
+fn handle_run(cli: &OtterCli, path: &Path, args: &[String]) -> Result<()> {
     let settings = CompilationSettings::from_cli(cli)?;
     let source = read_source(path)?;
     let stage = compile_pipeline(path, &source, &settings)?;
@@ -280,7 +295,7 @@ fn handle_run(cli: &OtterCli, path: &Path) -> Result<()> {
     if settings.jit_enabled() {
         match &stage.result {
             CompilationResult::PreparedProgram { program } => {
-                run_program_with_jit(program, &settings)?;
+                run_program_with_jit(program, &settings, args)?;
             }
             _ => {
                 bail!(
@@ -299,11 +314,11 @@ fn handle_run(cli: &OtterCli, path: &Path) -> Result<()> {
                 if settings.profile {
                     print_profile(&entry.metadata);
                 }
-                execute_binary(&entry.binary_path, &settings)?;
+                execute_binary(&entry.binary_path, &settings, args)?;
             }
             CompilationResult::Compiled { artifact, metadata } => {
                 println!("{} {}", "Building".blue().bold(), artifact.binary.display());
-                execute_binary(&artifact.binary, &settings)?;
+                execute_binary(&artifact.binary, &settings, args)?;
                 if settings.dump_ir
                     && let Some(ir) = &artifact.ir
                 {
@@ -482,6 +497,44 @@ pub fn compile_pipeline(
         module_processor.resolve_all_re_exports()
     })?;
 
+    // Calls through imports of local files may only reach `pub` functions
+    let visibility_errors = check_visibility(&program, &source_dir, &module_processor);
+    if !visibility_errors.is_empty() {
+        emit_visibility_errors(&source_id, source, &visibility_errors);
+        bail!("module visibility check failed");
+    }
+    for module in module_processor.local_modules() {
+        let module_dir = module.path.parent().unwrap_or(Path::new("."));
+        let errors = check_visibility(&module.program, module_dir, &module_processor);
+        if !errors.is_empty() {
+            let module_source = fs::read_to_string(&module.path)
+                .with_context(|| format!("failed to read {}", module.path.display()))?;
+            emit_visibility_errors(&module.path.display().to_string(), &module_source, &errors);
+            bail!("module visibility check failed");
+        }
+    }
+
+    // Compile the functions of imported local files along with the program
+    let mut program = program;
+    let linked_files = match profiler.record_phase("Module Linking", || {
+        link_local_modules(&mut program, &module_processor)
+    }) {
+        Ok(files) => files,
+        Err(err) => {
+            let Some(item) = err.downcast_ref::<UnsupportedItem>() else {
+                return Err(err);
+            };
+            let module_source = fs::read_to_string(&item.path)
+                .with_context(|| format!("failed to read {}", item.path.display()))?;
+            println!("\nModule errors:");
+            emit_diagnostics(
+                &[item.to_diagnostic(&item.path.display().to_string())],
+                &module_source,
+            );
+            bail!("module linking failed");
+        }
+    };
+
     // Register Rust FFI functions for type checking (before type checking)
     let registry = otterc_symbol::registry::SymbolRegistry::global();
     profiler.record_phase("Register FFI Functions", || {
@@ -509,6 +562,15 @@ pub fn compile_pipeline(
         return Err(err).with_context(|| "type checking failed");
     }
 
+    if !type_checker.warnings().is_empty() {
+        let diagnostics = otterc_typecheck::diagnostics_from_type_warnings(
+            type_checker.warnings(),
+            &source_id,
+            source,
+        );
+        emit_diagnostics(&diagnostics, source);
+    }
+
     if settings.check_only {
         profiler.push_phase("Codegen skipped", Duration::from_millis(0));
         return Ok(CompilationStage {
@@ -561,7 +623,7 @@ pub fn compile_pipeline(
         fallback
     };
 
-    let artifact = profiler.record_phase("Codegen", || {
+    let codegen_result = profiler.record_phase("Codegen", || {
         build_executable(
             &program,
             &expr_types,
@@ -571,7 +633,16 @@ pub fn compile_pipeline(
             &binary_path,
             &codegen_options,
         )
-    })?;
+    });
+    let artifact = match codegen_result {
+        Ok(artifact) => artifact,
+        Err(err) => {
+            if let Some(codegen) = err.downcast_ref::<CodegenError>() {
+                emit_codegen_error(&source_id, source, &linked_files, codegen);
+            }
+            return Err(err).with_context(|| "code generation failed");
+        }
+    };
 
     let build_duration_ms = profiler
         .phases()
@@ -644,6 +715,8 @@ pub struct CompilationSettings {
     time: bool,
     profile: bool,
     release: bool,
+    bounds_checks: bool,
+    infer_param_types: bool,
     tasks: bool,
     tasks_debug: bool,
     tasks_trace: bool,
@@ -727,6 +800,8 @@ impl CompilationSettings {
             time: cli.time,
             profile: cli.profile,
             release: cli.release,
+            bounds_checks: !cli.no_bounds_checks,
+            infer_param_types: cli.infer,
             tasks: cli.tasks,
             tasks_debug: cli.tasks_debug,
             tasks_trace: cli.tasks_trace,
@@ -815,6 +890,8 @@ impl CompilationSettings {
             pgo_profile_file: None,
             inline_threshold: None,
             target,
+            bounds_checks: self.bounds_checks,
+            infer_param_types: self.infer_param_types,
         }
     }
 
@@ -928,12 +1005,13 @@ fn find_stdlib_dir() -> Result<PathBuf> {
     )
 }
 
-fn execute_binary(path: &Path, settings: &CompilationSettings) -> Result<()> {
+fn execute_binary(path: &Path, settings: &CompilationSettings, args: &[String]) -> Result<()> {
     if settings.debug {
         println!("Running program: {}", path.display());
     }
 
     let mut command = ProcessCommand::new(path);
+    command.args(args);
     settings.apply_runtime_env(&mut command);
 
     let status = command
@@ -954,17 +1032,24 @@ fn execute_binary(path: &Path, settings: &CompilationSettings) -> Result<()> {
 fn run_program_with_jit(
     program: &otterc_ast::nodes::Program,
     settings: &CompilationSettings,
+    args: &[String],
 ) -> Result<()> {
     let _env_guard = RuntimeEnvGuard::apply(settings);
     let registry = SymbolRegistry::global();
-    let mut executor = JitExecutor::new(program, registry)?;
-    executor.execute_main()?;
+    let mut executor =
+        JitExecutor::new_with_param_inference(program, registry, settings.infer_param_types)?;
+    let exit_code = executor.execute_main(args)?;
 
     if settings.profile {
         let stats = executor.get_stats();
         print_jit_stats(&stats);
     }
 
+    if exit_code != 0 {
+        #[expect(clippy::exit, reason = "The program's exit code becomes ours")]
+        std::process::exit(exit_code);
+    }
+
     Ok(())
 }
 
@@ -1031,7 +1116,7 @@ fn handle_fmt(paths: &[PathBuf]) -> Result<()> {
     use glob::glob;
     use otterc_fmt::Formatter;
     use otterc_lexer::tokenize;
-    use otterc_parser::parse;
+    use otterc_parser::parse_surface;
 
     println!("Formatting OtterLang files...");
 
@@ -1072,7 +1157,7 @@ fn handle_fmt(paths: &[PathBuf]) -> Result<()> {
             clippy::map_err_ignore,
             reason = "TODO: Use the provided error when reporting"
         )]
-        let program = parse(&tokens)
+        let program = parse_surface(&tokens)
             .map_err(|_| anyhow::anyhow!("failed to parse {}", file_path.display()))?;
 
         let formatted = formatter.format_program(&program);
@@ -1105,24 +1190,7 @@ fn print_profile(metadata: &CacheMetadata) {
 }
 
 fn print_jit_stats(stats: &ExecutorStats) {
-    println!("\nJIT profile:");
-    if stats.profiler_metrics.is_empty() {
-        println!("  No functions executed yet");
-    } else {
-        for metric in &stats.profiler_metrics {
-            println!(
-                "  {:20} calls: {:6} avg: {:>8.3}µs total: {:>8.3}ms",
-                metric.name,
-                metric.call_count,
-                metric.avg_time.as_secs_f64() * 1_000_000.0,
-                metric.total_time.as_secs_f64() * 1000.0
-            );
-        }
-    }
-    println!(
-        "  Cache: {} function(s), {} bytes",
-        stats.cache_stats.total_functions, stats.cache_stats.total_size
-    );
+    println!("\n{stats}");
 }
 
 fn emit_lexer_errors(source_id: &str, source: &str, errors: &[LexerError]) {
@@ -1143,6 +1211,45 @@ fn emit_parser_errors(source_id: &str, source: &str, errors: &[ParserError]) {
     emit_diagnostics(&diagnostics, source);
 }
 
+fn emit_codegen_error(
+    source_id: &str,
+    source: &str,
+    linked_files: &HashMap<String, PathBuf>,
+    error: &CodegenError,
+) {
+    let Some((source_id, source)) = codegen_error_source(source_id, source, linked_files, error)
+    else {
+        return;
+    };
+    println!("\nCode generation errors:");
+    emit_diagnostics(&[error.to_diagnostic(&source_id)], &source);
+}
+
+/// Id and text of the file a codegen error's span points into. Functions
+/// linked in from other modules carry spans into their own files.
+fn codegen_error_source(
+    source_id: &str,
+    source: &str,
+    linked_files: &HashMap<String, PathBuf>,
+    error: &CodegenError,
+) -> Option<(String, String)> {
+    let (source_id, source) = match error.function().and_then(|name| linked_files.get(name)) {
+        Some(path) => (path.display().to_string(), fs::read_to_string(path).ok()?),
+        None => (source_id.to_string(), source.to_string()),
+    };
+    let span = error.span()?;
+    (span.end() <= source.len()).then_some((source_id, source))
+}
+
+fn emit_visibility_errors(source_id: &str, source: &str, errors: &[VisibilityError]) {
+    println!("\nModule errors:");
+    let diagnostics: Vec<Diagnostic> = errors
+        .iter()
+        .map(|err| err.to_diagnostic(source_id))
+        .collect();
+    emit_diagnostics(&diagnostics, source);
+}
+
 fn handle_test(
     cli: &OtterCli,
     paths: &[PathBuf],
@@ -1351,3 +1458,46 @@ fn alias_name_helper(alias: &str, crate_name: &str, canonical: &str) -> String {
         format!("{alias}.{canonical}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn codegen_errors_in_modules_point_into_the_module() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let main_file = temp_dir.path().join("main.ot");
+        let main_source = "use ./helpers\n\nfn main():\n    helpers.shout(\"hi\")\n";
+        let helpers_source = "pub fn shout(word):\n    println(word)\n";
+        fs::write(&main_file, main_source).expect("write main");
+        fs::write(temp_dir.path().join("helpers.ot"), helpers_source).expect("write helpers");
+
+        let cli = OtterCli::parse_from([
+            "otter",
+            "build",
+            "--no-cache",
+            main_file.to_str().expect("utf-8 path"),
+        ]);
+        let settings = CompilationSettings::from_cli(&cli).expect("settings");
+        let Err(err) = compile_pipeline(&main_file, main_source, &settings) else {
+            unreachable!("an untyped parameter fails code generation");
+        };
+        let codegen = err.downcast_ref::<CodegenError>().expect("codegen error");
+        assert_eq!(codegen.function(), Some("helpers.shout"));
+
+        let mut program = parse(&tokenize(main_source).expect("tokenize")).expect("parse");
+        let mut processor = ModuleProcessor::new(temp_dir.path().to_path_buf(), None);
+        processor.process_imports(&program).expect("imports");
+        let linked_files = link_local_modules(&mut program, &processor).expect("link");
+
+        let (source_id, source) =
+            codegen_error_source("main.ot", main_source, &linked_files, codegen)
+                .expect("error is located");
+        assert!(source_id.ends_with("helpers.ot"), "{source_id}");
+        assert_eq!(source, helpers_source);
+        let span = codegen.span().expect("span");
+        assert_eq!(&source[span.start()..span.end()], "word");
+    }
+}
@@ -0,0 +1,272 @@
+//! One-call compilation of Otter source text.
+//!
+//! [`compile_source`] runs the whole pipeline (lexing, parsing, type checking
+//! and code generation) and reports failures from any phase as a single
+//! [`CompileError`] carrying spanned diagnostics.
+
+use std::fmt;
+use std::path::Path;
+
+use otterc_codegen::{BuildArtifact, CodegenError, build_executable};
+use otterc_config::CodegenOptions;
+use otterc_lexer::tokenize;
+use otterc_parser::parse;
+use otterc_span::Span;
+use otterc_symbol::registry::SymbolRegistry;
+use otterc_typecheck::TypeChecker;
+use otterc_utils::errors::{Diagnostic, emit_diagnostics};
+
+/// Source id attached to diagnostics produced by [`compile_source`].
+pub const SOURCE_ID: &str = "<source>";
+
+/// The pipeline phase a [`CompileError`] was raised in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompilePhase {
+    Lexing,
+    Parsing,
+    TypeChecking,
+    Codegen,
+}
+
+impl fmt::Display for CompilePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Lexing => "lexing",
+            Self::Parsing => "parsing",
+            Self::TypeChecking => "type checking",
+            Self::Codegen => "code generation",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A failed compilation, with the diagnostics of the phase that failed.
+#[derive(Debug, Clone)]
+pub struct CompileError {
+    phase: CompilePhase,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl CompileError {
+    pub fn phase(&self) -> CompilePhase {
+        self.phase
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Renders the diagnostics against `source` on stderr.
+    pub fn emit(&self, source: &str) {
+        emit_diagnostics(&self.diagnostics, source);
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed", self.phase)?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  {}", diagnostic.message())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// Compiles `source` into an executable at `output`.
+///
+/// The source must be self-contained: `use` of Otter modules on disk and
+/// `rust:` crate imports are resolved by the `otter` CLI only. Code
+/// generation errors are located at the failing expression or statement when
+/// codegen knows it, and otherwise at the start of the file.
+pub fn compile_source(
+    source: &str,
+    output: &Path,
+    options: &CodegenOptions,
+) -> Result<BuildArtifact, CompileError> {
+    let tokens = tokenize(source).map_err(|errors| CompileError {
+        phase: CompilePhase::Lexing,
+        diagnostics: errors
+            .iter()
+            .map(|err| err.to_diagnostic(SOURCE_ID))
+            .collect(),
+    })?;
+
+    let program = parse(&tokens).map_err(|errors| CompileError {
+        phase: CompilePhase::Parsing,
+        diagnostics: errors
+            .iter()
+            .map(|err| err.to_diagnostic(SOURCE_ID))
+            .collect(),
+    })?;
+
+    let mut type_checker = TypeChecker::new().with_registry(SymbolRegistry::global());
+    if type_checker.check_program(&program).is_err() {
+        return Err(CompileError {
+            phase: CompilePhase::TypeChecking,
+            diagnostics: otterc_typecheck::diagnostics_from_type_errors(
+                type_checker.errors(),
+                SOURCE_ID,
+                source,
+            ),
+        });
+    }
+
+    let enum_layouts = type_checker.enum_layouts();
+    let (expr_types, expr_types_by_span, comprehension_var_types) = type_checker.into_type_maps();
+
+    build_executable(
+        &program,
+        &expr_types,
+        &expr_types_by_span,
+        &comprehension_var_types,
+        &enum_layouts,
+        output,
+        options,
+    )
+    .map_err(|err| CompileError {
+        phase: CompilePhase::Codegen,
+        diagnostics: vec![match err.downcast_ref::<CodegenError>() {
+            Some(codegen) => codegen.to_diagnostic(SOURCE_ID),
+            None => Diagnostic::error(SOURCE_ID, Span::new(0, 0), format!("{err:#}")),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(clippy::panic, reason = "Panicking on test failures is acceptable")]
+
+    use super::*;
+
+    fn compile_error(source: &str) -> CompileError {
+        let output = std::env::temp_dir().join("otter_compile_source_error");
+        match compile_source(source, &output, &CodegenOptions::default()) {
+            Ok(_) => panic!("expected compilation of {source:?} to fail"),
+            Err(err) => err,
+        }
+    }
+
+    #[test]
+    fn reports_the_failing_phase_with_spans() {
+        let err = compile_error("fn main():\n    let x = \"unterminated\n");
+        assert_eq!(err.phase(), CompilePhase::Lexing);
+        assert!(!err.diagnostics().is_empty());
+
+        let err = compile_error("fn main(:\n    pass\n");
+        assert_eq!(err.phase(), CompilePhase::Parsing);
+        assert!(err.diagnostics().iter().all(|diag| !diag.span().is_empty()));
+
+        let err = compile_error("fn main():\n    let x: int = \"text\"\n");
+        assert_eq!(err.phase(), CompilePhase::TypeChecking);
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM: cargo test -- --ignored
+    fn locates_codegen_errors() {
+        let source = "fn shout(word):\n    println(word)\n\nfn main():\n    shout(\"hi\")\n";
+        let err = compile_error(source);
+        assert_eq!(err.phase(), CompilePhase::Codegen);
+        let span = err.diagnostics()[0].span();
+        assert_eq!(&source[span.start()..span.end()], "word");
+    }
+
+    /// Builds and runs `source`, returning its standard output
+    fn run_program(name: &str, source: &str, options: &CodegenOptions) -> String {
+        let output = std::env::temp_dir().join(format!("otter_compile_source_{name}"));
+        let artifact =
+            compile_source(source, &output, options).unwrap_or_else(|err| panic!("{err}"));
+        let run = std::process::Command::new(&artifact.binary)
+            .output()
+            .unwrap_or_else(|err| panic!("failed to run {}: {err}", artifact.binary.display()));
+        assert!(run.status.success(), "{name} exited with {}", run.status);
+        String::from_utf8_lossy(&run.stdout).into_owned()
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn float_special_values_follow_ieee_754() {
+        let source = include_str!("../tests/float_special_values.ot");
+        // The expected output is listed in the file's header comment
+        let expected: String = source
+            .lines()
+            .skip_while(|line| !line.starts_with("# Pins"))
+            .skip(1)
+            .map_while(|line| line.strip_prefix("# "))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        assert!(expected.contains("nan == nan: false"));
+        assert_eq!(
+            run_program("float_special_values", source, &CodegenOptions::default()),
+            expected
+        );
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn codegen_test_program_passes() {
+        // Every check in the program panics, failing the run, when it breaks
+        let source = include_str!("../tests/codegen_tests.ot");
+        otterc_ffi::bootstrap_stdlib();
+        run_program("codegen_tests", source, &CodegenOptions::default());
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn caller_owned_strings_are_all_freed() {
+        let input = std::env::temp_dir().join("otter_owned_strings_input.txt");
+        std::fs::write(&input, "line one").unwrap();
+        // Reads handed to a runtime call, dropped at the end of a block, and
+        // left behind by `break`, `continue` and an early `return`
+        let source = format!(
+            "use io\nuse runtime\n\n\
+             fn is_empty(path: str) -> bool:\n    let text = io.read(path)\n    if text == \"\":\n        return true\n    return false\n\n\
+             fn main():\n    let path = \"{}\"\n    runtime.memory_profiler_start()\n\
+             \x20   println(io.read(path))\n\
+             \x20   if true:\n        let text = io.read(path)\n        println(text)\n\
+             \x20   let i = 0\n    while i < 3:\n        let text = io.read(path)\n        i = i + 1\n\
+             \x20       if i == 1:\n            continue\n        break\n\
+             \x20   let empty = is_empty(path)\n\
+             \x20   runtime.memory_profiler_stop()\n    println(runtime.memory_profiler_stats())\n",
+            input.display()
+        );
+        otterc_ffi::bootstrap_stdlib();
+        let output = run_program("owned_strings", &source, &CodegenOptions::default());
+        let stats: serde_json::Value =
+            serde_json::from_str(output.lines().last().unwrap_or_default()).unwrap();
+        // Five reads of "line one" plus its terminator
+        assert_eq!(stats["total_allocated"], 45, "{output}");
+        assert_eq!(stats["total_freed"], 45, "{output}");
+        assert_eq!(stats["active_allocations"], 0, "{output}");
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn deep_tail_recursion_runs_in_constant_stack() {
+        // Ten million frames would overflow the stack; without optimization
+        // only `musttail` keeps the recursion in one frame
+        let source = "fn sum_to(n: int, acc: int) -> int:\n    if n == 0:\n        return acc\n    return sum_to(n - 1, acc + n)\n\nfn main():\n    println(f\"{sum_to(10000000, 0)}\")\n";
+        let options = CodegenOptions {
+            opt_level: otterc_config::CodegenOptLevel::None,
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            run_program("deep_tail_recursion", source, &options),
+            "50000005000000\n"
+        );
+    }
+
+    #[test]
+    #[ignore] // Needs LLVM and the runtime library: OTTERC_RUNTIME_LIB=target/debug cargo test -- --ignored
+    fn compiles_and_runs_hello_world() {
+        assert_eq!(
+            run_program(
+                "hello",
+                "fn main():\n    println(\"Hello, world!\")\n",
+                &CodegenOptions::default()
+            ),
+            "Hello, world!\n"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub mod cli;
+pub mod compile;
 pub mod lsp;
 pub mod test;
 pub mod tools;
 
+pub use compile::{CompileError, CompilePhase, compile_source};
 pub use otterc_runtime::*;
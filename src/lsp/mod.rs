@@ -1,5 +1,6 @@
 use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::RwLock;
 use tower_lsp::jsonrpc::Result;
@@ -7,13 +8,14 @@ use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
 use otterc_ast::nodes::{Expr, Function, Node, Program, Statement, Type};
+use otterc_ast::visit::{Visitor, walk_function, walk_statement};
 use otterc_lexer::{LexerError, Token, tokenize};
 use otterc_parser::parse;
 use otterc_span::Span;
 use otterc_symbol::registry::SymbolRegistry;
-use otterc_typecheck::{self, TypeChecker};
+use otterc_typecheck::{self, TypeChecker, TypeInfo};
 use otterc_utils::errors::{
-    Diagnostic as OtterDiagnostic, DiagnosticSeverity as OtterDiagSeverity,
+    Diagnostic as OtterDiagnostic, DiagnosticSeverity as OtterDiagSeverity, Replacement,
 };
 
 const BUILTIN_FUNCTION_COMPLETIONS: &[(&str, &str)] = &[
@@ -96,7 +98,11 @@ impl CallableInfo {
             .params
             .iter()
             .map(|param| CallableParam {
-                name: param.as_ref().name.as_ref().clone(),
+                name: if param.as_ref().variadic {
+                    format!("*{}", param.as_ref().name.as_ref())
+                } else {
+                    param.as_ref().name.as_ref().clone()
+                },
                 ty: param
                     .as_ref()
                     .ty
@@ -132,8 +138,18 @@ enum SymbolKind {
 struct SymbolTable {
     /// All symbols with their info
     symbols: HashMap<String, SymbolInfo>,
-    /// References: symbol name -> list of spans where it's used
-    references: HashMap<String, Vec<Span>>,
+    /// Top-level function name -> span of the name in its header. Kept apart
+    /// from `symbols` so a local of the same name does not hide the function.
+    functions: HashMap<String, Span>,
+    /// Every function and method body with the names its parameters,
+    /// `let`s and loop variables declare locally
+    scopes: Vec<LocalScope>,
+}
+
+#[derive(Debug, Clone)]
+struct LocalScope {
+    span: Span,
+    names: BTreeSet<String>,
 }
 
 impl SymbolTable {
@@ -237,19 +253,12 @@ impl SymbolTable {
         );
     }
 
-    fn add_reference(&mut self, name: String, span: Span) {
-        self.references.entry(name).or_default().push(span);
-    }
-
     fn find_definition(&self, name: &str) -> Option<&SymbolInfo> {
         self.symbols.get(name)
     }
 
-    fn find_references(&self, name: &str) -> &[Span] {
-        self.references
-            .get(name)
-            .map(|v| v.as_slice())
-            .unwrap_or(&[])
+    fn find_function(&self, name: &str) -> Option<Span> {
+        self.functions.get(name).copied()
     }
 
     fn all_symbols(&self) -> impl Iterator<Item = (&String, &SymbolInfo)> {
@@ -259,15 +268,46 @@ impl SymbolTable {
     fn get(&self, name: &str) -> Option<&SymbolInfo> {
         self.symbols.get(name)
     }
+
+    /// Innermost function body declaring `name` locally that contains `offset`
+    fn local_scope_of(&self, name: &str, offset: usize) -> Option<&LocalScope> {
+        self.scopes
+            .iter()
+            .filter(|scope| scope.span.contains(offset) && scope.names.contains(name))
+            .min_by_key(|scope| scope.span.len())
+    }
 }
 
+/// How long `did_change` waits for typing to pause before recomputing
+/// diagnostics
+const DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Semantic token legend; a token's type is its index in this list
+const SEMANTIC_TOKEN_TYPES: [SemanticTokenType; 11] = [
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::CLASS,
+    SemanticTokenType::ENUM,
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::COMMENT,
+];
+
 #[derive(Default, Debug)]
 struct DocumentStore {
     documents: HashMap<Url, String>,
+    /// Symbol table of the last text that parsed, kept while the document is
+    /// mid-edit and does not
     symbol_tables: HashMap<Url, SymbolTable>,
+    /// Bumped on every edit; a diagnostics run for an older revision is stale
+    revisions: HashMap<Url, u64>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Backend {
     client: Client,
     state: Arc<RwLock<DocumentStore>>,
@@ -281,18 +321,21 @@ impl Backend {
         }
     }
 
-    async fn upsert_document(&self, uri: Url, text: String) {
-        {
-            let mut state = self.state.write().await;
-            state.documents.insert(uri.clone(), text);
-        }
-        self.publish_diagnostics(uri).await;
+    /// Stores `text` and returns the document's new revision
+    async fn upsert_document(&self, uri: Url, text: String) -> u64 {
+        let mut state = self.state.write().await;
+        state.documents.insert(uri.clone(), text);
+        let revision = state.revisions.entry(uri).or_default();
+        *revision += 1;
+        *revision
     }
 
     async fn remove_document(&self, uri: &Url) {
         {
             let mut state = self.state.write().await;
             state.documents.remove(uri);
+            state.symbol_tables.remove(uri);
+            state.revisions.remove(uri);
         }
         let _ = self
             .client
@@ -300,21 +343,47 @@ impl Backend {
             .await;
     }
 
-    async fn publish_diagnostics(&self, uri: Url) {
+    /// Publishes diagnostics for `revision` once edits have paused for
+    /// [`DIAGNOSTICS_DEBOUNCE`]
+    fn schedule_diagnostics(&self, uri: Url, revision: u64) {
+        let backend = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(DIAGNOSTICS_DEBOUNCE).await;
+            backend.publish_diagnostics(uri, revision).await;
+        });
+    }
+
+    async fn is_current(&self, uri: &Url, revision: u64) -> bool {
+        self.state.read().await.revisions.get(uri) == Some(&revision)
+    }
+
+    /// Recomputes and publishes diagnostics for `revision` of the document,
+    /// doing nothing if a later edit has superseded it
+    async fn publish_diagnostics(&self, uri: Url, revision: u64) {
         let text = {
             let state = self.state.read().await;
+            if state.revisions.get(&uri) != Some(&revision) {
+                return;
+            }
             state.documents.get(&uri).cloned()
         };
 
         if let Some(text) = text {
             let (diagnostics, symbol_table) = compute_lsp_diagnostics_and_symbols(&text);
 
-            // Store the symbol table
             {
                 let mut state = self.state.write().await;
-                state.symbol_tables.insert(uri.clone(), symbol_table);
+                if state.revisions.get(&uri) != Some(&revision) {
+                    return;
+                }
+                if let Some(symbol_table) = symbol_table {
+                    state.symbol_tables.insert(uri.clone(), symbol_table);
+                }
             }
 
+            if !self.is_current(&uri, revision).await {
+                return;
+            }
             let _ = self
                 .client
                 .publish_diagnostics(uri, diagnostics, None)
@@ -367,14 +436,7 @@ impl LanguageServer for Backend {
                 semantic_tokens_provider: Some(
                     SemanticTokensOptions {
                         legend: SemanticTokensLegend {
-                            token_types: vec![
-                                SemanticTokenType::FUNCTION,
-                                SemanticTokenType::VARIABLE,
-                                SemanticTokenType::PARAMETER,
-                                SemanticTokenType::TYPE,
-                                SemanticTokenType::CLASS,
-                                SemanticTokenType::ENUM,
-                            ],
+                            token_types: SEMANTIC_TOKEN_TYPES.to_vec(),
                             token_modifiers: vec![],
                         },
                         range: Some(true),
@@ -397,14 +459,18 @@ impl LanguageServer for Backend {
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.upsert_document(params.text_document.uri, params.text_document.text)
+        let uri = params.text_document.uri;
+        let revision = self
+            .upsert_document(uri.clone(), params.text_document.text)
             .await;
+        self.publish_diagnostics(uri, revision).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         if let Some(change) = params.content_changes.into_iter().last() {
-            self.upsert_document(params.text_document.uri, change.text)
-                .await;
+            let uri = params.text_document.uri;
+            let revision = self.upsert_document(uri.clone(), change.text).await;
+            self.schedule_diagnostics(uri, revision);
         }
     }
 
@@ -431,10 +497,9 @@ impl LanguageServer for Backend {
         };
 
         if let (Some(text), Some(symbol_table)) = (text, symbol_table)
-            && let Some(var_name) = word_at_position(&text, position)
-            && let Some(symbol_info) = symbol_table.find_definition(&var_name)
+            && let Some(span) = definition_at(&text, &symbol_table, position)
         {
-            let range = span_to_range(symbol_info.span, &text);
+            let range = span_to_range(span, &text);
             return Ok(Some(GotoDefinitionResponse::Scalar(Location {
                 uri: uri.clone(),
                 range,
@@ -463,6 +528,7 @@ impl LanguageServer for Backend {
     async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
         let uri = params.text_document_position.text_document.uri;
         let position = params.text_document_position.position;
+        let include_declaration = params.context.include_declaration;
 
         let (text, symbol_table) = {
             let state = self.state.read().await;
@@ -472,26 +538,16 @@ impl LanguageServer for Backend {
         };
 
         if let (Some(text), Some(symbol_table)) = (text, symbol_table)
-            && let Some(var_name) = word_at_position(&text, position)
+            && let Some(spans) =
+                reference_spans(&text, &symbol_table, position, include_declaration)
         {
-            let mut locations = Vec::new();
-
-            // Add definition
-            if let Some(symbol_info) = symbol_table.find_definition(&var_name) {
-                locations.push(Location {
-                    uri: uri.clone(),
-                    range: span_to_range(symbol_info.span, &text),
-                });
-            }
-
-            // Add all references
-            for span in symbol_table.find_references(&var_name) {
-                locations.push(Location {
+            let locations = spans
+                .into_iter()
+                .map(|span| Location {
                     uri: uri.clone(),
-                    range: span_to_range(*span, &text),
-                });
-            }
-
+                    range: span_to_range(span, &text),
+                })
+                .collect();
             return Ok(Some(locations));
         }
 
@@ -510,6 +566,16 @@ impl LanguageServer for Backend {
             (text, symbol_table)
         };
 
+        // Outline from the parsed program; a document that does not parse
+        // falls back to the flat list from its last symbol table
+        if let Some(text) = &text
+            && let Ok(tokens) = tokenize(text)
+            && let Ok(program) = parse(&tokens)
+        {
+            let symbols = outline_symbols(&program, &tokens, text);
+            return Ok(Some(DocumentSymbolResponse::Nested(symbols)));
+        }
+
         if let (Some(text), Some(symbol_table)) = (text, symbol_table) {
             let mut symbols = Vec::new();
             for (name, info) in symbol_table.all_symbols() {
@@ -605,29 +671,21 @@ impl LanguageServer for Backend {
             (text, symbol_table)
         };
 
+        validate_new_name(&new_name).map_err(tower_lsp::jsonrpc::Error::invalid_params)?;
+
         if let (Some(text), Some(symbol_table)) = (text, symbol_table)
-            && let Some(old_name) = word_at_position(&text, position)
+            && let Some(spans) = rename_spans(&text, &symbol_table, position)
         {
-            let mut changes = HashMap::new();
-            let mut edits = Vec::new();
-
-            // Add definition rename
-            if let Some(symbol_info) = symbol_table.find_definition(&old_name) {
-                edits.push(TextEdit {
-                    range: span_to_range(symbol_info.span, &text),
-                    new_text: new_name.clone(),
-                });
-            }
-
-            // Add all references
-            for span in symbol_table.find_references(&old_name) {
-                edits.push(TextEdit {
-                    range: span_to_range(*span, &text),
+            let edits: Vec<TextEdit> = spans
+                .into_iter()
+                .map(|span| TextEdit {
+                    range: span_to_range(span, &text),
                     new_text: new_name.clone(),
-                });
-            }
+                })
+                .collect();
 
             if !edits.is_empty() {
+                let mut changes = HashMap::new();
                 changes.insert(uri, edits);
                 return Ok(Some(WorkspaceEdit {
                     changes: Some(changes),
@@ -651,33 +709,10 @@ impl LanguageServer for Backend {
             (text, symbol_table)
         };
 
-        if let (Some(text), Some(symbol_table)) = (text, symbol_table)
-            && let Some(var_name) = word_at_position(&text, position)
-            && let Some(symbol_info) = symbol_table.find_definition(&var_name)
-        {
-            let kind_str = match symbol_info.kind {
-                SymbolKind::Function => "function",
-                SymbolKind::Variable => "variable",
-                SymbolKind::Parameter => "parameter",
-                SymbolKind::Struct => "struct",
-                SymbolKind::Enum => "enum",
-                SymbolKind::TypeAlias => "type",
-                SymbolKind::Method => "method",
-            };
-            let detail = symbol_info
-                .ty
-                .as_ref()
-                .map(|ty| format!("{}: {}", kind_str, ty))
-                .unwrap_or_else(|| kind_str.to_string());
-
-            let contents = HoverContents::Scalar(MarkedString::String(detail));
-            return Ok(Some(Hover {
-                contents,
-                range: Some(span_to_range(symbol_info.span, &text)),
-            }));
-        }
-
-        Ok(None)
+        Ok(match (text, symbol_table) {
+            (Some(text), Some(symbol_table)) => hover_at(&text, &symbol_table, position),
+            _ => None,
+        })
     }
 
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
@@ -840,49 +875,36 @@ impl LanguageServer for Backend {
             (text, symbol_table)
         };
 
-        if let (Some(text), Some(symbol_table)) = (text, symbol_table) {
-            let mut tokens = Vec::new();
-            let mut prev_line = 0;
-            let mut prev_col = 0;
-
-            for (_name, info) in symbol_table.all_symbols() {
-                let pos = span_to_position(info.span.start(), &text);
-                let token_type = match info.kind {
-                    SymbolKind::Function | SymbolKind::Method => 0, // FUNCTION
-                    SymbolKind::Variable => 1,                      // VARIABLE
-                    SymbolKind::Parameter => 2,                     // PARAMETER
-                    SymbolKind::Struct => 4,                        // CLASS
-                    SymbolKind::Enum => 5,                          // ENUM
-                    SymbolKind::TypeAlias => 3,                     // TYPE
-                };
-
-                let delta_line = pos.line as u32 - prev_line;
-                let delta_start = if delta_line == 0 {
-                    pos.character as u32 - prev_col
-                } else {
-                    pos.character as u32
-                };
-                let length = (info.span.end() - info.span.start()) as u32;
-
-                tokens.push(SemanticToken {
-                    delta_line,
-                    delta_start,
-                    length,
-                    token_type,
-                    token_modifiers_bitset: 0,
-                });
+        Ok(text.map(|text| {
+            let tokens = semantic_tokens(&text, &symbol_table.unwrap_or_default());
+            SemanticTokensResult::Tokens(SemanticTokens {
+                result_id: None,
+                data: delta_encode(&tokens),
+            })
+        }))
+    }
 
-                prev_line = pos.line as u32;
-                prev_col = pos.character as u32;
-            }
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        let uri = params.text_document.uri;
+        let (text, symbol_table) = {
+            let state = self.state.read().await;
+            let text = state.documents.get(&uri).cloned();
+            let symbol_table = state.symbol_tables.get(&uri).cloned();
+            (text, symbol_table)
+        };
 
-            return Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+        let lines = params.range.start.line..=params.range.end.line;
+        Ok(text.map(|text| {
+            let mut tokens = semantic_tokens(&text, &symbol_table.unwrap_or_default());
+            tokens.retain(|token| lines.contains(&token.line));
+            SemanticTokensRangeResult::Tokens(SemanticTokens {
                 result_id: None,
-                data: tokens,
-            })));
-        }
-
-        Ok(None)
+                data: delta_encode(&tokens),
+            })
+        }))
     }
 
     async fn code_action(
@@ -891,6 +913,12 @@ impl LanguageServer for Backend {
     ) -> Result<Option<Vec<CodeActionOrCommand>>> {
         let mut actions = Vec::new();
 
+        for diag in &params.context.diagnostics {
+            if let Some(action) = quick_fix(&params.text_document.uri, diag) {
+                actions.push(CodeActionOrCommand::CodeAction(action));
+            }
+        }
+
         // Add "Add type annotation" action for variables
         for diag in &params.context.diagnostics {
             if diag.message.contains("type") {
@@ -927,26 +955,6 @@ impl LanguageServer for Backend {
     }
 }
 
-/// Convert span start to Position
-fn span_to_position(byte_offset: usize, text: &str) -> Position {
-    let mut line = 0;
-    let mut character = 0;
-
-    for (i, ch) in text.char_indices() {
-        if i >= byte_offset {
-            break;
-        }
-        if ch == '\n' {
-            line += 1;
-            character = 0;
-        } else {
-            character += 1;
-        }
-    }
-
-    Position { line, character }
-}
-
 /// Run a standard I/O LSP server using the backend above.
 pub async fn run_stdio_server() {
     let stdin = tokio::io::stdin();
@@ -955,268 +963,626 @@ pub async fn run_stdio_server() {
     Server::new(stdin, stdout, socket).serve(service).await;
 }
 
-/// Build symbol table from program, tracking definitions and references
-fn build_symbol_table(program: &Program, tokens: &[Token], text: &str) -> SymbolTable {
-    let mut table = SymbolTable::new();
+/// Build symbol table from program, tracking definitions
+fn build_symbol_table(
+    program: &Program,
+    tokens: &[Token],
+    text: &str,
+    checker: Option<&TypeChecker>,
+) -> SymbolTable {
+    let mut definitions = DefinitionCollector {
+        table: SymbolTable::new(),
+        tokens,
+        text,
+        checker,
+        open_scopes: Vec::new(),
+    };
+    definitions.visit_program(program);
+    definitions.table
+}
 
-    // First pass: collect all definitions
-    build_symbol_table_from_statements(&program.statements, &mut table, tokens, text);
+/// Records every definition in the program, including those nested in
+/// blocks, match arms and methods
+struct DefinitionCollector<'a> {
+    table: SymbolTable,
+    tokens: &'a [Token],
+    text: &'a str,
+    checker: Option<&'a TypeChecker>,
+    /// Indices into `table.scopes` of the functions being walked
+    open_scopes: Vec<usize>,
+}
 
-    // Second pass: collect references from expressions
-    collect_references_from_statements(&program.statements, &mut table, tokens, text);
+impl DefinitionCollector<'_> {
+    fn declare_local(&mut self, name: &str) {
+        if let Some(&index) = self.open_scopes.last() {
+            self.table.scopes[index].names.insert(name.to_string());
+        }
+    }
 
-    table
+    fn add_parameters(&mut self, func: &Function) {
+        for param in &func.params {
+            self.declare_local(param.as_ref().name.as_ref());
+            let ty = param.as_ref().ty.as_ref().map(annotation_type_name);
+            self.table
+                .add_parameter(param.as_ref().name.as_ref().clone(), *param.span(), ty);
+        }
+    }
 }
 
-/// Recursively extract symbol definitions from statements
-fn build_symbol_table_from_statements(
-    statements: &[Node<Statement>],
-    table: &mut SymbolTable,
-    tokens: &[Token],
-    text: &str,
-) {
-    for stmt in statements {
+impl Visitor for DefinitionCollector<'_> {
+    fn visit_statement(&mut self, stmt: &Node<Statement>) {
         let span = stmt.span();
         match stmt.as_ref() {
             Statement::Let { name, ty, expr, .. } => {
                 let ty_str = ty
                     .as_ref()
-                    .map(|ty| format_type(ty.as_ref()))
-                    .or_else(|| infer_type_from_expr(expr.as_ref()));
-                table.add_variable(name.as_ref().clone(), *span, ty_str);
+                    .map(annotation_type_name)
+                    .or_else(|| inferred_type_name(self.checker, expr));
+                self.table
+                    .add_variable(name.as_ref().clone(), *span, ty_str);
+                self.declare_local(name.as_ref());
             }
-
             Statement::Function(func) => {
-                // Find function name span from tokens
-                if let Some(span) = find_name_span(&func.as_ref().name, tokens, text) {
+                // The function's name is the first matching identifier in its header
+                if let Some(name_span) =
+                    find_name_span_from(&func.as_ref().name, self.tokens, *span)
+                {
                     let sig = format_function_signature(func.as_ref());
                     let callable = Some(CallableInfo::from_function(func.as_ref()));
-                    table.add_function(func.as_ref().name.clone(), span, Some(sig), callable);
-                }
-                for param in &func.as_ref().params {
-                    let ty = param
-                        .as_ref()
-                        .ty
-                        .as_ref()
-                        .map(|ty| format_type(ty.as_ref()));
-                    table.add_parameter(param.as_ref().name.as_ref().clone(), *param.span(), ty);
+                    self.table.add_function(
+                        func.as_ref().name.clone(),
+                        name_span,
+                        Some(sig),
+                        callable,
+                    );
+                    self.table
+                        .functions
+                        .insert(func.as_ref().name.clone(), name_span);
                 }
-                build_symbol_table_from_statements(
-                    &func.as_ref().body.as_ref().statements,
-                    table,
-                    tokens,
-                    text,
-                );
             }
             Statement::Struct { name, methods, .. } => {
-                if let Some(span) = find_name_span(name, tokens, text) {
-                    table.add_struct(name.clone(), span);
+                if let Some(span) = find_name_span(name, self.tokens, self.text) {
+                    self.table.add_struct(name.clone(), span);
                 }
                 for method in methods {
-                    if let Some(span) = find_name_span(&method.as_ref().name, tokens, text) {
+                    if let Some(span) =
+                        find_name_span(&method.as_ref().name, self.tokens, self.text)
+                    {
                         let sig = format_function_signature(method.as_ref());
                         let callable = Some(CallableInfo::from_function(method.as_ref()));
-                        table.add_method(method.as_ref().name.clone(), span, Some(sig), callable);
+                        self.table.add_method(
+                            method.as_ref().name.clone(),
+                            span,
+                            Some(sig),
+                            callable,
+                        );
                     }
                 }
             }
             Statement::Enum { name, .. } => {
-                if let Some(span) = find_name_span(name, tokens, text) {
-                    table.add_enum(name.clone(), span);
+                if let Some(span) = find_name_span(name, self.tokens, self.text) {
+                    self.table.add_enum(name.clone(), span);
                 }
             }
             Statement::TypeAlias { name, .. } => {
-                if let Some(span) = find_name_span(name, tokens, text) {
-                    table.add_type_alias(name.clone(), span);
+                if let Some(span) = find_name_span(name, self.tokens, self.text) {
+                    self.table.add_type_alias(name.clone(), span);
                 }
             }
-            Statement::If {
-                then_block,
-                elif_blocks,
-                else_block,
-                ..
-            } => {
-                build_symbol_table_from_statements(
-                    &then_block.as_ref().statements,
-                    table,
-                    tokens,
-                    text,
-                );
-                for (_, block) in elif_blocks {
-                    build_symbol_table_from_statements(
-                        &block.as_ref().statements,
-                        table,
-                        tokens,
-                        text,
-                    );
-                }
-                if let Some(block) = else_block {
-                    build_symbol_table_from_statements(
-                        &block.as_ref().statements,
-                        table,
-                        tokens,
-                        text,
-                    );
-                }
+            Statement::For { var, .. } => {
+                self.table.add_variable(var.as_ref().clone(), *span, None);
+                self.declare_local(var.as_ref());
             }
-            Statement::For { var, body, .. } => {
-                table.add_variable(var.as_ref().clone(), *span, None);
-                build_symbol_table_from_statements(&body.as_ref().statements, table, tokens, text);
+            _ => {}
+        }
+        walk_statement(self, stmt);
+    }
+
+    fn visit_function(&mut self, func: &Node<Function>) {
+        self.table.scopes.push(LocalScope {
+            span: *func.span(),
+            names: BTreeSet::new(),
+        });
+        self.open_scopes.push(self.table.scopes.len() - 1);
+        self.add_parameters(func.as_ref());
+        walk_function(self, func);
+        self.open_scopes.pop();
+    }
+}
+
+/// Find span of a name in tokens (approximate)
+fn find_name_span(name: &str, tokens: &[Token], _text: &str) -> Option<Span> {
+    for token in tokens {
+        if let otterc_lexer::token::TokenKind::Identifier(id) = token.kind()
+            && id == name
+        {
+            return Some(token.span());
+        }
+    }
+    None
+}
+
+/// Span of the first identifier token `name` starting inside `within`
+fn find_name_span_from(name: &str, tokens: &[Token], within: Span) -> Option<Span> {
+    tokens
+        .iter()
+        .filter(|token| within.contains(token.span().start()))
+        .find(|token| {
+            matches!(token.kind(), otterc_lexer::token::TokenKind::Identifier(id) if id == name)
+        })
+        .map(Token::span)
+}
+
+/// Outline of a document: top-level functions, `let`s and types. Functions
+/// list their parameters and nested functions as children, and structs their
+/// methods.
+fn outline_symbols(program: &Program, tokens: &[Token], text: &str) -> Vec<DocumentSymbol> {
+    let mut symbols = Vec::new();
+    for stmt in &program.statements {
+        let span = *stmt.span();
+        match stmt.as_ref() {
+            Statement::Function(func) => symbols.push(function_outline(
+                func,
+                tokens,
+                text,
+                tower_lsp::lsp_types::SymbolKind::FUNCTION,
+            )),
+            Statement::Let { name, .. } => {
+                let selection = find_name_span_from(name.as_ref(), tokens, span).unwrap_or(span);
+                symbols.push(outline_symbol(
+                    name.as_ref().clone(),
+                    None,
+                    tower_lsp::lsp_types::SymbolKind::VARIABLE,
+                    span_to_range(span, text),
+                    span_to_range(selection, text),
+                    Vec::new(),
+                ));
             }
-            Statement::While { body, .. } => {
-                build_symbol_table_from_statements(&body.as_ref().statements, table, tokens, text);
+            Statement::Struct { name, methods, .. } => {
+                let children = methods
+                    .iter()
+                    .map(|method| {
+                        function_outline(
+                            method,
+                            tokens,
+                            text,
+                            tower_lsp::lsp_types::SymbolKind::METHOD,
+                        )
+                    })
+                    .collect();
+                let selection = find_name_span_from(name, tokens, span).unwrap_or(span);
+                symbols.push(outline_symbol(
+                    name.clone(),
+                    None,
+                    tower_lsp::lsp_types::SymbolKind::STRUCT,
+                    span_to_range(span, text),
+                    span_to_range(selection, text),
+                    children,
+                ));
             }
-            Statement::Block(block) => {
-                build_symbol_table_from_statements(&block.as_ref().statements, table, tokens, text);
+            Statement::Enum { name, .. } => {
+                let selection = find_name_span_from(name, tokens, span).unwrap_or(span);
+                symbols.push(outline_symbol(
+                    name.clone(),
+                    None,
+                    tower_lsp::lsp_types::SymbolKind::ENUM,
+                    span_to_range(span, text),
+                    span_to_range(selection, text),
+                    Vec::new(),
+                ));
             }
             _ => {}
         }
     }
+    symbols
 }
 
-/// Collect references to symbols from expressions
-fn collect_references_from_statements(
-    statements: &[Node<Statement>],
-    table: &mut SymbolTable,
+fn function_outline(
+    func: &Node<Function>,
     tokens: &[Token],
     text: &str,
-) {
-    for stmt in statements {
-        let span = stmt.span();
+    kind: tower_lsp::lsp_types::SymbolKind,
+) -> DocumentSymbol {
+    let span = *func.span();
+    let selection = find_name_span_from(&func.as_ref().name, tokens, span).unwrap_or(span);
+
+    let mut children: Vec<DocumentSymbol> = func
+        .as_ref()
+        .params
+        .iter()
+        .map(|param| {
+            let range = span_to_range(*param.span(), text);
+            outline_symbol(
+                param.as_ref().name.as_ref().clone(),
+                param
+                    .as_ref()
+                    .ty
+                    .as_ref()
+                    .map(|ty| format_type(ty.as_ref())),
+                tower_lsp::lsp_types::SymbolKind::VARIABLE,
+                range,
+                range,
+                Vec::new(),
+            )
+        })
+        .collect();
+
+    let mut nested = NestedFunctions::default();
+    nested.visit_block(&func.as_ref().body);
+    children.extend(nested.found.into_iter().map(|inner| {
+        function_outline(
+            inner,
+            tokens,
+            text,
+            tower_lsp::lsp_types::SymbolKind::FUNCTION,
+        )
+    }));
+
+    outline_symbol(
+        func.as_ref().name.clone(),
+        Some(format_function_signature(func.as_ref())),
+        kind,
+        span_to_range(span, text),
+        span_to_range(selection, text),
+        children,
+    )
+}
+
+fn outline_symbol(
+    name: String,
+    detail: Option<String>,
+    kind: tower_lsp::lsp_types::SymbolKind,
+    range: Range,
+    selection_range: Range,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    #[expect(
+        deprecated,
+        reason = "We are not using this deprecated field but it's required for constructing DocumentSymbol"
+    )]
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        range,
+        selection_range,
+        children: (!children.is_empty()).then_some(children),
+        deprecated: None,
+        tags: None,
+    }
+}
+
+/// Functions declared directly inside a block, at any statement depth but
+/// not inside another nested function
+#[derive(Default)]
+struct NestedFunctions<'a> {
+    found: Vec<&'a Node<Function>>,
+}
+
+impl<'a> NestedFunctions<'a> {
+    fn visit_block(&mut self, block: &'a Node<otterc_ast::nodes::Block>) {
+        for stmt in &block.as_ref().statements {
+            self.visit_statement(stmt);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &'a Node<Statement>) {
         match stmt.as_ref() {
-            Statement::Function(func) => {
-                collect_references_from_expr(
-                    &Expr::Call {
-                        func: Box::new(Node::new(
-                            Expr::Identifier(func.as_ref().name.clone()),
-                            *span,
-                        )),
-                        args: vec![],
-                    },
-                    table,
-                    tokens,
-                    text,
-                );
-                collect_references_from_statements(
-                    &func.as_ref().body.as_ref().statements,
-                    table,
-                    tokens,
-                    text,
-                );
-            }
-            Statement::Let { expr, .. } | Statement::Expr(expr) | Statement::Return(Some(expr)) => {
-                collect_references_from_expr(expr.as_ref(), table, tokens, text);
-            }
+            Statement::Function(func) => self.found.push(func),
             Statement::If {
-                cond,
                 then_block,
                 elif_blocks,
                 else_block,
                 ..
             } => {
-                collect_references_from_expr(cond.as_ref(), table, tokens, text);
-                collect_references_from_statements(
-                    &then_block.as_ref().statements,
-                    table,
-                    tokens,
-                    text,
-                );
-                for (cond, block) in elif_blocks {
-                    collect_references_from_expr(cond.as_ref(), table, tokens, text);
-                    collect_references_from_statements(
-                        &block.as_ref().statements,
-                        table,
-                        tokens,
-                        text,
-                    );
+                self.visit_block(then_block);
+                for (_, block) in elif_blocks {
+                    self.visit_block(block);
                 }
                 if let Some(block) = else_block {
-                    collect_references_from_statements(
-                        &block.as_ref().statements,
-                        table,
-                        tokens,
-                        text,
-                    );
+                    self.visit_block(block);
                 }
             }
-            Statement::For { iterable, body, .. } => {
-                collect_references_from_expr(iterable.as_ref(), table, tokens, text);
-                collect_references_from_statements(&body.as_ref().statements, table, tokens, text);
-            }
-            Statement::While { cond, body } => {
-                collect_references_from_expr(cond.as_ref(), table, tokens, text);
-                collect_references_from_statements(&body.as_ref().statements, table, tokens, text);
-            }
+            Statement::For { body, .. } | Statement::While { body, .. } => self.visit_block(body),
+            Statement::Block(block) => self.visit_block(block),
             _ => {}
         }
     }
 }
 
-/// Collect references from an expression
-fn collect_references_from_expr(
-    expr: &Expr,
-    table: &mut SymbolTable,
-    tokens: &[Token],
+/// Definition of the name under the cursor. A name followed by `(` is
+/// resolved as a function call first.
+fn definition_at(text: &str, symbol_table: &SymbolTable, position: Position) -> Option<Span> {
+    let name = word_at_position(text, position)?;
+    let function = symbol_table.find_function(&name);
+    if function.is_some() && is_call_at(text, position) {
+        return function;
+    }
+    symbol_table
+        .find_definition(&name)
+        .map(|info| info.span)
+        .or(function)
+}
+
+/// Every identifier token spelling the word at `position`, in document order.
+/// Without `include_declaration` the name tokens of its definitions are left
+/// out.
+fn reference_spans(
     text: &str,
-) {
-    match expr {
-        Expr::Identifier(name) => {
-            if let Some(span) = find_name_span(name, tokens, text) {
-                table.add_reference(name.clone(), span);
-            }
-        }
-        Expr::Call { func, args } => {
-            collect_references_from_expr(func.as_ref().as_ref(), table, tokens, text);
-            for arg in args {
-                collect_references_from_expr(arg.as_ref(), table, tokens, text);
+    symbol_table: &SymbolTable,
+    position: Position,
+    include_declaration: bool,
+) -> Option<Vec<Span>> {
+    let name = word_at_position(text, position)?;
+    let tokens = tokenize(text).ok()?;
+
+    let declarations: Vec<Span> = symbol_table
+        .find_definition(&name)
+        .and_then(|info| find_name_span_from(&name, &tokens, info.span))
+        .into_iter()
+        .chain(symbol_table.find_function(&name))
+        .collect();
+
+    let mut spans = Vec::new();
+    collect_identifier_spans(&tokens, &name, &mut spans);
+    spans.retain(|span| include_declaration || !declarations.contains(span));
+    Some(spans)
+}
+
+/// Spans of the identifier tokens spelling `name`, including those
+/// interpolated in f-strings
+fn collect_identifier_spans(tokens: &[Token], name: &str, out: &mut Vec<Span>) {
+    use otterc_lexer::token::{FStringSegment, TokenKind};
+
+    for token in tokens {
+        match token.kind() {
+            TokenKind::Identifier(id) if id == name => out.push(token.span()),
+            TokenKind::FString(segments) => {
+                for segment in segments {
+                    if let FStringSegment::Expr { tokens, .. } = segment {
+                        collect_identifier_spans(tokens, name, out);
+                    }
+                }
             }
+            _ => {}
         }
-        Expr::Member { object, .. } => {
-            collect_references_from_expr(object.as_ref().as_ref(), table, tokens, text);
-        }
-        Expr::Binary { left, right, .. } => {
-            collect_references_from_expr(left.as_ref().as_ref(), table, tokens, text);
-            collect_references_from_expr(right.as_ref().as_ref(), table, tokens, text);
+    }
+}
+
+/// Checks that `name` lexes as a single identifier
+fn validate_new_name(name: &str) -> std::result::Result<(), String> {
+    use otterc_lexer::token::TokenKind;
+
+    let Ok(tokens) = tokenize(name) else {
+        return Err(format!("`{name}` is not a valid identifier"));
+    };
+    let mut significant = tokens
+        .iter()
+        .filter(|token| !matches!(token.kind(), TokenKind::Newline | TokenKind::Eof));
+    match (significant.next(), significant.next()) {
+        (Some(token), None)
+            if matches!(
+                token.kind(),
+                TokenKind::Identifier(_) | TokenKind::UnicodeIdentifier(_)
+            ) =>
+        {
+            Ok(())
         }
-        Expr::Unary { expr, .. } => {
-            collect_references_from_expr(expr.as_ref().as_ref(), table, tokens, text);
+        (Some(token), None) if token.is_keyword() => Err(format!(
+            "`{name}` is a keyword and cannot be used as a name"
+        )),
+        _ => Err(format!("`{name}` is not a valid identifier")),
+    }
+}
+
+/// Identifier spans a rename of the word at `position` rewrites. A name
+/// declared locally in the function around the cursor is renamed only inside
+/// that function; any other name is renamed everywhere except in functions
+/// that declare their own local of the same name.
+fn rename_spans(text: &str, symbol_table: &SymbolTable, position: Position) -> Option<Vec<Span>> {
+    let all = reference_spans(text, symbol_table, position, true)?;
+    let name = word_at_position(text, position)?;
+    let offset = position_to_offset(text, position);
+
+    let spans = match symbol_table.local_scope_of(&name, offset) {
+        Some(scope) => all
+            .into_iter()
+            .filter(|span| {
+                symbol_table
+                    .local_scope_of(&name, span.start())
+                    .map(|s| s.span)
+                    == Some(scope.span)
+            })
+            .collect(),
+        None => all
+            .into_iter()
+            .filter(|span| symbol_table.local_scope_of(&name, span.start()).is_none())
+            .collect(),
+    };
+    Some(spans)
+}
+
+/// A semantic token at an absolute position, before delta encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PositionedToken {
+    line: u32,
+    start: u32,
+    length: u32,
+    token_type: u32,
+}
+
+/// Semantic tokens for `text`, in document order. Identifiers are typed by
+/// the symbol they name; unknown identifiers are highlighted only when called.
+/// F-string text is a string while the interpolated expressions are typed
+/// like any other code.
+fn semantic_tokens(text: &str, symbol_table: &SymbolTable) -> Vec<PositionedToken> {
+    let Ok(tokens) = tokenize(text) else {
+        return Vec::new();
+    };
+    let mut spans = Vec::new();
+    classify_tokens(&tokens, symbol_table, &mut spans);
+    spans.sort_by_key(|(span, _)| span.start());
+
+    // Walk the text once, splitting tokens that span lines since clients are
+    // not required to support multiline tokens. Columns and lengths are in
+    // UTF-16 code units, the protocol's default position encoding.
+    let mut positioned = Vec::new();
+    let (mut offset, mut line, mut column) = (0usize, 0u32, 0u32);
+    for (span, token_type) in spans {
+        if span.start() < offset || span.is_empty() {
+            continue;
         }
-        Expr::If {
-            cond,
-            then_branch,
-            else_branch,
-        } => {
-            collect_references_from_expr(cond.as_ref().as_ref(), table, tokens, text);
-            collect_references_from_expr(then_branch.as_ref().as_ref(), table, tokens, text);
-            if let Some(else_expr) = else_branch {
-                collect_references_from_expr(else_expr.as_ref().as_ref(), table, tokens, text);
+        for ch in text[offset..span.start()].chars() {
+            if ch == '\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += ch.len_utf16() as u32;
             }
         }
-        Expr::Array(elements) => {
-            for elem in elements {
-                collect_references_from_expr(elem.as_ref(), table, tokens, text);
+        for (index, piece) in text[span.start()..span.end()].split('\n').enumerate() {
+            if index > 0 {
+                line += 1;
+                column = 0;
             }
-        }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_references_from_expr(key.as_ref(), table, tokens, text);
-                collect_references_from_expr(value.as_ref(), table, tokens, text);
+            let length = piece.encode_utf16().count() as u32;
+            if length > 0 {
+                positioned.push(PositionedToken {
+                    line,
+                    start: column,
+                    length,
+                    token_type,
+                });
             }
+            column += length;
         }
-        _ => {}
+        offset = span.end();
     }
+    positioned
 }
 
-/// Find span of a name in tokens (approximate)
-fn find_name_span(name: &str, tokens: &[Token], _text: &str) -> Option<Span> {
-    for token in tokens {
-        if let otterc_lexer::token::TokenKind::Identifier(id) = token.kind()
-            && id == name
-        {
-            return Some(token.span());
-        }
+fn classify_tokens(tokens: &[Token], symbol_table: &SymbolTable, out: &mut Vec<(Span, u32)>) {
+    use otterc_lexer::token::{FStringSegment, TokenKind};
+
+    let index_of = |ty: SemanticTokenType| {
+        SEMANTIC_TOKEN_TYPES
+            .iter()
+            .position(|known| *known == ty)
+            .unwrap_or_default() as u32
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        let span = token.span();
+        let token_type = match token.kind() {
+            TokenKind::FString(segments) => {
+                let mut text_start = span.start();
+                for segment in segments {
+                    if let FStringSegment::Expr {
+                        tokens: inner,
+                        span: expr_span,
+                    } = segment
+                    {
+                        out.push((
+                            Span::new(text_start, expr_span.start()),
+                            index_of(SemanticTokenType::STRING),
+                        ));
+                        classify_tokens(inner, symbol_table, out);
+                        text_start = expr_span.end();
+                    }
+                }
+                out.push((
+                    Span::new(text_start, span.end()),
+                    index_of(SemanticTokenType::STRING),
+                ));
+                continue;
+            }
+            TokenKind::Identifier(name) | TokenKind::UnicodeIdentifier(name) => {
+                match symbol_table.get(name).map(|info| &info.kind) {
+                    Some(SymbolKind::Function | SymbolKind::Method) => SemanticTokenType::FUNCTION,
+                    Some(SymbolKind::Variable) => SemanticTokenType::VARIABLE,
+                    Some(SymbolKind::Parameter) => SemanticTokenType::PARAMETER,
+                    Some(SymbolKind::Struct) => SemanticTokenType::CLASS,
+                    Some(SymbolKind::Enum) => SemanticTokenType::ENUM,
+                    Some(SymbolKind::TypeAlias) => SemanticTokenType::TYPE,
+                    None if tokens
+                        .get(i + 1)
+                        .is_some_and(|next| matches!(next.kind(), TokenKind::LParen)) =>
+                    {
+                        SemanticTokenType::FUNCTION
+                    }
+                    None => continue,
+                }
+            }
+            TokenKind::Number(_) => SemanticTokenType::NUMBER,
+            TokenKind::StringLiteral(_) => SemanticTokenType::STRING,
+            TokenKind::Comment(_) => SemanticTokenType::COMMENT,
+            _ if token.is_keyword() => SemanticTokenType::KEYWORD,
+            _ if token.is_operator() => SemanticTokenType::OPERATOR,
+            _ => continue,
+        };
+        out.push((span, index_of(token_type)));
     }
-    None
+}
+
+/// The relative encoding the protocol expects: each token's line is relative
+/// to the previous token, and so is its start when both share a line
+fn delta_encode(tokens: &[PositionedToken]) -> Vec<SemanticToken> {
+    let (mut prev_line, mut prev_start) = (0, 0);
+    tokens
+        .iter()
+        .map(|token| {
+            let delta_line = token.line - prev_line;
+            let delta_start = if delta_line == 0 {
+                token.start - prev_start
+            } else {
+                token.start
+            };
+            (prev_line, prev_start) = (token.line, token.start);
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: token.length,
+                token_type: token.token_type,
+                token_modifiers_bitset: 0,
+            }
+        })
+        .collect()
+}
+
+/// Hover for the word at `position`: what kind of symbol it names and its
+/// declared or inferred type. Words that name no symbol get no hover.
+fn hover_at(text: &str, symbol_table: &SymbolTable, position: Position) -> Option<Hover> {
+    let name = word_at_position(text, position)?;
+    let symbol_info = symbol_table.find_definition(&name)?;
+    let kind_str = match symbol_info.kind {
+        SymbolKind::Function => "function",
+        SymbolKind::Variable => "variable",
+        SymbolKind::Parameter => "parameter",
+        SymbolKind::Struct => "struct",
+        SymbolKind::Enum => "enum",
+        SymbolKind::TypeAlias => "type",
+        SymbolKind::Method => "method",
+    };
+    let detail = symbol_info
+        .ty
+        .as_ref()
+        .map(|ty| format!("{}: {}", kind_str, ty))
+        .unwrap_or_else(|| kind_str.to_string());
+
+    Some(Hover {
+        contents: HoverContents::Scalar(MarkedString::String(detail)),
+        range: Some(span_to_range(symbol_info.span, text)),
+    })
+}
+
+/// Whether the word at `position` is directly followed by an argument list
+fn is_call_at(text: &str, position: Position) -> bool {
+    let offset = position_to_offset(text, position);
+    text.get(offset..).is_some_and(|rest| {
+        rest.trim_start_matches(|c: char| c.is_alphanumeric() || c == '_')
+            .trim_start_matches([' ', '\t'])
+            .starts_with('(')
+    })
 }
 
 /// Format function signature for display
@@ -1271,37 +1637,55 @@ fn format_type(ty: &Type) -> String {
     }
 }
 
-/// Infer type hint from expression (basic)
-fn infer_type_from_expr(_expr: &Expr) -> Option<String> {
-    None // Could be enhanced with type inference
+/// An annotated type, named the way the checker names inferred ones
+fn annotation_type_name(ty: &Node<Type>) -> String {
+    TypeInfo::from(ty).display_name()
+}
+
+/// Type the checker inferred for `expr`, if it got that far
+fn inferred_type_name(checker: Option<&TypeChecker>, expr: &Node<Expr>) -> Option<String> {
+    checker
+        .and_then(|checker| checker.type_at(*expr.span()))
+        .map(|ty| ty.display_name())
 }
 
-/// Compute diagnostics and build symbol table from source text
-fn compute_lsp_diagnostics_and_symbols(text: &str) -> (Vec<Diagnostic>, SymbolTable) {
+/// Compute diagnostics and build symbol table from source text. There is no
+/// symbol table for text that does not lex or parse.
+fn compute_lsp_diagnostics_and_symbols(text: &str) -> (Vec<Diagnostic>, Option<SymbolTable>) {
     let source_id = "lsp";
     match tokenize(text) {
         Ok(tokens) => match parse(&tokens) {
             Ok(program) => {
-                // Build symbol table from the parsed program
-                let symbol_table = build_symbol_table(&program, &tokens, text);
+                let mut checker = TypeChecker::new().with_registry(SymbolRegistry::global());
+                let type_check = checker.check_program(&program);
+
+                // Build symbol table from the parsed program; types inferred
+                // before any error are kept
+                let symbol_table = build_symbol_table(&program, &tokens, text, Some(&checker));
 
                 let diagnostics = {
-                    let mut checker = TypeChecker::new().with_registry(SymbolRegistry::global());
-                    if checker.check_program(&program).is_err() {
+                    let mut diagnostics = if type_check.is_err() {
                         otterc_typecheck::diagnostics_from_type_errors(
                             checker.errors(),
                             source_id,
                             text,
                         )
-                        .into_iter()
-                        .map(|diag| otter_diag_to_lsp(DiagnosticKind::Type, &diag, text))
-                        .collect()
                     } else {
                         Vec::new()
-                    }
+                    };
+                    diagnostics.extend(otterc_typecheck::diagnostics_from_type_warnings(
+                        checker.warnings(),
+                        source_id,
+                        text,
+                    ));
+                    diagnostics.sort_by_key(|diag| diag.span());
+                    diagnostics
+                        .into_iter()
+                        .map(|diag| otter_diag_to_lsp(DiagnosticKind::Type, &diag, text))
+                        .collect::<Vec<_>>()
                 };
 
-                (diagnostics, symbol_table)
+                (diagnostics, Some(symbol_table))
             }
             Err(errors) => {
                 let diagnostics = errors
@@ -1314,7 +1698,7 @@ fn compute_lsp_diagnostics_and_symbols(text: &str) -> (Vec<Diagnostic>, SymbolTa
                         )
                     })
                     .collect();
-                (diagnostics, SymbolTable::new())
+                (diagnostics, None)
             }
         },
         Err(errors) => {
@@ -1328,7 +1712,7 @@ fn compute_lsp_diagnostics_and_symbols(text: &str) -> (Vec<Diagnostic>, SymbolTa
                     )
                 })
                 .collect();
-            (diagnostics, SymbolTable::new())
+            (diagnostics, None)
         }
     }
 }
@@ -1422,10 +1806,40 @@ fn otter_diag_to_lsp(kind: DiagnosticKind, diag: &OtterDiagnostic, text: &str) -
         message,
         related_information: None,
         tags: None,
-        data: None,
+        data: diag
+            .replacement()
+            .map(|replacement| replacement_to_data(replacement, text)),
     }
 }
 
+/// Encodes a diagnostic's replacement into the LSP diagnostic's `data`, so
+/// that `code_action` can turn it back into a quick fix.
+fn replacement_to_data(replacement: &Replacement, text: &str) -> serde_json::Value {
+    serde_json::to_value(TextEdit {
+        range: span_to_range(replacement.span, text),
+        new_text: replacement.text.clone(),
+    })
+    .unwrap_or_default()
+}
+
+/// The quick fix carried in a diagnostic's `data`, if any.
+fn quick_fix(uri: &Url, diagnostic: &Diagnostic) -> Option<CodeAction> {
+    let edit: TextEdit = serde_json::from_value(diagnostic.data.clone()?).ok()?;
+    Some(CodeAction {
+        title: format!("Replace with `{}`", edit.new_text),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+            ..WorkspaceEdit::default()
+        }),
+        command: None,
+        is_preferred: Some(true),
+        disabled: None,
+        data: None,
+    })
+}
+
 fn snippet_with_highlight(text: &str, span: Span) -> Option<String> {
     if span.start() >= text.len() {
         return None;
@@ -1599,7 +2013,7 @@ for i in [1, 2, 3]:
         match tokenize(test_code) {
             Ok(tokens) => match parse(&tokens) {
                 Ok(program) => {
-                    let symbol_table = build_symbol_table(&program, &tokens, test_code);
+                    let symbol_table = build_symbol_table(&program, &tokens, test_code, None);
 
                     assert!(
                         symbol_table.find_definition("x").is_some(),
@@ -1655,7 +2069,7 @@ for i in [1, 2, 3]:
         match tokenize(test_code) {
             Ok(tokens) => match parse(&tokens) {
                 Ok(program) => {
-                    let symbol_table = build_symbol_table(&program, &tokens, test_code);
+                    let symbol_table = build_symbol_table(&program, &tokens, test_code, None);
 
                     let x_info = symbol_table.find_definition("x");
                     assert!(x_info.is_some(), "Should find definition for 'x'");
@@ -1675,4 +2089,312 @@ for i in [1, 2, 3]:
             }
         }
     }
+
+    #[test]
+    fn test_goto_definition_of_called_function() {
+        let text = "fn main():\n    foo()\n\nfn foo():\n    pass\n";
+        let tokens = tokenize(text).expect("tokenize");
+        let program = parse(&tokens).expect("parse");
+        let symbol_table = build_symbol_table(&program, &tokens, text, None);
+
+        let header = text.rfind("foo").expect("header");
+        let header_span = Span::new(header, header + 3);
+        assert_eq!(symbol_table.find_function("foo"), Some(header_span));
+
+        // The call comes before the definition, so the header is not the first `foo`
+        let call = Position {
+            line: 1,
+            character: 5,
+        };
+        assert!(is_call_at(text, call));
+        assert_eq!(definition_at(text, &symbol_table, call), Some(header_span));
+    }
+
+    #[test]
+    fn test_hover_reports_kind_and_type() {
+        let text = "fn scale(factor: f64) -> float:\n    let total = 2.5 * factor\n    return total\n\nfn main():\n    print(\"hi\")\n";
+        let table = compute_lsp_diagnostics_and_symbols(text)
+            .1
+            .expect("document parses");
+        let hover_text = |line, character| {
+            hover_at(text, &table, Position { line, character }).map(|hover| match hover.contents {
+                HoverContents::Scalar(MarkedString::String(detail)) => detail,
+                other => panic!("unexpected hover contents: {other:?}"),
+            })
+        };
+
+        assert_eq!(hover_text(2, 12).as_deref(), Some("variable: float"));
+        // Annotations and inferred types are named alike, whatever the
+        // annotation's spelling
+        assert_eq!(hover_text(1, 24).as_deref(), Some("parameter: float"));
+        assert!(hover_text(0, 4).is_some_and(|detail| detail.starts_with("function: ")));
+        // A word that names no symbol has no hover
+        assert_eq!(hover_text(5, 12), None);
+    }
+
+    #[test]
+    fn test_references_list_every_use() {
+        let text = "fn main():\n    let count = 1\n    count = count + 1\n    print(count)\n";
+        let table = compute_lsp_diagnostics_and_symbols(text)
+            .1
+            .expect("document parses");
+        let at = Position {
+            line: 3,
+            character: 11,
+        };
+
+        let lines = |spans: Vec<Span>| -> Vec<u32> {
+            spans
+                .into_iter()
+                .map(|span| span_to_range(span, text).start.line)
+                .collect()
+        };
+        let all = reference_spans(text, &table, at, true).expect("word under cursor");
+        assert_eq!(lines(all), [1, 2, 2, 3]);
+        let uses = reference_spans(text, &table, at, false).expect("word under cursor");
+        assert_eq!(lines(uses), [2, 2, 3]);
+    }
+
+    #[test]
+    fn test_rename_stays_in_local_scope() {
+        let text = "fn first():\n    let total = 1\n    print(total)\n\nfn second():\n    let total = 2\n    print(total)\n";
+        let table = compute_lsp_diagnostics_and_symbols(text)
+            .1
+            .expect("document parses");
+        let spans = rename_spans(
+            text,
+            &table,
+            Position {
+                line: 2,
+                character: 12,
+            },
+        )
+        .expect("word under cursor");
+
+        let lines: Vec<u32> = spans
+            .into_iter()
+            .map(|span| span_to_range(span, text).start.line)
+            .collect();
+        assert_eq!(lines, [1, 2]);
+
+        assert!(validate_new_name("grand_total").is_ok());
+        assert!(validate_new_name("while").unwrap_err().contains("keyword"));
+        assert!(validate_new_name("two words").is_err());
+    }
+
+    #[test]
+    fn test_semantic_tokens_classify_and_delta_encode() {
+        let text = "fn greet(name: string):\n    print(f\"hi {name}!\")\n";
+        let table = compute_lsp_diagnostics_and_symbols(text)
+            .1
+            .expect("document parses");
+        let tokens = semantic_tokens(text, &table);
+
+        let kind_at = |line, start| {
+            tokens
+                .iter()
+                .find(|token| token.line == line && token.start == start)
+                .map(|token| {
+                    SEMANTIC_TOKEN_TYPES[token.token_type as usize]
+                        .as_str()
+                        .to_owned()
+                })
+        };
+        assert_eq!(kind_at(0, 0).as_deref(), Some("keyword"));
+        assert_eq!(kind_at(0, 3).as_deref(), Some("function"));
+        assert_eq!(kind_at(0, 9).as_deref(), Some("parameter"));
+        // f"hi {name}!": the text around the interpolation is a string, the
+        // interpolated name is a parameter
+        assert_eq!(kind_at(1, 10).as_deref(), Some("string"));
+        assert_eq!(kind_at(1, 16).as_deref(), Some("parameter"));
+        assert_eq!(kind_at(1, 21).as_deref(), Some("string"));
+
+        // Columns count UTF-16 code units, so a non-BMP character before a
+        // token shifts it by two
+        let wide = semantic_tokens("let s = \"🦦\"\nlet n = 1\n", &SymbolTable::new());
+        let wide_kinds: Vec<(u32, u32, u32)> = wide
+            .iter()
+            .map(|token| (token.line, token.start, token.length))
+            .collect();
+        assert!(wide_kinds.contains(&(0, 8, 4)), "{wide_kinds:?}");
+
+        let encoded = delta_encode(&tokens);
+        let mut line = 0;
+        let mut start = 0;
+        for (token, delta) in tokens.iter().zip(&encoded) {
+            line += delta.delta_line;
+            start = if delta.delta_line == 0 {
+                start + delta.delta_start
+            } else {
+                delta.delta_start
+            };
+            assert_eq!((line, start), (token.line, token.start));
+        }
+    }
+
+    #[test]
+    fn test_type_errors_point_at_their_source() {
+        let text = "fn main():\n    let label: string = 42\n";
+        let (diagnostics, _) = compute_lsp_diagnostics_and_symbols(text);
+        let diagnostic = diagnostics
+            .iter()
+            .find(|diag| diag.severity == Some(DiagnosticSeverity::ERROR))
+            .expect("mismatched let should be reported");
+        assert_eq!(diagnostic.range.start.line, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_revision_keeps_last_good_symbols() {
+        let (service, _socket) = LspService::new(Backend::new);
+        let backend = service.inner();
+        let uri = Url::parse("file:///edit.ot").unwrap();
+
+        let first = backend
+            .upsert_document(uri.clone(), "fn main():\n    let ready = true\n".into())
+            .await;
+        backend.publish_diagnostics(uri.clone(), first).await;
+
+        // A half-typed edit does not parse, and one typed after it makes the
+        // half-typed revision stale
+        let broken = backend
+            .upsert_document(uri.clone(), "fn main(:\n".into())
+            .await;
+        let latest = backend
+            .upsert_document(uri.clone(), "fn main(\n".into())
+            .await;
+        assert!(!backend.is_current(&uri, broken).await);
+        backend.publish_diagnostics(uri.clone(), broken).await;
+        backend.publish_diagnostics(uri.clone(), latest).await;
+
+        let state = backend.state.read().await;
+        assert!(state.symbol_tables[&uri].find_definition("ready").is_some());
+    }
+
+    #[test]
+    fn test_outline_nests_params_and_functions() {
+        let text = "let limit = 3\n\nfn outer(a: int, b):\n    pass\n\nstruct Point:\n    x: float\n\n    fn norm(self) -> float:\n        return self.x\n";
+        let tokens = tokenize(text).expect("tokenize");
+        let program = parse(&tokens).expect("parse");
+        let outline = outline_symbols(&program, &tokens, text);
+
+        let names: Vec<&str> = outline.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["limit", "outer", "Point"]);
+        assert_eq!(outline[0].kind, tower_lsp::lsp_types::SymbolKind::VARIABLE);
+
+        let outer = &outline[1];
+        assert_eq!(outer.kind, tower_lsp::lsp_types::SymbolKind::FUNCTION);
+        assert_eq!(
+            outer.selection_range.start,
+            Position {
+                line: 2,
+                character: 3
+            }
+        );
+        let children = outer.children.as_deref().expect("outer has children");
+        let child_names: Vec<&str> = children.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(child_names, ["a", "b"]);
+        assert_eq!(children[0].detail.as_deref(), Some("int"));
+
+        let methods = outline[2].children.as_deref().expect("struct has methods");
+        assert_eq!(methods[0].name, "norm");
+        assert_eq!(methods[0].kind, tower_lsp::lsp_types::SymbolKind::METHOD);
+    }
+
+    #[test]
+    fn test_outline_nests_functions_declared_in_bodies() {
+        // The grammar only accepts `fn` at the top level, so move `helper`
+        // into the `if` inside `outer` by hand
+        let text =
+            "fn outer(a: int):\n    if a > 0:\n        pass\n\nfn helper(b: int):\n    pass\n";
+        let tokens = tokenize(text).expect("tokenize");
+        let mut program = parse(&tokens).expect("parse");
+        let helper = program.statements.pop().expect("helper");
+        let Statement::Function(outer) = program.statements[0].as_mut() else {
+            panic!("expected outer function");
+        };
+        let Statement::If { then_block, .. } = outer.as_mut().body.as_mut().statements[0].as_mut()
+        else {
+            panic!("expected if statement");
+        };
+        then_block.as_mut().statements.push(helper);
+
+        let outline = outline_symbols(&program, &tokens, text);
+        assert_eq!(outline.len(), 1);
+        let children = outline[0].children.as_deref().expect("outer has children");
+        let child_names: Vec<&str> = children.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(child_names, ["a", "helper"]);
+        assert_eq!(children[1].kind, tower_lsp::lsp_types::SymbolKind::FUNCTION);
+        let helper_params = children[1].children.as_deref().expect("helper has params");
+        assert_eq!(helper_params[0].name, "b");
+    }
+
+    #[test]
+    fn test_symbol_table_covers_nested_branches() {
+        let test_code = r#"
+struct Counter:
+    count: int
+
+    fn bump(self, step: int) -> int:
+        let next = self.count + step
+        return next
+
+fn describe(n: int) -> str:
+    match n:
+        case 0:
+            let zero = "none"
+            return zero
+        case _:
+            return f"{n} items"
+"#;
+        let tokens = tokenize(test_code).expect("tokenize");
+        let program = parse(&tokens).expect("parse");
+        let symbol_table = build_symbol_table(&program, &tokens, test_code, None);
+
+        for name in ["step", "next", "zero"] {
+            assert!(
+                symbol_table.find_definition(name).is_some(),
+                "'{name}' should be in symbol table"
+            );
+        }
+        let uses = reference_spans(
+            test_code,
+            &symbol_table,
+            Position {
+                line: 8,
+                character: 12,
+            },
+            true,
+        )
+        .expect("word under cursor");
+        assert_eq!(
+            uses.len(),
+            3,
+            "f-string interpolations should count as references"
+        );
+    }
+
+    #[test]
+    fn test_suggestion_becomes_quick_fix() {
+        let text = "fn main():\n    let count = 1\n    let total = cuont + 1\n";
+        let (diagnostics, _) = compute_lsp_diagnostics_and_symbols(text);
+        let uri = Url::parse("file:///main.ot").expect("valid uri");
+
+        let action = diagnostics
+            .iter()
+            .find_map(|diag| quick_fix(&uri, diag))
+            .expect("undefined variable should offer a quick fix");
+        assert_eq!(action.title, "Replace with `count`");
+        let edits = action
+            .edit
+            .and_then(|edit| edit.changes)
+            .and_then(|mut changes| changes.remove(&uri))
+            .expect("quick fix edits the document");
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: Range::new(Position::new(2, 16), Position::new(2, 21)),
+                new_text: "count".to_string(),
+            }]
+        );
+    }
 }
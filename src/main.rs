@@ -11,10 +11,10 @@ fn main() -> anyhow::Result<()> {
         {
             std::process::exit(1);
         }
-        // For other unexpected errors, print them.
+        // For other unexpected errors, print them along with their causes.
         #[expect(clippy::print_stderr, reason = "TODO: Use robust logging")]
         {
-            eprintln!("Error: {}", msg);
+            eprintln!("Error: {:#}", e);
         }
         std::process::exit(1);
     }